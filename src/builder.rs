@@ -0,0 +1,501 @@
+//! Construcción de comprobantes CFDI 4.0 nuevos, calculando sus importes y totales.
+
+use std::fmt;
+
+use crate::catalogos::{
+    Exportacion, FormaPago, Impuesto, Meses, MetodoPago, Moneda, ObjetoImp, Periodicidad,
+    RegimenFiscal, TipoDeComprobante, TipoFactor, TipoRelacion, UsoCfdi,
+};
+use crate::rfc::Rfc;
+use crate::totales::{self, redondear};
+use crate::validacion::ErrorValidacion;
+use crate::{
+    CfdiRelacionado, CfdiRelacionados, Comprobante, Concepto, Conceptos, Decimal, Emisor,
+    ImpuestosConcepto, InformacionGlobal, NaiveDateTime, Receptor, Retencion, Retenciones,
+    Traslado, Traslados,
+};
+
+/// Decimales con los que se escribe la `TasaOCuota`, como en el catálogo c_TasaOCuota
+const DECIMALES_TASA: u32 = 6;
+
+/// Error al construir un comprobante con [`ComprobanteBuilder::build`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorConstruccion {
+    /// No se indicó un dato requerido (ej. `"Emisor"`)
+    Faltante(&'static str),
+
+    /// El comprobante no cumple las reglas de [`Comprobante::validate`]
+    Validacion(Vec<ErrorValidacion>),
+}
+
+impl fmt::Display for ErrorConstruccion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorConstruccion::Faltante(dato) => {
+                write!(f, "No se indicó {} del comprobante", dato)
+            }
+            ErrorConstruccion::Validacion(errores) => {
+                let errores: Vec<_> = errores.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", errores.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorConstruccion {}
+
+/// Construye un [`Comprobante`] 4.0 nuevo.
+///
+/// Solo se indican los datos de cada concepto (cantidad, valor unitario, descuento y las
+/// tasas de sus impuestos); al llamar [`ComprobanteBuilder::build`] se calculan:
+///
+/// - El `Importe` de cada concepto (`Cantidad` x `ValorUnitario`), y la `Base` e `Importe` de
+///   sus impuestos, sobre el importe menos el descuento.
+/// - El `SubTotal`, el `Descuento` y el `Total` del comprobante.
+/// - Los impuestos del comprobante, agrupados por `Impuesto`, `TipoFactor` y `TasaOCuota` (las
+///   retenciones solo por `Impuesto`), y sus totales.
+///
+/// Los importes se redondean a los decimales de la moneda (la mitad hacia arriba), y la
+/// `TasaOCuota` se escribe con 6 decimales. Si falta un dato requerido, o el comprobante no
+/// cumple las reglas de [`Comprobante::validate`], `build` regresa un [`ErrorConstruccion`].
+///
+/// La `Moneda` es MXN y la `Exportacion` 01 (No aplica) si no se indican otras.
+///
+/// ```rust
+/// use cfdi::catalogos::{
+///     FormaPago, Impuesto, MetodoPago, RegimenFiscal, TipoDeComprobante, TipoFactor, UsoCfdi,
+/// };
+/// use cfdi::{ComprobanteBuilder, ConceptoBuilder, Decimal};
+///
+/// let decimal = |valor: &str| valor.parse::<Decimal>().unwrap();
+///
+/// let cfdi = ComprobanteBuilder::new(TipoDeComprobante::Ingreso)
+///     .fecha("2024-05-20T13:45:10".parse().unwrap())
+///     .lugar_expedicion("45079")
+///     .forma_pago(FormaPago::TransferenciaElectronica)
+///     .metodo_pago(MetodoPago::PagoEnUnaExhibicion)
+///     .emisor(
+///         "EKU9003173C9",
+///         "ESCUELA KEMPER URGATE",
+///         RegimenFiscal::GeneralDeLeyPersonasMorales,
+///     )
+///     .receptor(
+///         "URE180429TM6",
+///         "UNIVERSIDAD ROBOTICA ESPAÑOLA",
+///         "65000",
+///         RegimenFiscal::GeneralDeLeyPersonasMorales,
+///         UsoCfdi::GastosEnGeneral,
+///     )
+///     .concepto(
+///         ConceptoBuilder::new("84111506", "E48", "Asesoría", decimal("3"), decimal("333.333"))
+///             .traslado(Impuesto::Iva, TipoFactor::Tasa, decimal("0.16")),
+///     )
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(cfdi.conceptos.concepto[0].importe.to_string(), "1000.00");
+/// assert_eq!(cfdi.subtotal.to_string(), "1000.00");
+/// assert_eq!(cfdi.total.to_string(), "1160.00");
+///
+/// let traslado = &cfdi.impuestos.as_ref().unwrap().get_traslados()[0];
+/// assert_eq!(traslado.tasa_o_cuota.unwrap().to_string(), "0.160000");
+/// assert_eq!(traslado.importe.unwrap().to_string(), "160.00");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComprobanteBuilder {
+    tipo_comprobante: TipoDeComprobante,
+    serie: Option<String>,
+    folio: Option<String>,
+    fecha: Option<NaiveDateTime>,
+    condiciones_de_pago: Option<String>,
+    lugar_expedicion: Option<String>,
+    moneda: Moneda,
+    tipo_cambio: Option<Decimal>,
+    forma_pago: Option<FormaPago>,
+    metodo_pago: Option<MetodoPago>,
+    exportacion: Exportacion,
+    informacion_global: Option<InformacionGlobal>,
+    cfdi_relacionados: Vec<CfdiRelacionados>,
+    emisor: Option<Emisor>,
+    receptor: Option<Receptor>,
+    conceptos: Vec<ConceptoBuilder>,
+}
+
+impl ComprobanteBuilder {
+    pub fn new(tipo_comprobante: TipoDeComprobante) -> Self {
+        ComprobanteBuilder {
+            tipo_comprobante,
+            serie: None,
+            folio: None,
+            fecha: None,
+            condiciones_de_pago: None,
+            lugar_expedicion: None,
+            moneda: Moneda::Mxn,
+            tipo_cambio: None,
+            forma_pago: None,
+            metodo_pago: None,
+            exportacion: Exportacion::NoAplica,
+            informacion_global: None,
+            cfdi_relacionados: vec![],
+            emisor: None,
+            receptor: None,
+            conceptos: vec![],
+        }
+    }
+
+    /// Serie y folio del comprobante, para control interno del emisor
+    pub fn serie_folio(mut self, serie: impl Into<String>, folio: impl Into<String>) -> Self {
+        self.serie = Some(serie.into());
+        self.folio = Some(folio.into());
+        self
+    }
+
+    /// Fecha de expedición, en la hora local del `LugarExpedicion` (ver [`fechas`](crate::fechas))
+    pub fn fecha(mut self, fecha: NaiveDateTime) -> Self {
+        self.fecha = Some(fecha);
+        self
+    }
+
+    /// Código postal del lugar de expedición
+    pub fn lugar_expedicion(mut self, codigo_postal: impl Into<String>) -> Self {
+        self.lugar_expedicion = Some(codigo_postal.into());
+        self
+    }
+
+    pub fn moneda(mut self, moneda: Moneda) -> Self {
+        self.moneda = moneda;
+        self
+    }
+
+    /// Tipo de cambio a MXN. Requerido cuando la moneda no es MXN ni XXX
+    pub fn tipo_cambio(mut self, tipo_cambio: Decimal) -> Self {
+        self.tipo_cambio = Some(tipo_cambio);
+        self
+    }
+
+    pub fn forma_pago(mut self, forma_pago: FormaPago) -> Self {
+        self.forma_pago = Some(forma_pago);
+        self
+    }
+
+    pub fn metodo_pago(mut self, metodo_pago: MetodoPago) -> Self {
+        self.metodo_pago = Some(metodo_pago);
+        self
+    }
+
+    pub fn exportacion(mut self, exportacion: Exportacion) -> Self {
+        self.exportacion = exportacion;
+        self
+    }
+
+    /// Condiciones comerciales de pago (ej. "Contado", "30 días")
+    pub fn condiciones_de_pago(mut self, condiciones: impl Into<String>) -> Self {
+        self.condiciones_de_pago = Some(condiciones.into());
+        self
+    }
+
+    /// Periodo de las operaciones, para una factura global a público en general
+    pub fn informacion_global(
+        mut self,
+        periodicidad: Periodicidad,
+        meses: Meses,
+        anio: u16,
+    ) -> Self {
+        self.informacion_global = Some(InformacionGlobal {
+            periodicidad,
+            meses,
+            anio,
+        });
+        self
+    }
+
+    /// Relaciona el comprobante con los CFDI de `uuids` (ej. las facturas a las que aplica una
+    /// nota de crédito). Los UUID con el mismo tipo de relación se agregan al mismo grupo.
+    pub fn cfdi_relacionados<S: Into<String>>(
+        mut self,
+        tipo_relacion: TipoRelacion,
+        uuids: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let uuids = uuids
+            .into_iter()
+            .map(|uuid| CfdiRelacionado { uuid: uuid.into() });
+        match self
+            .cfdi_relacionados
+            .iter_mut()
+            .find(|r| r.tipo_relacion == tipo_relacion)
+        {
+            Some(grupo) => grupo.cfdi_relacionado.extend(uuids),
+            None => self.cfdi_relacionados.push(CfdiRelacionados {
+                tipo_relacion,
+                cfdi_relacionado: uuids.collect(),
+            }),
+        }
+        self
+    }
+
+    pub fn emisor(
+        mut self,
+        rfc: impl Into<String>,
+        nombre: impl Into<String>,
+        regimen_fiscal: RegimenFiscal,
+    ) -> Self {
+        self.emisor = Some(Emisor {
+            rfc: Rfc::new(rfc),
+            nombre: Some(nombre.into()),
+            regimen_fiscal,
+            fac_atr_adquirente: None,
+        });
+        self
+    }
+
+    /// Receptor nacional, con el código postal de su domicilio fiscal
+    pub fn receptor(
+        mut self,
+        rfc: impl Into<String>,
+        nombre: impl Into<String>,
+        domicilio_fiscal: impl Into<String>,
+        regimen_fiscal: RegimenFiscal,
+        uso_cfdi: UsoCfdi,
+    ) -> Self {
+        self.receptor = Some(Receptor {
+            rfc: Rfc::new(rfc),
+            nombre: Some(nombre.into()),
+            domicilio_fiscal: Some(domicilio_fiscal.into()),
+            residencia_fiscal: None,
+            num_reg_id_trib: None,
+            regimen_fiscal: Some(regimen_fiscal),
+            uso_cfdi,
+        });
+        self
+    }
+
+    /// Agrega un concepto. Sus importes se calculan con los decimales de la moneda del
+    /// comprobante al llamar [`ComprobanteBuilder::build`].
+    pub fn concepto(mut self, concepto: ConceptoBuilder) -> Self {
+        self.conceptos.push(concepto);
+        self
+    }
+
+    /// Calcula los importes y totales, y regresa el comprobante si cumple las reglas de
+    /// [`Comprobante::validate`]. El comprobante no está sellado (ver [`Comprobante::sellar`]).
+    pub fn build(self) -> Result<Comprobante, ErrorConstruccion> {
+        let fecha = self.fecha.ok_or(ErrorConstruccion::Faltante("Fecha"))?;
+        let lugar_expedicion = self
+            .lugar_expedicion
+            .ok_or(ErrorConstruccion::Faltante("LugarExpedicion"))?;
+        let emisor = self.emisor.ok_or(ErrorConstruccion::Faltante("Emisor"))?;
+        let receptor = self
+            .receptor
+            .ok_or(ErrorConstruccion::Faltante("Receptor"))?;
+        if self.conceptos.is_empty() {
+            return Err(ErrorConstruccion::Faltante("Concepto"));
+        }
+
+        let decimales = totales::decimales(&self.moneda);
+        let concepto: Vec<Concepto> = self
+            .conceptos
+            .into_iter()
+            .map(|c| c.concepto(decimales))
+            .collect();
+        let totales = totales::calcular(&concepto, &self.moneda);
+
+        let cfdi = Comprobante {
+            version: "4.0".to_string(),
+            serie: self.serie,
+            folio: self.folio,
+            fecha,
+            sello: None,
+            forma_pago: self.forma_pago,
+            no_certificado: None,
+            certificado: None,
+            condiciones_de_pago: self.condiciones_de_pago,
+            subtotal: totales.subtotal,
+            descuento: totales.descuento,
+            moneda: self.moneda,
+            tipo_cambio: self.tipo_cambio,
+            total: totales.total,
+            tipo_comprobante: self.tipo_comprobante,
+            exportacion: Some(self.exportacion),
+            metodo_pago: self.metodo_pago,
+            lugar_expedicion,
+            confirmacion: None,
+            informacion_global: self.informacion_global,
+            cfdi_relacionados: self.cfdi_relacionados,
+            emisor,
+            receptor,
+            conceptos: Conceptos { concepto },
+            impuestos: totales.impuestos,
+            complemento: None,
+            addenda: vec![],
+            forma_original: None,
+        };
+
+        let errores = cfdi.validate();
+        if errores.is_empty() {
+            Ok(cfdi)
+        } else {
+            Err(ErrorConstruccion::Validacion(errores))
+        }
+    }
+}
+
+/// Concepto de un comprobante construido con [`ComprobanteBuilder`].
+///
+/// El `Importe` y los impuestos se calculan al construir el comprobante. El `ObjetoImp` es 02
+/// (Sí objeto de impuesto) si el concepto tiene impuestos y 01 si no, a menos que se indique
+/// otro.
+#[derive(Debug, Clone)]
+pub struct ConceptoBuilder {
+    clave_prod_serv: String,
+    clave_unidad: String,
+    descripcion: String,
+    cantidad: Decimal,
+    valor_unitario: Decimal,
+    no_identificacion: Option<String>,
+    unidad: Option<String>,
+    descuento: Option<Decimal>,
+    objeto_imp: Option<ObjetoImp>,
+    traslados: Vec<(Impuesto, TipoFactor, Option<Decimal>)>,
+    retenciones: Vec<(Impuesto, TipoFactor, Decimal)>,
+}
+
+impl ConceptoBuilder {
+    pub fn new(
+        clave_prod_serv: impl Into<String>,
+        clave_unidad: impl Into<String>,
+        descripcion: impl Into<String>,
+        cantidad: Decimal,
+        valor_unitario: Decimal,
+    ) -> Self {
+        ConceptoBuilder {
+            clave_prod_serv: clave_prod_serv.into(),
+            clave_unidad: clave_unidad.into(),
+            descripcion: descripcion.into(),
+            cantidad,
+            valor_unitario,
+            no_identificacion: None,
+            unidad: None,
+            descuento: None,
+            objeto_imp: None,
+            traslados: vec![],
+            retenciones: vec![],
+        }
+    }
+
+    /// Número de parte, SKU o identificador del producto
+    pub fn no_identificacion(mut self, no_identificacion: impl Into<String>) -> Self {
+        self.no_identificacion = Some(no_identificacion.into());
+        self
+    }
+
+    pub fn unidad(mut self, unidad: impl Into<String>) -> Self {
+        self.unidad = Some(unidad.into());
+        self
+    }
+
+    pub fn descuento(mut self, descuento: Decimal) -> Self {
+        self.descuento = Some(descuento);
+        self
+    }
+
+    pub fn objeto_imp(mut self, objeto_imp: ObjetoImp) -> Self {
+        self.objeto_imp = Some(objeto_imp);
+        self
+    }
+
+    /// Agrega un traslado de tipo Tasa o Cuota. La base es el importe del concepto menos su
+    /// descuento.
+    pub fn traslado(
+        mut self,
+        impuesto: Impuesto,
+        tipo_factor: TipoFactor,
+        tasa_o_cuota: Decimal,
+    ) -> Self {
+        self.traslados
+            .push((impuesto, tipo_factor, Some(tasa_o_cuota)));
+        self
+    }
+
+    /// Agrega un traslado Exento, que no tiene `TasaOCuota` ni `Importe`
+    pub fn exento(mut self, impuesto: Impuesto) -> Self {
+        self.traslados.push((impuesto, TipoFactor::Exento, None));
+        self
+    }
+
+    /// Agrega una retención de tipo Tasa. La base es el importe del concepto menos su
+    /// descuento.
+    ///
+    /// Las retenciones no pueden ser de tipo Exento.
+    pub fn retencion(mut self, impuesto: Impuesto, tasa: Decimal) -> Self {
+        self.retenciones.push((impuesto, TipoFactor::Tasa, tasa));
+        self
+    }
+
+    /// Agrega una retención de tipo Cuota. La base es el importe del concepto menos su
+    /// descuento.
+    pub fn retencion_cuota(mut self, impuesto: Impuesto, cuota: Decimal) -> Self {
+        self.retenciones.push((impuesto, TipoFactor::Cuota, cuota));
+        self
+    }
+
+    fn concepto(self, decimales: u32) -> Concepto {
+        let importe = redondear(self.cantidad * self.valor_unitario, decimales);
+        let descuento = self.descuento.map(|d| redondear(d, decimales));
+        let base = importe - descuento.unwrap_or_default();
+
+        let traslado: Vec<Traslado> = self
+            .traslados
+            .into_iter()
+            .map(|(impuesto, tipo_factor, tasa_o_cuota)| Traslado {
+                base: Some(base),
+                impuesto,
+                tipo_factor,
+                tasa_o_cuota: tasa_o_cuota.map(tasa),
+                importe: tasa_o_cuota.map(|t| redondear(base * t, decimales)),
+            })
+            .collect();
+        let retencion: Vec<Retencion> = self
+            .retenciones
+            .into_iter()
+            .map(|(impuesto, tipo_factor, tasa_o_cuota)| Retencion {
+                base: Some(base),
+                impuesto,
+                tipo_factor: Some(tipo_factor),
+                tasa_o_cuota: Some(tasa(tasa_o_cuota)),
+                importe: redondear(base * tasa_o_cuota, decimales),
+            })
+            .collect();
+
+        let impuestos =
+            (!traslado.is_empty() || !retencion.is_empty()).then(|| ImpuestosConcepto {
+                traslados: (!traslado.is_empty()).then_some(Traslados { traslado }),
+                retenciones: (!retencion.is_empty()).then_some(Retenciones { retencion }),
+            });
+        let objeto_imp = self.objeto_imp.unwrap_or(if impuestos.is_some() {
+            ObjetoImp::SiObjeto
+        } else {
+            ObjetoImp::NoObjeto
+        });
+
+        Concepto {
+            clave_product: self.clave_prod_serv,
+            no_identificacion: self.no_identificacion,
+            cantidad: self.cantidad,
+            clave_unidad: self.clave_unidad,
+            unidad: self.unidad,
+            descripcion: self.descripcion,
+            valor_unitario: self.valor_unitario,
+            importe,
+            descuento,
+            objeto_imp: Some(objeto_imp),
+            impuestos,
+        }
+    }
+}
+
+fn tasa(mut tasa_o_cuota: Decimal) -> Decimal {
+    if tasa_o_cuota.scale() < DECIMALES_TASA {
+        tasa_o_cuota.rescale(DECIMALES_TASA);
+    }
+    tasa_o_cuota
+}
@@ -0,0 +1,515 @@
+//! Generación de la cadena original del CFDI 4.0 y 3.3, sin necesidad de un motor XSLT.
+//!
+//! Se reproduce lo que hacen `cadenaoriginal_4_0.xslt` y `cadenaoriginal_3_3.xslt` del SAT:
+//! se recorre el documento y se
+//! escribe cada atributo separado por `|`, con los espacios normalizados, y se envuelve todo
+//! entre `||`. Los atributos *requeridos* se escriben aunque no existan (como `|` vacío), los
+//! *opcionales* solo si el atributo existe.
+//!
+//! Cada complemento soportado define el orden de sus atributos en su propio módulo (función
+//! `atributos_cadena`). El [`TimbreFiscalDigital`](crate::TimbreFiscalDigital) y la Addenda
+//! no forman parte de la cadena. El timbre tiene su propia cadena original, ver
+//! [`TimbreFiscalDigital::cadena_original`](crate::TimbreFiscalDigital::cadena_original).
+//!
+//! # Complementos no soportados
+//!
+//! Solo se incluyen los complementos Pagos, Nómina, Carta Porte y Comercio Exterior. Los
+//! demás complementos que el SAT sí incluye en la cadena (`implocal`, `leyendasFisc`,
+//! `donat`, `ine`, los de `ComplementoConcepto`, etc.) se omiten, así que la cadena de un
+//! documento que los tenga **no es la correcta**: no coincide con la que genera el XSLT del
+//! SAT y el sello no se puede verificar con ella.
+
+use anyhow::{anyhow, bail, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::complementos::{cartaporte, cce20, nomina12, pagos20};
+use crate::fechas::FORMATO;
+use crate::{Comprobante, TimbreFiscalDigital};
+
+/// Atributo de un nodo, en el orden en que aparece en la cadena original
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Atributo {
+    Requerido(&'static str),
+    Opcional(&'static str),
+}
+
+use Atributo::{Opcional, Requerido};
+
+/// Tabla con el orden de los atributos de los nodos de un complemento, a partir del nombre
+/// del nodo padre, del nodo y la versión del complemento. Los nodos sin tabla no se incluyen
+/// en la cadena.
+type TablaComplemento = fn(&str, &str, &str) -> Option<&'static [Atributo]>;
+
+const COMPROBANTE: &[Atributo] = &[
+    Requerido("Version"),
+    Opcional("Serie"),
+    Opcional("Folio"),
+    Requerido("Fecha"),
+    Opcional("FormaPago"),
+    Requerido("NoCertificado"),
+    Opcional("CondicionesDePago"),
+    Requerido("SubTotal"),
+    Opcional("Descuento"),
+    Requerido("Moneda"),
+    Opcional("TipoCambio"),
+    Requerido("Total"),
+    Requerido("TipoDeComprobante"),
+    Requerido("Exportacion"),
+    Opcional("MetodoPago"),
+    Requerido("LugarExpedicion"),
+    Opcional("Confirmacion"),
+];
+
+const INFORMACION_GLOBAL: &[Atributo] = &[
+    Requerido("Periodicidad"),
+    Requerido("Meses"),
+    Requerido("Año"),
+];
+
+const EMISOR: &[Atributo] = &[
+    Requerido("Rfc"),
+    Requerido("Nombre"),
+    Requerido("RegimenFiscal"),
+    Opcional("FacAtrAdquirente"),
+];
+
+const RECEPTOR: &[Atributo] = &[
+    Requerido("Rfc"),
+    Requerido("Nombre"),
+    Requerido("DomicilioFiscalReceptor"),
+    Opcional("ResidenciaFiscal"),
+    Opcional("NumRegIdTrib"),
+    Requerido("RegimenFiscalReceptor"),
+    Requerido("UsoCFDI"),
+];
+
+const CONCEPTO: &[Atributo] = &[
+    Requerido("ClaveProdServ"),
+    Opcional("NoIdentificacion"),
+    Requerido("Cantidad"),
+    Requerido("ClaveUnidad"),
+    Opcional("Unidad"),
+    Requerido("Descripcion"),
+    Requerido("ValorUnitario"),
+    Requerido("Importe"),
+    Opcional("Descuento"),
+    Requerido("ObjetoImp"),
+];
+
+const TRASLADO: &[Atributo] = &[
+    Requerido("Base"),
+    Requerido("Impuesto"),
+    Requerido("TipoFactor"),
+    Opcional("TasaOCuota"),
+    Opcional("Importe"),
+];
+
+const RETENCION_CONCEPTO: &[Atributo] = &[
+    Requerido("Base"),
+    Requerido("Impuesto"),
+    Requerido("TipoFactor"),
+    Requerido("TasaOCuota"),
+    Requerido("Importe"),
+];
+
+const RETENCION: &[Atributo] = &[Requerido("Impuesto"), Requerido("Importe")];
+
+const A_CUENTA_TERCEROS: &[Atributo] = &[
+    Requerido("RfcACuentaTerceros"),
+    Requerido("NombreACuentaTerceros"),
+    Requerido("RegimenFiscalACuentaTerceros"),
+    Requerido("DomicilioFiscalACuentaTerceros"),
+];
+
+const COMPROBANTE_33: &[Atributo] = &[
+    Requerido("Version"),
+    Opcional("Serie"),
+    Opcional("Folio"),
+    Requerido("Fecha"),
+    Opcional("FormaPago"),
+    Requerido("NoCertificado"),
+    Opcional("CondicionesDePago"),
+    Requerido("SubTotal"),
+    Opcional("Descuento"),
+    Requerido("Moneda"),
+    Opcional("TipoCambio"),
+    Requerido("Total"),
+    Requerido("TipoDeComprobante"),
+    Opcional("MetodoPago"),
+    Requerido("LugarExpedicion"),
+    Opcional("Confirmacion"),
+];
+
+const EMISOR_33: &[Atributo] = &[
+    Requerido("Rfc"),
+    Opcional("Nombre"),
+    Requerido("RegimenFiscal"),
+];
+
+const RECEPTOR_33: &[Atributo] = &[
+    Requerido("Rfc"),
+    Opcional("Nombre"),
+    Opcional("ResidenciaFiscal"),
+    Opcional("NumRegIdTrib"),
+    Requerido("UsoCFDI"),
+];
+
+const CONCEPTO_33: &[Atributo] = &[
+    Requerido("ClaveProdServ"),
+    Opcional("NoIdentificacion"),
+    Requerido("Cantidad"),
+    Requerido("ClaveUnidad"),
+    Opcional("Unidad"),
+    Requerido("Descripcion"),
+    Requerido("ValorUnitario"),
+    Requerido("Importe"),
+    Opcional("Descuento"),
+];
+
+/// Traslado a nivel comprobante en la versión 3.3, sin `Base`
+const TRASLADO_33: &[Atributo] = &[
+    Requerido("Impuesto"),
+    Requerido("TipoFactor"),
+    Requerido("TasaOCuota"),
+    Requerido("Importe"),
+];
+
+const PARTE: &[Atributo] = &[
+    Requerido("ClaveProdServ"),
+    Opcional("NoIdentificacion"),
+    Requerido("Cantidad"),
+    Opcional("Unidad"),
+    Requerido("Descripcion"),
+    Opcional("ValorUnitario"),
+    Opcional("Importe"),
+];
+
+/// Atributos de los nodos que cambian entre versiones del CFDI
+struct Esquema {
+    comprobante: &'static [Atributo],
+    emisor: &'static [Atributo],
+    receptor: &'static [Atributo],
+    concepto: &'static [Atributo],
+    /// Traslados a nivel comprobante
+    traslado: &'static [Atributo],
+}
+
+const CFDI_40: Esquema = Esquema {
+    comprobante: COMPROBANTE,
+    emisor: EMISOR,
+    receptor: RECEPTOR,
+    concepto: CONCEPTO,
+    traslado: TRASLADO,
+};
+
+const CFDI_33: Esquema = Esquema {
+    comprobante: COMPROBANTE_33,
+    emisor: EMISOR_33,
+    receptor: RECEPTOR_33,
+    concepto: CONCEPTO_33,
+    traslado: TRASLADO_33,
+};
+
+impl Comprobante {
+    /// Genera la cadena original (`||4.0|...||` o `||3.3|...||`) del comprobante, que es la que se firma
+    /// para obtener el `Sello`.
+    ///
+    /// Se calcula sobre el xml que genera [`Comprobante::to_xml`]. Para verificar un
+    /// comprobante recibido conviene usar [`cadena_original`] con
+    /// el xml original.
+    ///
+    /// ```rust
+    /// # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+    /// #     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1160.00"
+    /// #     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+    /// #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+    /// #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+    /// #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+    /// #   <cfdi:Conceptos>
+    /// #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+    /// #         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="01"/>
+    /// #   </cfdi:Conceptos>
+    /// # </cfdi:Comprobante>"#;
+    /// let cfdi = cfdi::parse_cfdi(xml).unwrap();
+    ///
+    /// assert_eq!(
+    ///     cfdi.cadena_original().unwrap(),
+    ///     "||4.0|2024-01-15T10:00:00||1000.00|MXN|1160.00|I|01|PUE|44100\
+    ///      |EKU9003173C9|ESCUELA KEMPER URGATE|601\
+    ///      |URE180429TM6|UNIVERSIDAD ROBOTICA ESPAÑOLA|65000|601|G03\
+    ///      |84111506|1|ACT|Servicio|1000.00|1000.00|01||"
+    /// );
+    /// ```
+    pub fn cadena_original(&self) -> Result<String> {
+        cadena_original(&self.to_xml()?)
+    }
+}
+
+impl TimbreFiscalDigital {
+    /// Cadena original del timbre (`||1.1|UUID|FechaTimbrado|...||`), que es la que firma el
+    /// SAT en `SelloSAT`. Se genera como lo hace `cadenaoriginal_TFD_1_1.xslt`, o
+    /// `cadenaoriginal_TFD_1_0.xslt` para los timbres 1.0 (sin `RfcProvCertif` ni `Leyenda`).
+    pub fn cadena_original(&self) -> String {
+        let mut cadena = Cadena::default();
+        cadena.valor(&self.version);
+        cadena.valor(&self.uuid);
+        cadena.valor(&self.fecha_timbrado.format(FORMATO).to_string());
+        if self.version != "1.0" {
+            cadena.valor(self.rfc_prov_certif.as_deref().unwrap_or_default());
+            if let Some(leyenda) = &self.leyenda {
+                cadena.valor(leyenda);
+            }
+        }
+        cadena.valor(&self.sello_cfd);
+        cadena.valor(&self.no_certificado_sat);
+        cadena.terminar()
+    }
+}
+
+/// Genera la cadena original de un CFDI 4.0 o 3.3 a partir de su xml, tal cual lo haría
+/// `cadenaoriginal_4_0.xslt` (o `cadenaoriginal_3_3.xslt`). Al trabajar sobre el texto original, los valores se usan
+/// exactamente como vienen escritos en el documento.
+///
+/// Solo se incluyen los complementos Pagos, Nómina, Carta Porte y Comercio Exterior; los
+/// demás se omiten, así que la cadena de un documento con otros complementos (`implocal`,
+/// `leyendasFisc`, `ComplementoConcepto`, ...) no coincide con la del SAT.
+pub fn cadena_original(xml: &str) -> Result<String> {
+    let comprobante = Nodo::leer(xml)?;
+    if comprobante.nombre != "Comprobante" {
+        bail!("El nodo principal no es un Comprobante");
+    }
+    let version = comprobante
+        .atributo("Version")
+        .or_else(|| comprobante.atributo("version"));
+    let esquema = match version {
+        Some("4.0") => &CFDI_40,
+        Some("3.3") => &CFDI_33,
+        Some(v) => bail!(
+            "Versión de CFDI no soportada para la cadena original: {}",
+            v
+        ),
+        None => bail!("El comprobante no tiene atributo Version"),
+    };
+
+    let mut cadena = Cadena::default();
+    cadena.comprobante(&comprobante, esquema);
+    Ok(cadena.terminar())
+}
+
+/// Nombre del primer complemento del xml que no se incluye en la cadena original, ya sea en
+/// `Complemento` o en el `ComplementoConcepto` de un concepto. Con estos complementos la
+/// cadena no es la del SAT.
+pub(crate) fn complemento_no_soportado(xml: &str) -> Result<Option<String>> {
+    let comprobante = Nodo::leer(xml)?;
+    let complementos = comprobante
+        .hijos("Complemento")
+        .flat_map(|c| c.hijos.iter())
+        .filter(|c| c.nombre != "TimbreFiscalDigital" && tabla_complemento(&c.nombre).is_none());
+    let complementos_concepto = comprobante
+        .nietos("Conceptos", "Concepto")
+        .flat_map(|c| c.hijos("ComplementoConcepto"))
+        .flat_map(|c| c.hijos.iter());
+    let nombre = complementos
+        .chain(complementos_concepto)
+        .next()
+        .map(|c| c.nombre.clone());
+    Ok(nombre)
+}
+
+/// Tabla de atributos de los complementos que se incluyen en la cadena original
+fn tabla_complemento(nombre: &str) -> Option<TablaComplemento> {
+    let tabla: TablaComplemento = match nombre {
+        "Pagos" => pagos20::atributos_cadena,
+        "Nomina" => nomina12::atributos_cadena,
+        "CartaPorte" => cartaporte::atributos_cadena,
+        "ComercioExterior" => cce20::atributos_cadena,
+        _ => return None,
+    };
+    Some(tabla)
+}
+
+#[derive(Default)]
+struct Cadena(String);
+
+impl Cadena {
+    fn terminar(self) -> String {
+        format!("|{}||", self.0)
+    }
+
+    fn valor(&mut self, valor: &str) {
+        self.0.push('|');
+        // normalize-space() de XPath
+        let mut primero = true;
+        for parte in valor.split_ascii_whitespace() {
+            if !primero {
+                self.0.push(' ');
+            }
+            self.0.push_str(parte);
+            primero = false;
+        }
+    }
+
+    fn atributos(&mut self, nodo: &Nodo, atributos: &[Atributo]) {
+        for atributo in atributos {
+            match *atributo {
+                Requerido(nombre) => self.valor(nodo.atributo(nombre).unwrap_or_default()),
+                Opcional(nombre) => {
+                    if let Some(valor) = nodo.atributo(nombre) {
+                        self.valor(valor)
+                    }
+                }
+            }
+        }
+    }
+
+    fn comprobante(&mut self, comprobante: &Nodo, esquema: &Esquema) {
+        self.atributos(comprobante, esquema.comprobante);
+        for nodo in comprobante.hijos("InformacionGlobal") {
+            self.atributos(nodo, INFORMACION_GLOBAL);
+        }
+        for relacionados in comprobante.hijos("CfdiRelacionados") {
+            self.atributos(relacionados, &[Requerido("TipoRelacion")]);
+            for relacionado in relacionados.hijos("CfdiRelacionado") {
+                self.atributos(relacionado, &[Requerido("UUID")]);
+            }
+        }
+        for nodo in comprobante.hijos("Emisor") {
+            self.atributos(nodo, esquema.emisor);
+        }
+        for nodo in comprobante.hijos("Receptor") {
+            self.atributos(nodo, esquema.receptor);
+        }
+        for concepto in comprobante
+            .hijos("Conceptos")
+            .flat_map(|c| c.hijos("Concepto"))
+        {
+            self.concepto(concepto, esquema);
+        }
+        for impuestos in comprobante.hijos("Impuestos") {
+            for retencion in impuestos.nietos("Retenciones", "Retencion") {
+                self.atributos(retencion, RETENCION);
+            }
+            self.atributos(impuestos, &[Opcional("TotalImpuestosRetenidos")]);
+            for traslado in impuestos.nietos("Traslados", "Traslado") {
+                self.atributos(traslado, esquema.traslado);
+            }
+            self.atributos(impuestos, &[Opcional("TotalImpuestosTrasladados")]);
+        }
+        for complemento in comprobante
+            .hijos("Complemento")
+            .flat_map(|c| c.hijos.iter())
+        {
+            let Some(tabla) = tabla_complemento(&complemento.nombre) else {
+                continue;
+            };
+            let version = complemento.atributo("Version").unwrap_or_default();
+            self.complemento(complemento, "", version, tabla);
+        }
+    }
+
+    fn concepto(&mut self, concepto: &Nodo, esquema: &Esquema) {
+        self.atributos(concepto, esquema.concepto);
+        for impuestos in concepto.hijos("Impuestos") {
+            for traslado in impuestos.nietos("Traslados", "Traslado") {
+                self.atributos(traslado, TRASLADO);
+            }
+            for retencion in impuestos.nietos("Retenciones", "Retencion") {
+                self.atributos(retencion, RETENCION_CONCEPTO);
+            }
+        }
+        for nodo in concepto.hijos("ACuentaTerceros") {
+            self.atributos(nodo, A_CUENTA_TERCEROS);
+        }
+        for nodo in concepto.hijos("InformacionAduanera") {
+            self.atributos(nodo, &[Requerido("NumeroPedimento")]);
+        }
+        for nodo in concepto.hijos("CuentaPredial") {
+            self.atributos(nodo, &[Requerido("Numero")]);
+        }
+        for parte in concepto.hijos("Parte") {
+            self.atributos(parte, PARTE);
+            for nodo in parte.hijos("InformacionAduanera") {
+                self.atributos(nodo, &[Requerido("NumeroPedimento")]);
+            }
+        }
+    }
+
+    /// Los complementos se recorren en el orden del documento: primero los atributos del
+    /// nodo y después sus hijos
+    fn complemento(&mut self, nodo: &Nodo, padre: &str, version: &str, tabla: TablaComplemento) {
+        let Some(atributos) = tabla(padre, &nodo.nombre, version) else {
+            return;
+        };
+        self.atributos(nodo, atributos);
+        for hijo in &nodo.hijos {
+            self.complemento(hijo, &nodo.nombre, version, tabla);
+        }
+    }
+}
+
+/// Nodo de un documento xml, sin prefijo y sin texto
+#[derive(Debug)]
+struct Nodo {
+    nombre: String,
+    atributos: Vec<(String, String)>,
+    hijos: Vec<Nodo>,
+}
+
+impl Nodo {
+    fn leer(xml: &str) -> Result<Nodo> {
+        let mut reader = Reader::from_str(xml);
+        let mut pila: Vec<Nodo> = vec![];
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => pila.push(Nodo::nuevo(&e)?),
+                Event::Empty(e) => {
+                    let nodo = Nodo::nuevo(&e)?;
+                    match pila.last_mut() {
+                        Some(padre) => padre.hijos.push(nodo),
+                        None => return Ok(nodo),
+                    }
+                }
+                Event::End(_) => {
+                    let nodo = pila.pop().ok_or_else(|| anyhow!("xml mal formado"))?;
+                    match pila.last_mut() {
+                        Some(padre) => padre.hijos.push(nodo),
+                        None => return Ok(nodo),
+                    }
+                }
+                Event::Eof => bail!("xml incompleto"),
+                _ => {}
+            }
+        }
+    }
+
+    fn nuevo(e: &BytesStart) -> Result<Nodo> {
+        let nombre = std::str::from_utf8(e.local_name().into_inner())?.to_string();
+        let mut atributos = vec![];
+        for atributo in e.attributes() {
+            let atributo = atributo?;
+            let clave = std::str::from_utf8(atributo.key.into_inner())?.to_string();
+            atributos.push((clave, atributo.unescape_value()?.into_owned()));
+        }
+        Ok(Nodo {
+            nombre,
+            atributos,
+            hijos: vec![],
+        })
+    }
+
+    fn atributo(&self, nombre: &str) -> Option<&str> {
+        self.atributos
+            .iter()
+            .find(|(k, _)| k == nombre)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn hijos<'a>(&'a self, nombre: &'a str) -> impl Iterator<Item = &'a Nodo> + 'a {
+        self.hijos.iter().filter(move |h| h.nombre == nombre)
+    }
+
+    fn nietos<'a>(&'a self, hijo: &'a str, nieto: &'a str) -> impl Iterator<Item = &'a Nodo> + 'a {
+        self.hijos(hijo).flat_map(move |h| h.hijos(nieto))
+    }
+}
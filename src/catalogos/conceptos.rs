@@ -0,0 +1,143 @@
+//! Catálogos c_ClaveProdServ y c_ClaveUnidad, usados en los [`Concepto`](crate::Concepto).
+//!
+//! Los catálogos están incluidos en el binario como texto (un registro por línea, campos
+//! separados por tabulador y ordenados por clave), y se leen la primera vez que se consultan.
+//! Solo están disponibles con el feature `catalogos-conceptos`.
+//!
+//! Los archivos `c_ClaveProdServ.tsv` y `c_ClaveUnidad.tsv` se generan a partir de las hojas
+//! del mismo nombre en el `catCFDI.xls` que publica el SAT:
+//!
+//! - c_ClaveProdServ: `clave`, `descripción`, `incluir IVA trasladado`,
+//!   `incluir IEPS trasladado`, `inicio de vigencia`, `fin de vigencia`
+//! - c_ClaveUnidad: `clave`, `nombre`, `símbolo`, `inicio de vigencia`, `fin de vigencia`
+//!
+//! Las fechas van como `AAAA-MM-DD` y los campos opcionales vacíos. Por ahora solo se incluye
+//! una muestra de las claves de uso más común; las demás no se encuentran.
+//!
+//! ```rust
+//! use cfdi::catalogos::conceptos::{ClaveProdServ, ClaveUnidad, Inclusion};
+//!
+//! let clave = ClaveProdServ::buscar("50202306").unwrap();
+//! assert_eq!(clave.descripcion, "Refrescos");
+//! assert_eq!(clave.incluir_ieps_trasladado, Inclusion::Si);
+//! assert!(clave.vigente_en("2024-05-20T13:45:10".parse().unwrap()));
+//!
+//! assert_eq!(ClaveUnidad::buscar("KGM").unwrap().simbolo, Some("kg"));
+//! ```
+
+use std::sync::OnceLock;
+
+use chrono::NaiveDateTime;
+
+const CLAVE_PROD_SERV: &str = include_str!("c_ClaveProdServ.tsv");
+const CLAVE_UNIDAD: &str = include_str!("c_ClaveUnidad.tsv");
+
+/// Si un concepto con la clave debe incluir el impuesto trasladado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Inclusion {
+    Si,
+    No,
+    Opcional,
+}
+
+/// Registro del catálogo c_ClaveProdServ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaveProdServ {
+    pub clave: &'static str,
+    pub descripcion: &'static str,
+    pub incluir_iva_trasladado: Inclusion,
+    pub incluir_ieps_trasladado: Inclusion,
+    /// Fecha de inicio de vigencia (`AAAA-MM-DD`)
+    pub inicio_vigencia: &'static str,
+    /// Fecha de fin de vigencia (`AAAA-MM-DD`), si la clave ya no es vigente
+    pub fin_vigencia: Option<&'static str>,
+}
+
+/// Registro del catálogo c_ClaveUnidad
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaveUnidad {
+    pub clave: &'static str,
+    pub nombre: &'static str,
+    pub simbolo: Option<&'static str>,
+    /// Fecha de inicio de vigencia (`AAAA-MM-DD`)
+    pub inicio_vigencia: &'static str,
+    /// Fecha de fin de vigencia (`AAAA-MM-DD`), si la clave ya no es vigente
+    pub fin_vigencia: Option<&'static str>,
+}
+
+impl ClaveProdServ {
+    /// Busca una clave en el catálogo
+    pub fn buscar(clave: &str) -> Option<&'static ClaveProdServ> {
+        static CATALOGO: OnceLock<Vec<ClaveProdServ>> = OnceLock::new();
+        let catalogo = CATALOGO.get_or_init(|| {
+            registros(CLAVE_PROD_SERV)
+                .map(|campos| ClaveProdServ {
+                    clave: campos[0],
+                    descripcion: campos[1],
+                    incluir_iva_trasladado: inclusion(campos[2]),
+                    incluir_ieps_trasladado: inclusion(campos[3]),
+                    inicio_vigencia: campos[4],
+                    fin_vigencia: opcional(campos[5]),
+                })
+                .collect()
+        });
+        let i = catalogo.binary_search_by(|c| c.clave.cmp(clave)).ok()?;
+        Some(&catalogo[i])
+    }
+
+    /// `true` si la clave era vigente en `fecha` (normalmente la `Fecha` del comprobante)
+    pub fn vigente_en(&self, fecha: NaiveDateTime) -> bool {
+        vigente(self.inicio_vigencia, self.fin_vigencia, fecha)
+    }
+}
+
+impl ClaveUnidad {
+    /// Busca una clave en el catálogo
+    pub fn buscar(clave: &str) -> Option<&'static ClaveUnidad> {
+        static CATALOGO: OnceLock<Vec<ClaveUnidad>> = OnceLock::new();
+        let catalogo = CATALOGO.get_or_init(|| {
+            registros(CLAVE_UNIDAD)
+                .map(|campos| ClaveUnidad {
+                    clave: campos[0],
+                    nombre: campos[1],
+                    simbolo: opcional(campos[2]),
+                    inicio_vigencia: campos[3],
+                    fin_vigencia: opcional(campos[4]),
+                })
+                .collect()
+        });
+        let i = catalogo.binary_search_by(|c| c.clave.cmp(clave)).ok()?;
+        Some(&catalogo[i])
+    }
+
+    /// `true` si la clave era vigente en `fecha` (normalmente la `Fecha` del comprobante)
+    pub fn vigente_en(&self, fecha: NaiveDateTime) -> bool {
+        vigente(self.inicio_vigencia, self.fin_vigencia, fecha)
+    }
+}
+
+fn registros(datos: &'static str) -> impl Iterator<Item = Vec<&'static str>> {
+    datos
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split('\t').collect())
+}
+
+fn opcional(campo: &'static str) -> Option<&'static str> {
+    Some(campo).filter(|c| !c.is_empty())
+}
+
+fn inclusion(campo: &str) -> Inclusion {
+    match campo {
+        "Sí" => Inclusion::Si,
+        "No" => Inclusion::No,
+        _ => Inclusion::Opcional,
+    }
+}
+
+/// Las fechas `AAAA-MM-DD` se pueden comparar como texto
+fn vigente(inicio: &str, fin: Option<&str>, fecha: NaiveDateTime) -> bool {
+    let fecha = fecha.date().to_string();
+    let fecha = fecha.as_str();
+    inicio <= fecha && fin.is_none_or(|fin| fecha <= fin)
+}
@@ -0,0 +1,553 @@
+//! Catálogos del SAT usados en los atributos del comprobante.
+//!
+//! Cada catálogo es un enum que se lee y escribe en el xml con la clave del SAT (ej. `"03"`
+//! para [`FormaPago::TransferenciaElectronica`]). Las claves que no están en el catálogo se
+//! leen como `Unknown`, para que una clave nueva del SAT no impida leer el comprobante.
+//!
+//! ```rust
+//! use cfdi::catalogos::{FormaPago, UsoCfdi};
+//!
+//! let forma: FormaPago = "03".parse().unwrap();
+//! assert_eq!(forma, FormaPago::TransferenciaElectronica);
+//! assert_eq!(forma.clave(), "03");
+//! assert_eq!(forma.descripcion(), Some("Transferencia electrónica de fondos"));
+//!
+//! let uso = UsoCfdi::from("X99");
+//! assert_eq!(uso, UsoCfdi::Unknown("X99".to_string()));
+//! assert_eq!(uso.descripcion(), None);
+//! ```
+//!
+//! Los catálogos c_ClaveProdServ y c_ClaveUnidad están en el módulo `conceptos`, disponible
+//! con el feature `catalogos-conceptos`.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "catalogos-conceptos")]
+pub mod conceptos;
+
+/// Define un enum de catálogo: cada variante con su clave y descripción, más la variante
+/// `Unknown` para las claves que no están en el catálogo.
+macro_rules! catalogo {
+    (
+        $(#[$meta:meta])*
+        $nombre:ident {
+            $( $variante:ident = $clave:literal => $descripcion:literal, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $nombre {
+            $(
+                #[doc = concat!("`", $clave, "` - ", $descripcion)]
+                $variante,
+            )*
+            /// Clave que no está en el catálogo
+            Unknown(String),
+        }
+
+        impl $nombre {
+            /// Clave del SAT, como se escribe en el xml
+            pub fn clave(&self) -> &str {
+                match self {
+                    $( $nombre::$variante => $clave, )*
+                    $nombre::Unknown(clave) => clave,
+                }
+            }
+
+            /// Descripción del catálogo del SAT. `None` si la clave no está en el catálogo
+            pub fn descripcion(&self) -> Option<&'static str> {
+                match self {
+                    $( $nombre::$variante => Some($descripcion), )*
+                    $nombre::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl From<&str> for $nombre {
+            fn from(clave: &str) -> Self {
+                match clave {
+                    $( $clave => $nombre::$variante, )*
+                    otra => $nombre::Unknown(otra.to_string()),
+                }
+            }
+        }
+
+        impl FromStr for $nombre {
+            type Err = Infallible;
+
+            fn from_str(clave: &str) -> Result<Self, Self::Err> {
+                Ok($nombre::from(clave))
+            }
+        }
+
+        impl fmt::Display for $nombre {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.clave())
+            }
+        }
+
+        impl Serialize for $nombre {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.clave())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $nombre {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let clave = String::deserialize(deserializer)?;
+                Ok($nombre::from(clave.as_str()))
+            }
+        }
+    };
+}
+
+catalogo! {
+    /// c_FormaPago - Forma en que se pagó (o se pagará) el comprobante
+    FormaPago {
+        Efectivo = "01" => "Efectivo",
+        ChequeNominativo = "02" => "Cheque nominativo",
+        TransferenciaElectronica = "03" => "Transferencia electrónica de fondos",
+        TarjetaDeCredito = "04" => "Tarjeta de crédito",
+        MonederoElectronico = "05" => "Monedero electrónico",
+        DineroElectronico = "06" => "Dinero electrónico",
+        ValesDeDespensa = "08" => "Vales de despensa",
+        DacionEnPago = "12" => "Dación en pago",
+        PagoPorSubrogacion = "13" => "Pago por subrogación",
+        PagoPorConsignacion = "14" => "Pago por consignación",
+        Condonacion = "15" => "Condonación",
+        Compensacion = "17" => "Compensación",
+        Novacion = "23" => "Novación",
+        Confusion = "24" => "Confusión",
+        RemisionDeDeuda = "25" => "Remisión de deuda",
+        PrescripcionOCaducidad = "26" => "Prescripción o caducidad",
+        ASatisfaccionDelAcreedor = "27" => "A satisfacción del acreedor",
+        TarjetaDeDebito = "28" => "Tarjeta de débito",
+        TarjetaDeServicios = "29" => "Tarjeta de servicios",
+        AplicacionDeAnticipos = "30" => "Aplicación de anticipos",
+        IntermediarioPagos = "31" => "Intermediario pagos",
+        PorDefinir = "99" => "Por definir",
+    }
+}
+
+catalogo! {
+    /// c_MetodoPago
+    MetodoPago {
+        PagoEnUnaExhibicion = "PUE" => "Pago en una sola exhibición",
+        PagoEnParcialidadesODiferido = "PPD" => "Pago en parcialidades o diferido",
+    }
+}
+
+catalogo! {
+    /// c_TipoDeComprobante
+    TipoDeComprobante {
+        Ingreso = "I" => "Ingreso",
+        Egreso = "E" => "Egreso",
+        Traslado = "T" => "Traslado",
+        Nomina = "N" => "Nómina",
+        Pago = "P" => "Pago",
+    }
+}
+
+catalogo! {
+    /// c_RegimenFiscal
+    RegimenFiscal {
+        GeneralDeLeyPersonasMorales = "601" => "General de Ley Personas Morales",
+        PersonasMoralesSinFinesDeLucro = "603" => "Personas Morales con Fines no Lucrativos",
+        SueldosYSalarios = "605" => "Sueldos y Salarios e Ingresos Asimilados a Salarios",
+        Arrendamiento = "606" => "Arrendamiento",
+        EnajenacionOAdquisicionDeBienes = "607" => "Régimen de Enajenación o Adquisición de Bienes",
+        DemasIngresos = "608" => "Demás ingresos",
+        ResidentesEnElExtranjero = "610" => "Residentes en el Extranjero sin Establecimiento Permanente en México",
+        IngresosPorDividendos = "611" => "Ingresos por Dividendos (socios y accionistas)",
+        ActividadesEmpresarialesYProfesionales = "612" => "Personas Físicas con Actividades Empresariales y Profesionales",
+        IngresosPorIntereses = "614" => "Ingresos por intereses",
+        IngresosPorPremios = "615" => "Régimen de los ingresos por obtención de premios",
+        SinObligacionesFiscales = "616" => "Sin obligaciones fiscales",
+        SociedadesCooperativasDeProduccion = "620" => "Sociedades Cooperativas de Producción que optan por diferir sus ingresos",
+        IncorporacionFiscal = "621" => "Incorporación Fiscal",
+        ActividadesAgricolasGanaderasSilvicolasYPesqueras = "622" => "Actividades Agrícolas, Ganaderas, Silvícolas y Pesqueras",
+        OpcionalParaGruposDeSociedades = "623" => "Opcional para Grupos de Sociedades",
+        Coordinados = "624" => "Coordinados",
+        PlataformasTecnologicas = "625" => "Régimen de las Actividades Empresariales con ingresos a través de Plataformas Tecnológicas",
+        SimplificadoDeConfianza = "626" => "Régimen Simplificado de Confianza",
+        Hidrocarburos = "628" => "Hidrocarburos",
+        RegimenesFiscalesPreferentes = "629" => "De los Regímenes Fiscales Preferentes y de las Empresas Multinacionales",
+        EnajenacionDeAccionesEnBolsa = "630" => "Enajenación de acciones en bolsa de valores",
+    }
+}
+
+catalogo! {
+    /// c_UsoCFDI - Uso que le dará el receptor al comprobante
+    ///
+    /// Incluye `P01` (Por definir), que solo se usa en comprobantes 3.3.
+    UsoCfdi {
+        AdquisicionDeMercancias = "G01" => "Adquisición de mercancías",
+        DevolucionesDescuentosOBonificaciones = "G02" => "Devoluciones, descuentos o bonificaciones",
+        GastosEnGeneral = "G03" => "Gastos en general",
+        Construcciones = "I01" => "Construcciones",
+        MobiliarioYEquipoDeOficina = "I02" => "Mobiliario y equipo de oficina por inversiones",
+        EquipoDeTransporte = "I03" => "Equipo de transporte",
+        EquipoDeComputo = "I04" => "Equipo de computo y accesorios",
+        DadosTroquelesMoldes = "I05" => "Dados, troqueles, moldes, matrices y herramental",
+        ComunicacionesTelefonicas = "I06" => "Comunicaciones telefónicas",
+        ComunicacionesSatelitales = "I07" => "Comunicaciones satelitales",
+        OtraMaquinariaYEquipo = "I08" => "Otra maquinaria y equipo",
+        HonorariosMedicos = "D01" => "Honorarios médicos, dentales y gastos hospitalarios",
+        GastosMedicosPorIncapacidad = "D02" => "Gastos médicos por incapacidad o discapacidad",
+        GastosFunerales = "D03" => "Gastos funerales",
+        Donativos = "D04" => "Donativos",
+        InteresesHipotecarios = "D05" => "Intereses reales efectivamente pagados por créditos hipotecarios (casa habitación)",
+        AportacionesVoluntariasSar = "D06" => "Aportaciones voluntarias al SAR",
+        PrimasPorSegurosDeGastosMedicos = "D07" => "Primas por seguros de gastos médicos",
+        TransportacionEscolar = "D08" => "Gastos de transportación escolar obligatoria",
+        CuentasParaElAhorro = "D09" => "Depósitos en cuentas para el ahorro, primas que tengan como base planes de pensiones",
+        ServiciosEducativos = "D10" => "Pagos por servicios educativos (colegiaturas)",
+        SinEfectosFiscales = "S01" => "Sin efectos fiscales",
+        Pagos = "CP01" => "Pagos",
+        Nomina = "CN01" => "Nómina",
+        PorDefinir = "P01" => "Por definir",
+    }
+}
+
+catalogo! {
+    /// c_Exportacion
+    Exportacion {
+        NoAplica = "01" => "No aplica",
+        Definitiva = "02" => "Definitiva con clave A1",
+        Temporal = "03" => "Temporal",
+        DefinitivaDistintaA1 = "04" => "Definitiva con clave distinta a A1 o cuando no existe enajenación en términos del CFF",
+    }
+}
+
+catalogo! {
+    /// c_TipoRelacion - Relación del comprobante con los CFDI relacionados
+    ///
+    /// Incluye `08` y `09`, que solo se usan en comprobantes 3.3.
+    TipoRelacion {
+        NotaDeCredito = "01" => "Nota de crédito de los documentos relacionados",
+        NotaDeDebito = "02" => "Nota de débito de los documentos relacionados",
+        DevolucionDeMercancia = "03" => "Devolución de mercancía sobre facturas o traslados previos",
+        Sustitucion = "04" => "Sustitución de los CFDI previos",
+        TrasladosFacturadosPreviamente = "05" => "Traslados de mercancías facturados previamente",
+        FacturaPorTrasladosPrevios = "06" => "Factura generada por los traslados previos",
+        AplicacionDeAnticipo = "07" => "CFDI por aplicación de anticipo",
+        PagosEnParcialidades = "08" => "Factura generada por pagos en parcialidades",
+        PagosDiferidos = "09" => "Factura generada por pagos diferidos",
+    }
+}
+
+catalogo! {
+    /// c_Periodicidad - Periodo de las operaciones de una factura global
+    Periodicidad {
+        Diario = "01" => "Diario",
+        Semanal = "02" => "Semanal",
+        Quincenal = "03" => "Quincenal",
+        Mensual = "04" => "Mensual",
+        Bimestral = "05" => "Bimestral",
+    }
+}
+
+catalogo! {
+    /// c_Meses - Mes o bimestre de las operaciones de una factura global
+    Meses {
+        Enero = "01" => "Enero",
+        Febrero = "02" => "Febrero",
+        Marzo = "03" => "Marzo",
+        Abril = "04" => "Abril",
+        Mayo = "05" => "Mayo",
+        Junio = "06" => "Junio",
+        Julio = "07" => "Julio",
+        Agosto = "08" => "Agosto",
+        Septiembre = "09" => "Septiembre",
+        Octubre = "10" => "Octubre",
+        Noviembre = "11" => "Noviembre",
+        Diciembre = "12" => "Diciembre",
+        EneroFebrero = "13" => "Enero-Febrero",
+        MarzoAbril = "14" => "Marzo-Abril",
+        MayoJunio = "15" => "Mayo-Junio",
+        JulioAgosto = "16" => "Julio-Agosto",
+        SeptiembreOctubre = "17" => "Septiembre-Octubre",
+        NoviembreDiciembre = "18" => "Noviembre-Diciembre",
+    }
+}
+
+catalogo! {
+    /// c_ObjetoImp - Si el concepto es objeto de impuestos
+    ObjetoImp {
+        NoObjeto = "01" => "No objeto de impuesto.",
+        SiObjeto = "02" => "Sí objeto de impuesto.",
+        SiObjetoNoObligadoAlDesglose = "03" => "Sí objeto del impuesto y no obligado al desglose.",
+        SiObjetoNoCausaImpuesto = "04" => "Sí objeto del impuesto y no causa impuesto.",
+        SiObjetoIvaCreditoPodebi = "05" => "Sí objeto del impuesto, IVA crédito PODEBI.",
+        SiObjetoIvaNoTraslado = "06" => "Sí objeto del IVA, No traslado IVA.",
+        NoTrasladoIvaSiDesgloseIeps = "07" => "No traslado del IVA, Sí desglose IEPS.",
+        NoTrasladoIvaNoDesgloseIeps = "08" => "No traslado del IVA, No desglose IEPS.",
+    }
+}
+
+catalogo! {
+    /// c_Impuesto
+    Impuesto {
+        Isr = "001" => "ISR",
+        Iva = "002" => "IVA",
+        Ieps = "003" => "IEPS",
+    }
+}
+
+catalogo! {
+    /// c_TipoFactor
+    TipoFactor {
+        Tasa = "Tasa" => "Tasa",
+        Cuota = "Cuota" => "Cuota",
+        Exento = "Exento" => "Exento",
+    }
+}
+
+catalogo! {
+    /// c_Moneda
+    Moneda {
+        Aed = "AED" => "Dirham de EAU",
+        Afn = "AFN" => "Afghani",
+        All = "ALL" => "Lek",
+        Amd = "AMD" => "Dram armenio",
+        Ang = "ANG" => "Florín antillano neerlandés",
+        Aoa = "AOA" => "Kwanza",
+        Ars = "ARS" => "Peso Argentino",
+        Aud = "AUD" => "Dólar Australiano",
+        Awg = "AWG" => "Aruba Florin",
+        Azn = "AZN" => "Azerbaijanian Manat",
+        Bam = "BAM" => "Convertibles marca",
+        Bbd = "BBD" => "Dólar de Barbados",
+        Bdt = "BDT" => "Taka",
+        Bgn = "BGN" => "Lev búlgaro",
+        Bhd = "BHD" => "Dinar de Bahrein",
+        Bif = "BIF" => "Burundi Franc",
+        Bmd = "BMD" => "Dólar de Bermudas",
+        Bnd = "BND" => "Dólar de Brunei",
+        Bob = "BOB" => "Boliviano",
+        Bov = "BOV" => "Mvdol",
+        Brl = "BRL" => "Real brasileño",
+        Bsd = "BSD" => "Dólar de las Bahamas",
+        Btn = "BTN" => "Ngultrum",
+        Bwp = "BWP" => "Pula",
+        Byn = "BYN" => "Rublo bielorruso",
+        Bzd = "BZD" => "Dólar de Belice",
+        Cad = "CAD" => "Dolar Canadiense",
+        Cdf = "CDF" => "Franco congoleño",
+        Che = "CHE" => "WIR Euro",
+        Chf = "CHF" => "Franco Suizo",
+        Chw = "CHW" => "Franc WIR",
+        Clf = "CLF" => "Unidad de Fomento",
+        Clp = "CLP" => "Peso chileno",
+        Cny = "CNY" => "Yuan Renminbi",
+        Cop = "COP" => "Peso Colombiano",
+        Cou = "COU" => "Unidad de Valor real",
+        Crc = "CRC" => "Colón Costarricense",
+        Cuc = "CUC" => "Peso Convertible",
+        Cup = "CUP" => "Peso Cubano",
+        Cve = "CVE" => "Cabo Verde Escudo",
+        Czk = "CZK" => "Corona checa",
+        Djf = "DJF" => "Franco de Djibouti",
+        Dkk = "DKK" => "Corona Danesa",
+        Dop = "DOP" => "Peso Dominicano",
+        Dzd = "DZD" => "Dinar argelino",
+        Egp = "EGP" => "Libra egipcia",
+        Ern = "ERN" => "Nakfa",
+        Etb = "ETB" => "Birr etíope",
+        Eur = "EUR" => "Euro",
+        Fjd = "FJD" => "Dólar de Fiji",
+        Fkp = "FKP" => "Libra malvinense",
+        Gbp = "GBP" => "Libra Esterlina",
+        Gel = "GEL" => "Lari",
+        Ghs = "GHS" => "Cedi de Ghana",
+        Gip = "GIP" => "Libra de Gibraltar",
+        Gmd = "GMD" => "Dalasi",
+        Gnf = "GNF" => "Franco guineano",
+        Gtq = "GTQ" => "Quetzal",
+        Gyd = "GYD" => "Dólar guyanés",
+        Hkd = "HKD" => "Dólar de Hong Kong",
+        Hnl = "HNL" => "Lempira",
+        Hrk = "HRK" => "Kuna",
+        Htg = "HTG" => "Gourde",
+        Huf = "HUF" => "Florín",
+        Idr = "IDR" => "Rupia",
+        Ils = "ILS" => "Nuevo Shekel Israelí",
+        Inr = "INR" => "Rupia India",
+        Iqd = "IQD" => "Dinar iraquí",
+        Irr = "IRR" => "Rial iraní",
+        Isk = "ISK" => "Corona islandesa",
+        Jmd = "JMD" => "Dólar Jamaiquino",
+        Jod = "JOD" => "Dinar jordano",
+        Jpy = "JPY" => "Yen",
+        Kes = "KES" => "Chelín keniano",
+        Kgs = "KGS" => "Som",
+        Khr = "KHR" => "Riel",
+        Kmf = "KMF" => "Franco Comoro",
+        Kpw = "KPW" => "Corea del Norte ganó",
+        Krw = "KRW" => "Won",
+        Kwd = "KWD" => "Dinar kuwaití",
+        Kyd = "KYD" => "Dólar de las Islas Caimán",
+        Kzt = "KZT" => "Tenge",
+        Lak = "LAK" => "Kip",
+        Lbp = "LBP" => "Libra libanesa",
+        Lkr = "LKR" => "Rupia de Sri Lanka",
+        Lrd = "LRD" => "Dólar liberiano",
+        Lsl = "LSL" => "Loti",
+        Lyd = "LYD" => "Dinar libio",
+        Mad = "MAD" => "Dirham marroquí",
+        Mdl = "MDL" => "Leu moldavo",
+        Mga = "MGA" => "Ariary malgache",
+        Mkd = "MKD" => "Denar",
+        Mmk = "MMK" => "Kyat",
+        Mnt = "MNT" => "Tugrik",
+        Mop = "MOP" => "Pataca",
+        Mru = "MRU" => "Ouguiya",
+        Mur = "MUR" => "Rupia de Mauricio",
+        Mvr = "MVR" => "Rupia",
+        Mwk = "MWK" => "Kwacha",
+        Mxn = "MXN" => "Peso Mexicano",
+        Mxv = "MXV" => "México Unidad de Inversión (UDI)",
+        Myr = "MYR" => "Ringgit malayo",
+        Mzn = "MZN" => "Mozambique Metical",
+        Nad = "NAD" => "Dólar de Namibia",
+        Ngn = "NGN" => "Naira",
+        Nio = "NIO" => "Córdoba Oro",
+        Nok = "NOK" => "Corona Noruega",
+        Npr = "NPR" => "Rupia nepalí",
+        Nzd = "NZD" => "Dólar de Nueva Zelanda",
+        Omr = "OMR" => "Rial omaní",
+        Pab = "PAB" => "Balboa",
+        Pen = "PEN" => "Sol",
+        Pgk = "PGK" => "Kina",
+        Php = "PHP" => "Peso filipino",
+        Pkr = "PKR" => "Rupia de Pakistán",
+        Pln = "PLN" => "Zloty",
+        Pyg = "PYG" => "Guaraní",
+        Qar = "QAR" => "Qatar Rial",
+        Ron = "RON" => "Leu rumano",
+        Rsd = "RSD" => "Dinar serbio",
+        Rub = "RUB" => "Rublo ruso",
+        Rwf = "RWF" => "Franco ruandés",
+        Sar = "SAR" => "Riyal saudí",
+        Sbd = "SBD" => "Dólar de las Islas Salomón",
+        Scr = "SCR" => "Rupia de Seychelles",
+        Sdg = "SDG" => "Libra sudanesa",
+        Sek = "SEK" => "Corona Sueca",
+        Sgd = "SGD" => "Dólar de Singapur",
+        Shp = "SHP" => "Libra de Santa Helena",
+        Sll = "SLL" => "Leona",
+        Sos = "SOS" => "Chelín somalí",
+        Srd = "SRD" => "Dólar de Suriname",
+        Ssp = "SSP" => "Libra sudanesa Sur",
+        Stn = "STN" => "Dobra",
+        Svc = "SVC" => "Colon El Salvador",
+        Syp = "SYP" => "Libra Siria",
+        Szl = "SZL" => "Lilangeni",
+        Thb = "THB" => "Baht",
+        Tjs = "TJS" => "Somoni",
+        Tmt = "TMT" => "Turkmenistán nuevo manat",
+        Tnd = "TND" => "Dinar tunecino",
+        Top = "TOP" => "Pa'anga",
+        Try = "TRY" => "Lira turca",
+        Ttd = "TTD" => "Dólar de Trinidad y Tobago",
+        Twd = "TWD" => "Nuevo dólar de Taiwán",
+        Tzs = "TZS" => "Shilling tanzano",
+        Uah = "UAH" => "Hryvnia",
+        Ugx = "UGX" => "Shilling de Uganda",
+        Usd = "USD" => "Dolar americano",
+        Usn = "USN" => "Dólar estadounidense (día siguiente)",
+        Uyi = "UYI" => "Peso Uruguay en Unidades Indexadas (URUIURUI)",
+        Uyu = "UYU" => "Peso Uruguayo",
+        Uyw = "UYW" => "Unidad previsional",
+        Uzs = "UZS" => "Uzbekistán Sum",
+        Ves = "VES" => "Bolívar Soberano",
+        Vnd = "VND" => "Dong",
+        Vuv = "VUV" => "Vatu",
+        Wst = "WST" => "Tala",
+        Xaf = "XAF" => "Franco CFA BEAC",
+        Xag = "XAG" => "Plata",
+        Xau = "XAU" => "Oro",
+        Xba = "XBA" => "Unidad de Mercados de Bonos Unidad Europea Composite (EURCO)",
+        Xbb = "XBB" => "Unidad Monetaria de Bonos de Mercados Unidad Europea (UEM-6)",
+        Xbc = "XBC" => "Mercados de Bonos Unidad Europea unidad de cuenta a 9 (UCE-9)",
+        Xbd = "XBD" => "Mercados de Bonos Unidad Europea unidad de cuenta a 17 (UCE-17)",
+        Xcd = "XCD" => "Dólar del Caribe Oriental",
+        Xdr = "XDR" => "DEG (Derechos Especiales de Giro)",
+        Xof = "XOF" => "Franco CFA BCEAO",
+        Xpd = "XPD" => "Paladio",
+        Xpf = "XPF" => "Franco CFP",
+        Xpt = "XPT" => "Platino",
+        Xsu = "XSU" => "Sucre",
+        Xts = "XTS" => "Códigos reservados específicamente para propósitos de prueba",
+        Xua = "XUA" => "Unidad ADB de Cuenta",
+        Xxx = "XXX" => "Los códigos asignados para las transacciones en que intervenga ninguna moneda",
+        Yer = "YER" => "Rial yemení",
+        Zar = "ZAR" => "Rand",
+        Zmw = "ZMW" => "Kwacha zambiano",
+        Zwl = "ZWL" => "Zimbabwe Dólar",
+    }
+}
+
+impl RegimenFiscal {
+    /// `true` si el régimen aplica a personas físicas
+    pub fn aplica_persona_fisica(&self) -> bool {
+        use RegimenFiscal::*;
+        matches!(
+            self,
+            SueldosYSalarios
+                | Arrendamiento
+                | EnajenacionOAdquisicionDeBienes
+                | DemasIngresos
+                | ResidentesEnElExtranjero
+                | IngresosPorDividendos
+                | ActividadesEmpresarialesYProfesionales
+                | IngresosPorIntereses
+                | IngresosPorPremios
+                | SinObligacionesFiscales
+                | IncorporacionFiscal
+                | PlataformasTecnologicas
+                | SimplificadoDeConfianza
+                | RegimenesFiscalesPreferentes
+                | EnajenacionDeAccionesEnBolsa
+        )
+    }
+
+    /// `true` si el régimen aplica a personas morales
+    pub fn aplica_persona_moral(&self) -> bool {
+        use RegimenFiscal::*;
+        matches!(
+            self,
+            GeneralDeLeyPersonasMorales
+                | PersonasMoralesSinFinesDeLucro
+                | ResidentesEnElExtranjero
+                | SociedadesCooperativasDeProduccion
+                | ActividadesAgricolasGanaderasSilvicolasYPesqueras
+                | OpcionalParaGruposDeSociedades
+                | Coordinados
+                | SimplificadoDeConfianza
+                | Hidrocarburos
+        )
+    }
+}
+
+impl Moneda {
+    /// Número de decimales que admite la moneda en los importes del comprobante. `None`
+    /// si la moneda no está en el catálogo.
+    pub fn decimales(&self) -> Option<u32> {
+        use Moneda::*;
+        match self {
+            Bif | Clp | Djf | Gnf | Isk | Jpy | Kmf | Krw | Pyg | Rwf | Ugx | Uyi | Vnd | Vuv
+            | Xaf | Xof | Xpf => Some(0),
+            // Metales, unidades de cuenta y códigos sin moneda
+            Xag | Xau | Xba | Xbb | Xbc | Xbd | Xdr | Xpd | Xpt | Xsu | Xts | Xua | Xxx => Some(0),
+            Bhd | Iqd | Jod | Kwd | Lyd | Omr | Tnd => Some(3),
+            Clf | Uyw => Some(4),
+            Unknown(_) => None,
+            _ => Some(2),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Lectura de comprobantes CFDI 3.2.
+//!
+//! La versión 3.2 usa atributos en minúsculas (`fecha`, `subTotal`, etc.), el impuesto por
+//! nombre (`IVA`) y la tasa como porcentaje (`16.00`). Se lee con sus propios structs y se
+//! convierte al mismo [`Comprobante`] que las demás versiones:
+//!
+//! - `tipoDeComprobante` (ingreso, egreso, traslado) se convierte a [`TipoDeComprobante`].
+//! - `metodoDePago` corresponde a la [`FormaPago`] de las versiones posteriores, y
+//!   `formaDePago` (ej. "Pago en una sola exhibición") al [`MetodoPago`].
+//! - El `Regimen` del primer nodo `RegimenFiscal` del emisor es su régimen fiscal.
+//! - Los traslados son de tipo Tasa, y la `tasa` se divide entre 100 para obtener la
+//!   `TasaOCuota`.
+//! - Sin `Moneda` se considera MXN.
+//! - Los atributos que no existen en 3.2 y son requeridos desde 3.3 (`ClaveProdServ`,
+//!   `ClaveUnidad` y `UsoCFDI`) quedan vacíos.
+//!
+//! Los domicilios y la información aduanera de los conceptos no se leen. Los complementos
+//! distintos del [`TimbreFiscalDigital`] (versión 1.0) se conservan como xml, igual que en las
+//! demás versiones.
+
+use serde::Deserialize;
+
+use crate::catalogos::{
+    FormaPago, Impuesto, MetodoPago, Moneda, RegimenFiscal, TipoDeComprobante, TipoFactor, UsoCfdi,
+};
+use crate::lectura::{self, ErrorLectura};
+use crate::rfc::Rfc;
+use crate::{fechas, Decimal, NaiveDateTime};
+use crate::{
+    Complemento, Comprobante, Concepto, Conceptos, Emisor, Impuestos, Receptor, Retencion,
+    Retenciones, TimbreFiscalDigital, Traslado, Traslados,
+};
+
+/// Lee un CFDI 3.2 y lo convierte a [`Comprobante`]
+pub(crate) fn parse(xml: &str) -> Result<Comprobante, ErrorLectura> {
+    let comprobante: Comprobante32 = lectura::deserializar(xml, "Comprobante")?;
+    Ok(comprobante.into())
+}
+
+#[derive(Deserialize)]
+struct Comprobante32 {
+    #[serde(rename = "@version")]
+    version: String,
+    #[serde(rename = "@serie")]
+    serie: Option<String>,
+    #[serde(rename = "@folio")]
+    folio: Option<String>,
+    #[serde(rename = "@fecha", with = "fechas::formato")]
+    fecha: NaiveDateTime,
+    #[serde(rename = "@sello")]
+    sello: Option<String>,
+    #[serde(rename = "@formaDePago")]
+    forma_de_pago: Option<String>,
+    #[serde(rename = "@noCertificado")]
+    no_certificado: Option<String>,
+    #[serde(rename = "@certificado")]
+    certificado: Option<String>,
+    #[serde(rename = "@condicionesDePago")]
+    condiciones_de_pago: Option<String>,
+    #[serde(rename = "@subTotal")]
+    subtotal: Decimal,
+    #[serde(rename = "@descuento")]
+    descuento: Option<Decimal>,
+    #[serde(rename = "@TipoCambio")]
+    tipo_cambio: Option<Decimal>,
+    #[serde(rename = "@Moneda")]
+    moneda: Option<String>,
+    #[serde(rename = "@total")]
+    total: Decimal,
+    #[serde(rename = "@tipoDeComprobante")]
+    tipo_comprobante: String,
+    #[serde(rename = "@metodoDePago")]
+    metodo_de_pago: Option<String>,
+    #[serde(rename = "@LugarExpedicion")]
+    lugar_expedicion: String,
+
+    #[serde(rename = "Emisor")]
+    emisor: Emisor32,
+    #[serde(rename = "Receptor")]
+    receptor: Receptor32,
+    #[serde(rename = "Conceptos")]
+    conceptos: Conceptos32,
+    #[serde(rename = "Impuestos")]
+    impuestos: Option<Impuestos32>,
+    #[serde(rename = "Complemento")]
+    complemento: Option<Complemento32>,
+}
+
+#[derive(Deserialize)]
+struct Emisor32 {
+    #[serde(rename = "@rfc")]
+    rfc: Rfc,
+    #[serde(rename = "@nombre")]
+    nombre: Option<String>,
+    #[serde(rename = "RegimenFiscal", default)]
+    regimen_fiscal: Vec<Regimen32>,
+}
+
+#[derive(Deserialize)]
+struct Regimen32 {
+    #[serde(rename = "@Regimen")]
+    regimen: String,
+}
+
+#[derive(Deserialize)]
+struct Receptor32 {
+    #[serde(rename = "@rfc")]
+    rfc: Rfc,
+    #[serde(rename = "@nombre")]
+    nombre: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Conceptos32 {
+    #[serde(rename = "Concepto")]
+    concepto: Vec<Concepto32>,
+}
+
+#[derive(Deserialize)]
+struct Concepto32 {
+    #[serde(rename = "@cantidad")]
+    cantidad: Decimal,
+    #[serde(rename = "@unidad")]
+    unidad: Option<String>,
+    #[serde(rename = "@noIdentificacion")]
+    no_identificacion: Option<String>,
+    #[serde(rename = "@descripcion")]
+    descripcion: String,
+    #[serde(rename = "@valorUnitario")]
+    valor_unitario: Decimal,
+    #[serde(rename = "@importe")]
+    importe: Decimal,
+}
+
+#[derive(Deserialize)]
+struct Impuestos32 {
+    #[serde(rename = "@totalImpuestosRetenidos")]
+    total_impuestos_retenidos: Option<Decimal>,
+    #[serde(rename = "@totalImpuestosTrasladados")]
+    total_impuestos_trasladados: Option<Decimal>,
+    #[serde(rename = "Retenciones")]
+    retenciones: Option<Retenciones32>,
+    #[serde(rename = "Traslados")]
+    traslados: Option<Traslados32>,
+}
+
+#[derive(Deserialize)]
+struct Retenciones32 {
+    #[serde(rename = "Retencion")]
+    retencion: Vec<Retencion32>,
+}
+
+#[derive(Deserialize)]
+struct Retencion32 {
+    #[serde(rename = "@impuesto")]
+    impuesto: String,
+    #[serde(rename = "@importe")]
+    importe: Decimal,
+}
+
+#[derive(Deserialize)]
+struct Traslados32 {
+    #[serde(rename = "Traslado")]
+    traslado: Vec<Traslado32>,
+}
+
+#[derive(Deserialize)]
+struct Traslado32 {
+    #[serde(rename = "@impuesto")]
+    impuesto: String,
+    #[serde(rename = "@tasa")]
+    tasa: Decimal,
+    #[serde(rename = "@importe")]
+    importe: Decimal,
+}
+
+#[derive(Deserialize)]
+struct Complemento32 {
+    #[serde(rename = "TimbreFiscalDigital")]
+    timbre_fiscal_digital: Option<Timbre10>,
+}
+
+#[derive(Deserialize)]
+struct Timbre10 {
+    #[serde(rename = "@version")]
+    version: String,
+    #[serde(rename = "@UUID")]
+    uuid: String,
+    #[serde(rename = "@FechaTimbrado", with = "fechas::formato")]
+    fecha_timbrado: NaiveDateTime,
+    #[serde(rename = "@selloCFD")]
+    sello_cfd: String,
+    #[serde(rename = "@noCertificadoSAT")]
+    no_certificado_sat: String,
+    #[serde(rename = "@selloSAT")]
+    sello_sat: String,
+}
+
+fn impuesto(nombre: &str) -> Impuesto {
+    match nombre {
+        "ISR" => Impuesto::Isr,
+        "IVA" => Impuesto::Iva,
+        "IEPS" => Impuesto::Ieps,
+        otro => Impuesto::from(otro),
+    }
+}
+
+fn tipo_comprobante(tipo: &str) -> TipoDeComprobante {
+    match tipo {
+        "ingreso" => TipoDeComprobante::Ingreso,
+        "egreso" => TipoDeComprobante::Egreso,
+        "traslado" => TipoDeComprobante::Traslado,
+        otro => TipoDeComprobante::from(otro),
+    }
+}
+
+fn metodo_pago(forma_de_pago: &str) -> MetodoPago {
+    let texto = forma_de_pago.to_lowercase();
+    if texto.contains("una sola exhibici") {
+        MetodoPago::PagoEnUnaExhibicion
+    } else if texto.contains("parcialidad") {
+        MetodoPago::PagoEnParcialidadesODiferido
+    } else {
+        MetodoPago::from(forma_de_pago)
+    }
+}
+
+impl From<Comprobante32> for Comprobante {
+    fn from(c: Comprobante32) -> Self {
+        let regimen = c
+            .emisor
+            .regimen_fiscal
+            .first()
+            .map(|r| RegimenFiscal::from(r.regimen.as_str()))
+            .unwrap_or_else(|| RegimenFiscal::from(""));
+
+        let concepto = c
+            .conceptos
+            .concepto
+            .into_iter()
+            .map(|concepto| Concepto {
+                clave_product: String::new(),
+                no_identificacion: concepto.no_identificacion,
+                cantidad: concepto.cantidad,
+                clave_unidad: String::new(),
+                unidad: concepto.unidad,
+                descripcion: concepto.descripcion,
+                valor_unitario: concepto.valor_unitario,
+                importe: concepto.importe,
+                descuento: None,
+                objeto_imp: None,
+                impuestos: None,
+            })
+            .collect();
+
+        let impuestos = c.impuestos.map(|i| Impuestos {
+            total_impuestos_retenidos: i.total_impuestos_retenidos,
+            total_impuestos_trasladados: i.total_impuestos_trasladados,
+            retenciones: i.retenciones.map(|r| Retenciones {
+                retencion: r
+                    .retencion
+                    .into_iter()
+                    .map(|r| Retencion {
+                        base: None,
+                        impuesto: impuesto(&r.impuesto),
+                        tipo_factor: None,
+                        tasa_o_cuota: None,
+                        importe: r.importe,
+                    })
+                    .collect(),
+            }),
+            traslados: i.traslados.map(|t| Traslados {
+                traslado: t
+                    .traslado
+                    .into_iter()
+                    .map(|t| Traslado {
+                        base: None,
+                        impuesto: impuesto(&t.impuesto),
+                        tipo_factor: TipoFactor::Tasa,
+                        tasa_o_cuota: Some(t.tasa / Decimal::ONE_HUNDRED),
+                        importe: Some(t.importe),
+                    })
+                    .collect(),
+            }),
+        });
+
+        let complemento = c.complemento.map(|complemento| Complemento {
+            timbre_fiscal_digital: complemento.timbre_fiscal_digital.map(|tfd| {
+                TimbreFiscalDigital {
+                    version: tfd.version,
+                    uuid: tfd.uuid,
+                    fecha_timbrado: tfd.fecha_timbrado,
+                    rfc_prov_certif: None,
+                    leyenda: None,
+                    sello_cfd: tfd.sello_cfd,
+                    no_certificado_sat: tfd.no_certificado_sat,
+                    sello_sat: tfd.sello_sat,
+                }
+            }),
+            pagos: None,
+            nomina: vec![],
+            carta_porte: None,
+            comercio_exterior: None,
+            otros: vec![],
+        });
+
+        Comprobante {
+            version: c.version,
+            serie: c.serie,
+            folio: c.folio,
+            fecha: c.fecha,
+            sello: c.sello,
+            forma_pago: c.metodo_de_pago.as_deref().map(FormaPago::from),
+            no_certificado: c.no_certificado,
+            certificado: c.certificado,
+            condiciones_de_pago: c.condiciones_de_pago,
+            subtotal: c.subtotal,
+            descuento: c.descuento,
+            moneda: c.moneda.as_deref().map(Moneda::from).unwrap_or(Moneda::Mxn),
+            tipo_cambio: c.tipo_cambio,
+            total: c.total,
+            tipo_comprobante: tipo_comprobante(&c.tipo_comprobante),
+            exportacion: None,
+            metodo_pago: c.forma_de_pago.as_deref().map(metodo_pago),
+            lugar_expedicion: c.lugar_expedicion,
+            confirmacion: None,
+            informacion_global: None,
+            cfdi_relacionados: vec![],
+            emisor: Emisor {
+                rfc: c.emisor.rfc,
+                nombre: c.emisor.nombre,
+                regimen_fiscal: regimen,
+                fac_atr_adquirente: None,
+            },
+            receptor: Receptor {
+                rfc: c.receptor.rfc,
+                nombre: c.receptor.nombre,
+                domicilio_fiscal: None,
+                residencia_fiscal: None,
+                num_reg_id_trib: None,
+                regimen_fiscal: None,
+                uso_cfdi: UsoCfdi::from(""),
+            },
+            conceptos: Conceptos { concepto },
+            impuestos,
+            complemento,
+            addenda: vec![],
+            forma_original: None,
+        }
+    }
+}
@@ -0,0 +1,1095 @@
+//! Complemento Carta Porte (`cartaporte31:CartaPorte`, y versiones anteriores
+//! `cartaporte30` y `cartaporte20`).
+//!
+//! Las tres versiones comparten el mismo nombre de nodo, por lo que se leen en el mismo
+//! struct [`CartaPorte`]. Los atributos que no existen en alguna versión son opcionales, y
+//! [`CartaPorte::version`] indica cuál se recibió.
+//!
+//! ```markdown
+//!|-CartaPorte
+//!     |-- RegimenesAduaneros (opcional, 3.1)
+//!     |-- Ubicaciones
+//!          |-- Ubicacion (2..n)
+//!               |-- Domicilio (opcional)
+//!     |-- Mercancias
+//!          |-- Mercancia (1..n)
+//!          |-- Autotransporte (opcional)
+//!          |-- TransporteMaritimo (opcional)
+//!          |-- TransporteAereo (opcional)
+//!          |-- TransporteFerroviario (opcional)
+//!     |-- FiguraTransporte (opcional)
+//!          |-- TiposFigura (1..n)
+//!     |**-> id_ccp (3.0+)
+//! ```
+//!
+//! ```rust
+//! let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4"
+//!     xmlns:cartaporte31="http://www.sat.gob.mx/CartaPorte31" Version="4.0"
+//!     Fecha="2024-03-10T08:00:00" SubTotal="0" Moneda="XXX" Total="0" TipoDeComprobante="T"
+//!     Exportacion="01" LugarExpedicion="44100">
+//!   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//!   <cfdi:Receptor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE"
+//!       DomicilioFiscalReceptor="44100" RegimenFiscalReceptor="601" UsoCFDI="S01"/>
+//!   <cfdi:Conceptos>
+//!     <cfdi:Concepto ClaveProdServ="78101800" Cantidad="1" ClaveUnidad="E48"
+//!         Descripcion="Flete" ValorUnitario="0" Importe="0" ObjetoImp="01"/>
+//!   </cfdi:Conceptos>
+//!   <cfdi:Complemento>
+//!     <cartaporte31:CartaPorte Version="3.1" IdCCP="CCC5E3F2-7A51-4B28-A6C1-1D7E2F0B9A11"
+//!         TranspInternac="No" TotalDistRec="120">
+//!       <cartaporte31:Ubicaciones>
+//!         <cartaporte31:Ubicacion TipoUbicacion="Origen" RFCRemitenteDestinatario="EKU9003173C9"
+//!             FechaHoraSalidaLlegada="2024-03-10T08:00:00">
+//!           <cartaporte31:Domicilio Estado="JAL" Pais="MEX" CodigoPostal="44100"/>
+//!         </cartaporte31:Ubicacion>
+//!         <cartaporte31:Ubicacion TipoUbicacion="Destino" RFCRemitenteDestinatario="EKU9003173C9"
+//!             FechaHoraSalidaLlegada="2024-03-10T11:00:00" DistanciaRecorrida="120">
+//!           <cartaporte31:Domicilio Estado="GUA" Pais="MEX" CodigoPostal="37000"/>
+//!         </cartaporte31:Ubicacion>
+//!       </cartaporte31:Ubicaciones>
+//!       <cartaporte31:Mercancias PesoBrutoTotal="1000" UnidadPeso="KGM" NumTotalMercancias="1">
+//!         <cartaporte31:Mercancia BienesTransp="24112700" Descripcion="Tarimas" Cantidad="10"
+//!             ClaveUnidad="H87" PesoEnKg="1000"/>
+//!         <cartaporte31:Autotransporte PermSCT="TPAF01" NumPermisoSCT="0X2XTXZ0X5X0X3X2X1X0">
+//!           <cartaporte31:IdentificacionVehicular ConfigVehicular="C2" PesoBrutoVehicular="10"
+//!               PlacaVM="501AAA" AnioModeloVM="2020"/>
+//!           <cartaporte31:Seguros AseguraRespCivil="SW Seguros" PolizaRespCivil="123456"/>
+//!         </cartaporte31:Autotransporte>
+//!       </cartaporte31:Mercancias>
+//!       <cartaporte31:FiguraTransporte>
+//!         <cartaporte31:TiposFigura TipoFigura="01" RFCFigura="VAAM130719H60"
+//!             NumLicencia="a234567890" NombreFigura="Juan Perez"/>
+//!       </cartaporte31:FiguraTransporte>
+//!     </cartaporte31:CartaPorte>
+//!   </cfdi:Complemento>
+//! </cfdi:Comprobante>"#;
+//!
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//! let carta_porte = cfdi.complemento.unwrap().carta_porte.unwrap();
+//!
+//! assert_eq!(carta_porte.version, "3.1");
+//! assert!(carta_porte.id_ccp.is_some());
+//! assert_eq!(carta_porte.get_ubicaciones().len(), 2);
+//! let autotransporte = carta_porte.mercancias.autotransporte.unwrap();
+//! assert_eq!(autotransporte.identificacion_vehicular.placa_vm, "501AAA");
+//! ```
+
+use crate::cadena::Atributo::{self, Opcional, Requerido};
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento Carta Porte
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartaPorte {
+    /// Versión del complemento: "2.0", "3.0" o "3.1"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Identificador del Complemento Carta Porte. Solo versiones 3.0+
+    #[serde(rename = "@IdCCP")]
+    pub id_ccp: Option<String>,
+
+    /// "Sí" o "No"
+    #[serde(rename = "@TranspInternac")]
+    pub transp_internac: String,
+
+    /// Solo versión 3.0. En 3.1 se usa [`CartaPorte::regimenes_aduaneros`]
+    #[serde(rename = "@RegimenAduanero")]
+    pub regimen_aduanero: Option<String>,
+
+    #[serde(rename = "@EntradaSalidaMerc")]
+    pub entrada_salida_merc: Option<String>,
+
+    #[serde(rename = "@PaisOrigenDestino")]
+    pub pais_origen_destino: Option<String>,
+
+    #[serde(rename = "@ViaEntradaSalida")]
+    pub via_entrada_salida: Option<String>,
+
+    #[serde(rename = "@TotalDistRec")]
+    pub total_dist_rec: Option<Decimal>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@RegistroISTMO")]
+    pub registro_istmo: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@UbicacionPoloOrigen")]
+    pub ubicacion_polo_origen: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@UbicacionPoloDestino")]
+    pub ubicacion_polo_destino: Option<String>,
+
+    /// Solo versión 3.1
+    #[serde(rename = "RegimenesAduaneros")]
+    pub regimenes_aduaneros: Option<RegimenesAduaneros>,
+
+    #[serde(rename = "Ubicaciones")]
+    pub ubicaciones: Ubicaciones,
+
+    #[serde(rename = "Mercancias")]
+    pub mercancias: Mercancias,
+
+    #[serde(rename = "FiguraTransporte")]
+    pub figura_transporte: Option<FiguraTransporte>,
+}
+
+impl CartaPorte {
+    /// Regresa un vector con las [`Ubicacion`] del traslado
+    pub fn get_ubicaciones(&self) -> Vec<Ubicacion> {
+        self.ubicaciones.ubicacion.clone()
+    }
+
+    /// Regresa un vector con las [`Mercancia`] transportadas
+    pub fn get_mercancias(&self) -> Vec<Mercancia> {
+        self.mercancias.mercancia.clone()
+    }
+}
+
+/// Lista de [`RegimenAduaneroCCP`] (versión 3.1)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegimenesAduaneros {
+    #[serde(rename = "RegimenAduaneroCCP")]
+    pub regimen_aduanero: Vec<RegimenAduaneroCCP>,
+}
+
+/// Régimen aduanero aplicable al transporte internacional (versión 3.1)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegimenAduaneroCCP {
+    #[serde(rename = "@RegimenAduanero")]
+    pub regimen_aduanero: String,
+}
+
+/// Lista de [`Ubicacion`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Ubicaciones {
+    #[serde(rename = "Ubicacion")]
+    pub ubicacion: Vec<Ubicacion>,
+}
+
+/// Origen o destino del traslado
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Ubicacion {
+    /// "Origen" o "Destino"
+    #[serde(rename = "@TipoUbicacion")]
+    pub tipo_ubicacion: String,
+
+    #[serde(rename = "@IDUbicacion")]
+    pub id_ubicacion: Option<String>,
+
+    #[serde(rename = "@RFCRemitenteDestinatario")]
+    pub rfc_remitente_destinatario: String,
+
+    #[serde(rename = "@NombreRemitenteDestinatario")]
+    pub nombre_remitente_destinatario: Option<String>,
+
+    #[serde(rename = "@NumRegIdTrib")]
+    pub num_reg_id_trib: Option<String>,
+
+    #[serde(rename = "@ResidenciaFiscal")]
+    pub residencia_fiscal: Option<String>,
+
+    #[serde(rename = "@NumEstacion")]
+    pub num_estacion: Option<String>,
+
+    #[serde(rename = "@NombreEstacion")]
+    pub nombre_estacion: Option<String>,
+
+    #[serde(rename = "@NavegacionTrafico")]
+    pub navegacion_trafico: Option<String>,
+
+    #[serde(rename = "@FechaHoraSalidaLlegada")]
+    pub fecha_hora_salida_llegada: String,
+
+    #[serde(rename = "@TipoEstacion")]
+    pub tipo_estacion: Option<String>,
+
+    #[serde(rename = "@DistanciaRecorrida")]
+    pub distancia_recorrida: Option<Decimal>,
+
+    #[serde(rename = "Domicilio")]
+    pub domicilio: Option<Domicilio>,
+}
+
+/// Domicilio de una ubicación o de una figura de transporte
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Domicilio {
+    #[serde(rename = "@Calle")]
+    pub calle: Option<String>,
+
+    #[serde(rename = "@NumeroExterior")]
+    pub numero_exterior: Option<String>,
+
+    #[serde(rename = "@NumeroInterior")]
+    pub numero_interior: Option<String>,
+
+    #[serde(rename = "@Colonia")]
+    pub colonia: Option<String>,
+
+    #[serde(rename = "@Localidad")]
+    pub localidad: Option<String>,
+
+    #[serde(rename = "@Referencia")]
+    pub referencia: Option<String>,
+
+    #[serde(rename = "@Municipio")]
+    pub municipio: Option<String>,
+
+    #[serde(rename = "@Estado")]
+    pub estado: String,
+
+    #[serde(rename = "@Pais")]
+    pub pais: String,
+
+    #[serde(rename = "@CodigoPostal")]
+    pub codigo_postal: String,
+}
+
+/// Mercancías transportadas y el medio de transporte
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Mercancias {
+    #[serde(rename = "@PesoBrutoTotal")]
+    pub peso_bruto_total: Decimal,
+
+    #[serde(rename = "@UnidadPeso")]
+    pub unidad_peso: String,
+
+    #[serde(rename = "@PesoNetoTotal")]
+    pub peso_neto_total: Option<Decimal>,
+
+    #[serde(rename = "@NumTotalMercancias")]
+    pub num_total_mercancias: u32,
+
+    #[serde(rename = "@CargoPorTasacion")]
+    pub cargo_por_tasacion: Option<Decimal>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@LogisticaInversaRecoleccionDevolucion")]
+    pub logistica_inversa_recoleccion_devolucion: Option<String>,
+
+    #[serde(rename = "Mercancia")]
+    pub mercancia: Vec<Mercancia>,
+
+    #[serde(rename = "Autotransporte")]
+    pub autotransporte: Option<Autotransporte>,
+
+    #[serde(rename = "TransporteMaritimo")]
+    pub transporte_maritimo: Option<TransporteMaritimo>,
+
+    #[serde(rename = "TransporteAereo")]
+    pub transporte_aereo: Option<TransporteAereo>,
+
+    #[serde(rename = "TransporteFerroviario")]
+    pub transporte_ferroviario: Option<TransporteFerroviario>,
+}
+
+/// Un bien transportado
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Mercancia {
+    #[serde(rename = "@BienesTransp")]
+    pub bienes_transp: String,
+
+    #[serde(rename = "@ClaveSTCC")]
+    pub clave_stcc: Option<String>,
+
+    #[serde(rename = "@Descripcion")]
+    pub descripcion: String,
+
+    #[serde(rename = "@Cantidad")]
+    pub cantidad: Decimal,
+
+    #[serde(rename = "@ClaveUnidad")]
+    pub clave_unidad: String,
+
+    #[serde(rename = "@Unidad")]
+    pub unidad: Option<String>,
+
+    #[serde(rename = "@Dimensiones")]
+    pub dimensiones: Option<String>,
+
+    #[serde(rename = "@MaterialPeligroso")]
+    pub material_peligroso: Option<String>,
+
+    #[serde(rename = "@CveMaterialPeligroso")]
+    pub cve_material_peligroso: Option<String>,
+
+    #[serde(rename = "@Embalaje")]
+    pub embalaje: Option<String>,
+
+    #[serde(rename = "@DescripEmbalaje")]
+    pub descrip_embalaje: Option<String>,
+
+    #[serde(rename = "@SectorCOFEPRIS")]
+    pub sector_cofepris: Option<String>,
+
+    #[serde(rename = "@NombreIngredienteActivo")]
+    pub nombre_ingrediente_activo: Option<String>,
+
+    #[serde(rename = "@NomQuimico")]
+    pub nom_quimico: Option<String>,
+
+    #[serde(rename = "@DenominacionGenericaProd")]
+    pub denominacion_generica_prod: Option<String>,
+
+    #[serde(rename = "@DenominacionDistintivaProd")]
+    pub denominacion_distintiva_prod: Option<String>,
+
+    #[serde(rename = "@Fabricante")]
+    pub fabricante: Option<String>,
+
+    #[serde(rename = "@FechaCaducidad")]
+    pub fecha_caducidad: Option<String>,
+
+    #[serde(rename = "@LoteMedicamento")]
+    pub lote_medicamento: Option<String>,
+
+    #[serde(rename = "@FormaFarmaceutica")]
+    pub forma_farmaceutica: Option<String>,
+
+    #[serde(rename = "@CondicionesEspTransp")]
+    pub condiciones_esp_transp: Option<String>,
+
+    #[serde(rename = "@RegistroSanitarioFolioAutorizacion")]
+    pub registro_sanitario_folio_autorizacion: Option<String>,
+
+    #[serde(rename = "@PermisoImportacion")]
+    pub permiso_importacion: Option<String>,
+
+    #[serde(rename = "@FolioImpoVUCEM")]
+    pub folio_impo_vucem: Option<String>,
+
+    #[serde(rename = "@NumCAS")]
+    pub num_cas: Option<String>,
+
+    #[serde(rename = "@RazonSocialEmpImp")]
+    pub razon_social_emp_imp: Option<String>,
+
+    #[serde(rename = "@NumRegSanPlagCOFEPRIS")]
+    pub num_reg_san_plag_cofepris: Option<String>,
+
+    #[serde(rename = "@DatosFabricante")]
+    pub datos_fabricante: Option<String>,
+
+    #[serde(rename = "@DatosFormulador")]
+    pub datos_formulador: Option<String>,
+
+    #[serde(rename = "@DatosMaquilador")]
+    pub datos_maquilador: Option<String>,
+
+    #[serde(rename = "@UsoAutorizado")]
+    pub uso_autorizado: Option<String>,
+
+    #[serde(rename = "@PesoEnKg")]
+    pub peso_en_kg: Decimal,
+
+    #[serde(rename = "@ValorMercancia")]
+    pub valor_mercancia: Option<Decimal>,
+
+    #[serde(rename = "@Moneda")]
+    pub moneda: Option<String>,
+
+    #[serde(rename = "@FraccionArancelaria")]
+    pub fraccion_arancelaria: Option<String>,
+
+    #[serde(rename = "@UUIDComercioExt")]
+    pub uuid_comercio_ext: Option<String>,
+
+    /// Solo versión 3.1
+    #[serde(rename = "@TipoMateria")]
+    pub tipo_materia: Option<String>,
+
+    /// Solo versión 3.1
+    #[serde(rename = "@DescripcionMateria")]
+    pub descripcion_materia: Option<String>,
+
+    /// Solo versión 2.0. En versiones 3.0+ se usa `documentacion_aduanera`
+    #[serde(rename = "Pedimentos", default)]
+    pub pedimentos: Vec<Pedimentos>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "DocumentacionAduanera", default)]
+    pub documentacion_aduanera: Vec<DocumentacionAduanera>,
+
+    #[serde(rename = "GuiasIdentificacion", default)]
+    pub guias_identificacion: Vec<GuiasIdentificacion>,
+
+    #[serde(rename = "CantidadTransporta", default)]
+    pub cantidad_transporta: Vec<CantidadTransporta>,
+
+    #[serde(rename = "DetalleMercancia")]
+    pub detalle_mercancia: Option<DetalleMercancia>,
+}
+
+/// Pedimento de importación (versión 2.0)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Pedimentos {
+    #[serde(rename = "@Pedimento")]
+    pub pedimento: String,
+}
+
+/// Documento aduanero de la mercancía (versiones 3.0+)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DocumentacionAduanera {
+    #[serde(rename = "@TipoDocumento")]
+    pub tipo_documento: String,
+
+    #[serde(rename = "@NumPedimento")]
+    pub num_pedimento: Option<String>,
+
+    #[serde(rename = "@IdentDocAduanero")]
+    pub ident_doc_aduanero: Option<String>,
+
+    #[serde(rename = "@RFCImpo")]
+    pub rfc_impo: Option<String>,
+}
+
+/// Guía de identificación de paquetería
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GuiasIdentificacion {
+    #[serde(rename = "@NumeroGuiaIdentificacion")]
+    pub numero_guia_identificacion: String,
+
+    #[serde(rename = "@DescripGuiaIdentificacion")]
+    pub descrip_guia_identificacion: String,
+
+    #[serde(rename = "@PesoGuiaIdentificacion")]
+    pub peso_guia_identificacion: Decimal,
+}
+
+/// Cantidad de la mercancía que se traslada entre dos ubicaciones
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CantidadTransporta {
+    #[serde(rename = "@Cantidad")]
+    pub cantidad: Decimal,
+
+    #[serde(rename = "@IDOrigen")]
+    pub id_origen: String,
+
+    #[serde(rename = "@IDDestino")]
+    pub id_destino: String,
+
+    #[serde(rename = "@CvesTransporte")]
+    pub cves_transporte: Option<String>,
+}
+
+/// Detalle de la mercancía transportada (principalmente transporte marítimo)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DetalleMercancia {
+    #[serde(rename = "@UnidadPesoMerc")]
+    pub unidad_peso_merc: String,
+
+    #[serde(rename = "@PesoBruto")]
+    pub peso_bruto: Decimal,
+
+    #[serde(rename = "@PesoNeto")]
+    pub peso_neto: Decimal,
+
+    #[serde(rename = "@PesoTara")]
+    pub peso_tara: Decimal,
+
+    #[serde(rename = "@NumPiezas")]
+    pub num_piezas: Option<u32>,
+}
+
+/// Datos del autotransporte federal
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Autotransporte {
+    #[serde(rename = "@PermSCT")]
+    pub perm_sct: String,
+
+    #[serde(rename = "@NumPermisoSCT")]
+    pub num_permiso_sct: String,
+
+    #[serde(rename = "IdentificacionVehicular")]
+    pub identificacion_vehicular: IdentificacionVehicular,
+
+    #[serde(rename = "Seguros")]
+    pub seguros: Seguros,
+
+    #[serde(rename = "Remolques")]
+    pub remolques: Option<Remolques>,
+}
+
+/// Vehículo que realiza el autotransporte
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IdentificacionVehicular {
+    #[serde(rename = "@ConfigVehicular")]
+    pub config_vehicular: String,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@PesoBrutoVehicular")]
+    pub peso_bruto_vehicular: Option<Decimal>,
+
+    #[serde(rename = "@PlacaVM")]
+    pub placa_vm: String,
+
+    #[serde(rename = "@AnioModeloVM")]
+    pub anio_modelo_vm: u16,
+}
+
+/// Seguros del autotransporte
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Seguros {
+    #[serde(rename = "@AseguraRespCivil")]
+    pub asegura_resp_civil: String,
+
+    #[serde(rename = "@PolizaRespCivil")]
+    pub poliza_resp_civil: String,
+
+    #[serde(rename = "@AseguraMedAmbiente")]
+    pub asegura_med_ambiente: Option<String>,
+
+    #[serde(rename = "@PolizaMedAmbiente")]
+    pub poliza_med_ambiente: Option<String>,
+
+    #[serde(rename = "@AseguraCarga")]
+    pub asegura_carga: Option<String>,
+
+    #[serde(rename = "@PolizaCarga")]
+    pub poliza_carga: Option<String>,
+
+    #[serde(rename = "@PrimaSeguro")]
+    pub prima_seguro: Option<Decimal>,
+}
+
+/// Lista de [`Remolque`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Remolques {
+    #[serde(rename = "Remolque")]
+    pub remolque: Vec<Remolque>,
+}
+
+/// Remolque o semirremolque del autotransporte
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Remolque {
+    #[serde(rename = "@SubTipoRem")]
+    pub subtipo_rem: String,
+
+    #[serde(rename = "@Placa")]
+    pub placa: String,
+}
+
+/// Datos del transporte marítimo
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransporteMaritimo {
+    #[serde(rename = "@PermSCT")]
+    pub perm_sct: Option<String>,
+
+    #[serde(rename = "@NumPermisoSCT")]
+    pub num_permiso_sct: Option<String>,
+
+    #[serde(rename = "@NombreAseg")]
+    pub nombre_aseg: Option<String>,
+
+    #[serde(rename = "@NumPolizaSeguro")]
+    pub num_poliza_seguro: Option<String>,
+
+    #[serde(rename = "@TipoEmbarcacion")]
+    pub tipo_embarcacion: String,
+
+    #[serde(rename = "@Matricula")]
+    pub matricula: String,
+
+    #[serde(rename = "@NumeroOMI")]
+    pub numero_omi: String,
+
+    #[serde(rename = "@AnioEmbarcacion")]
+    pub anio_embarcacion: Option<u16>,
+
+    #[serde(rename = "@NombreEmbarc")]
+    pub nombre_embarc: Option<String>,
+
+    #[serde(rename = "@NacionalidadEmbarc")]
+    pub nacionalidad_embarc: String,
+
+    #[serde(rename = "@UnidadesDeArqBruto")]
+    pub unidades_de_arq_bruto: Decimal,
+
+    #[serde(rename = "@TipoCarga")]
+    pub tipo_carga: String,
+
+    /// Solo versión 2.0
+    #[serde(rename = "@NumCertITC")]
+    pub num_cert_itc: Option<String>,
+
+    #[serde(rename = "@Eslora")]
+    pub eslora: Option<Decimal>,
+
+    #[serde(rename = "@Manga")]
+    pub manga: Option<Decimal>,
+
+    #[serde(rename = "@Calado")]
+    pub calado: Option<Decimal>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@Puntal")]
+    pub puntal: Option<Decimal>,
+
+    #[serde(rename = "@LineaNaviera")]
+    pub linea_naviera: Option<String>,
+
+    #[serde(rename = "@NombreAgenteNaviero")]
+    pub nombre_agente_naviero: String,
+
+    #[serde(rename = "@NumAutorizacionNaviero")]
+    pub num_autorizacion_naviero: String,
+
+    #[serde(rename = "@NumViaje")]
+    pub num_viaje: Option<String>,
+
+    #[serde(rename = "@NumConocEmbarc")]
+    pub num_conoc_embarc: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@PermisoTempNavegacion")]
+    pub permiso_temp_navegacion: Option<String>,
+
+    #[serde(rename = "Contenedor", default)]
+    pub contenedor: Vec<Contenedor>,
+}
+
+/// Contenedor del transporte marítimo
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Contenedor {
+    /// Requerido en la versión 2.0, opcional en 3.0+
+    #[serde(rename = "@MatriculaContenedor")]
+    pub matricula_contenedor: Option<String>,
+
+    #[serde(rename = "@TipoContenedor")]
+    pub tipo_contenedor: String,
+
+    #[serde(rename = "@NumPrecinto")]
+    pub num_precinto: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@IdCCPRelacionado")]
+    pub id_ccp_relacionado: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@PlacaVMCCP")]
+    pub placa_vm_ccp: Option<String>,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@FechaCertificacionCCP")]
+    pub fecha_certificacion_ccp: Option<String>,
+
+    /// Remolques en los que se transporta el contenedor. Solo versiones 3.0+
+    #[serde(rename = "RemolquesCCP")]
+    pub remolques: Option<RemolquesCCP>,
+}
+
+/// Lista de [`RemolqueCCP`] (versiones 3.0+)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RemolquesCCP {
+    #[serde(rename = "RemolqueCCP")]
+    pub remolque: Vec<RemolqueCCP>,
+}
+
+/// Remolque en el que se transporta un [`Contenedor`] (versiones 3.0+)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RemolqueCCP {
+    #[serde(rename = "@SubTipoRemCCP")]
+    pub subtipo_rem: String,
+
+    #[serde(rename = "@PlacaCCP")]
+    pub placa: String,
+}
+
+/// Datos del transporte aéreo
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransporteAereo {
+    #[serde(rename = "@PermSCT")]
+    pub perm_sct: String,
+
+    #[serde(rename = "@NumPermisoSCT")]
+    pub num_permiso_sct: String,
+
+    #[serde(rename = "@MatriculaAeronave")]
+    pub matricula_aeronave: Option<String>,
+
+    #[serde(rename = "@NombreAseg")]
+    pub nombre_aseg: Option<String>,
+
+    #[serde(rename = "@NumPolizaSeguro")]
+    pub num_poliza_seguro: Option<String>,
+
+    #[serde(rename = "@NumeroGuia")]
+    pub numero_guia: String,
+
+    #[serde(rename = "@LugarContrato")]
+    pub lugar_contrato: Option<String>,
+
+    #[serde(rename = "@CodigoTransportista")]
+    pub codigo_transportista: String,
+
+    #[serde(rename = "@RFCEmbarcador")]
+    pub rfc_embarcador: Option<String>,
+
+    #[serde(rename = "@NumRegIdTribEmbarc")]
+    pub num_reg_id_trib_embarc: Option<String>,
+
+    #[serde(rename = "@ResidenciaFiscalEmbarc")]
+    pub residencia_fiscal_embarc: Option<String>,
+
+    #[serde(rename = "@NombreEmbarcador")]
+    pub nombre_embarcador: Option<String>,
+}
+
+/// Datos del transporte ferroviario
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransporteFerroviario {
+    #[serde(rename = "@TipoDeServicio")]
+    pub tipo_de_servicio: String,
+
+    /// Solo versiones 3.0+
+    #[serde(rename = "@TipoDeTrafico")]
+    pub tipo_de_trafico: Option<String>,
+
+    #[serde(rename = "@NombreAseg")]
+    pub nombre_aseg: Option<String>,
+
+    #[serde(rename = "@NumPolizaSeguro")]
+    pub num_poliza_seguro: Option<String>,
+
+    #[serde(rename = "DerechosDePaso", default)]
+    pub derechos_de_paso: Vec<DerechosDePaso>,
+
+    #[serde(rename = "Carro")]
+    pub carro: Vec<Carro>,
+}
+
+/// Derecho de paso pagado durante el traslado
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DerechosDePaso {
+    #[serde(rename = "@TipoDerechoDePaso")]
+    pub tipo_derecho_de_paso: String,
+
+    #[serde(rename = "@KilometrajePagado")]
+    pub kilometraje_pagado: Decimal,
+}
+
+/// Carro del tren
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Carro {
+    #[serde(rename = "@TipoCarro")]
+    pub tipo_carro: String,
+
+    #[serde(rename = "@MatriculaCarro")]
+    pub matricula_carro: String,
+
+    #[serde(rename = "@GuiaCarro")]
+    pub guia_carro: String,
+
+    #[serde(rename = "@ToneladasNetasCarro")]
+    pub toneladas_netas_carro: Decimal,
+
+    #[serde(rename = "Contenedor", default)]
+    pub contenedor: Vec<ContenedorCarro>,
+}
+
+/// Contenedor dentro de un [`Carro`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContenedorCarro {
+    #[serde(rename = "@TipoContenedor")]
+    pub tipo_contenedor: String,
+
+    #[serde(rename = "@PesoContenedorVacio")]
+    pub peso_contenedor_vacio: Decimal,
+
+    #[serde(rename = "@PesoNetoMercancia")]
+    pub peso_neto_mercancia: Decimal,
+}
+
+/// Lista de [`TiposFigura`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FiguraTransporte {
+    #[serde(rename = "TiposFigura")]
+    pub tipos_figura: Vec<TiposFigura>,
+}
+
+/// Figura que participa en el transporte (operador, propietario, arrendador, etc.)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TiposFigura {
+    #[serde(rename = "@TipoFigura")]
+    pub tipo_figura: String,
+
+    #[serde(rename = "@RFCFigura")]
+    pub rfc_figura: Option<String>,
+
+    #[serde(rename = "@NumLicencia")]
+    pub num_licencia: Option<String>,
+
+    #[serde(rename = "@NombreFigura")]
+    pub nombre_figura: String,
+
+    #[serde(rename = "@NumRegIdTribFigura")]
+    pub num_reg_id_trib_figura: Option<String>,
+
+    #[serde(rename = "@ResidenciaFiscalFigura")]
+    pub residencia_fiscal_figura: Option<String>,
+
+    #[serde(rename = "PartesTransporte", default)]
+    pub partes_transporte: Vec<PartesTransporte>,
+
+    #[serde(rename = "Domicilio")]
+    pub domicilio: Option<Domicilio>,
+}
+
+/// Parte del transporte de la que la figura es propietaria o arrendadora
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartesTransporte {
+    #[serde(rename = "@ParteTransporte")]
+    pub parte_transporte: String,
+}
+
+const DOMICILIO: &[Atributo] = &[
+    Opcional("Calle"),
+    Opcional("NumeroExterior"),
+    Opcional("NumeroInterior"),
+    Opcional("Colonia"),
+    Opcional("Localidad"),
+    Opcional("Referencia"),
+    Opcional("Municipio"),
+    Requerido("Estado"),
+    Requerido("Pais"),
+    Requerido("CodigoPostal"),
+];
+
+/// Orden de los atributos en la cadena original, según `CartaPorte20.xslt`,
+/// `CartaPorte30.xslt` y `CartaPorte31.xslt`. Los atributos que solo existen en algunas
+/// versiones son opcionales, y solo cambia el orden de los atributos de `Contenedor`.
+pub(crate) fn atributos_cadena(
+    padre: &str,
+    nodo: &str,
+    version: &str,
+) -> Option<&'static [Atributo]> {
+    let atributos: &[Atributo] = match (padre, nodo) {
+        ("", "CartaPorte") => &[
+            Requerido("Version"),
+            Opcional("IdCCP"),
+            Requerido("TranspInternac"),
+            Opcional("RegimenAduanero"),
+            Opcional("EntradaSalidaMerc"),
+            Opcional("PaisOrigenDestino"),
+            Opcional("ViaEntradaSalida"),
+            Opcional("TotalDistRec"),
+            Opcional("RegistroISTMO"),
+            Opcional("UbicacionPoloOrigen"),
+            Opcional("UbicacionPoloDestino"),
+        ],
+        ("CartaPorte", "RegimenesAduaneros") => &[],
+        ("RegimenesAduaneros", "RegimenAduaneroCCP") => &[Requerido("RegimenAduanero")],
+        ("CartaPorte", "Ubicaciones") => &[],
+        ("Ubicaciones", "Ubicacion") => &[
+            Requerido("TipoUbicacion"),
+            Opcional("IDUbicacion"),
+            Requerido("RFCRemitenteDestinatario"),
+            Opcional("NombreRemitenteDestinatario"),
+            Opcional("NumRegIdTrib"),
+            Opcional("ResidenciaFiscal"),
+            Opcional("NumEstacion"),
+            Opcional("NombreEstacion"),
+            Opcional("NavegacionTrafico"),
+            Requerido("FechaHoraSalidaLlegada"),
+            Opcional("TipoEstacion"),
+            Opcional("DistanciaRecorrida"),
+        ],
+        ("Ubicacion", "Domicilio") | ("TiposFigura", "Domicilio") => DOMICILIO,
+        ("CartaPorte", "Mercancias") => &[
+            Requerido("PesoBrutoTotal"),
+            Requerido("UnidadPeso"),
+            Opcional("PesoNetoTotal"),
+            Requerido("NumTotalMercancias"),
+            Opcional("CargoPorTasacion"),
+            Opcional("LogisticaInversaRecoleccionDevolucion"),
+        ],
+        ("Mercancias", "Mercancia") => &[
+            Requerido("BienesTransp"),
+            Opcional("ClaveSTCC"),
+            Requerido("Descripcion"),
+            Requerido("Cantidad"),
+            Requerido("ClaveUnidad"),
+            Opcional("Unidad"),
+            Opcional("Dimensiones"),
+            Opcional("MaterialPeligroso"),
+            Opcional("CveMaterialPeligroso"),
+            Opcional("Embalaje"),
+            Opcional("DescripEmbalaje"),
+            Opcional("SectorCOFEPRIS"),
+            Opcional("NombreIngredienteActivo"),
+            Opcional("NomQuimico"),
+            Opcional("DenominacionGenericaProd"),
+            Opcional("DenominacionDistintivaProd"),
+            Opcional("Fabricante"),
+            Opcional("FechaCaducidad"),
+            Opcional("LoteMedicamento"),
+            Opcional("FormaFarmaceutica"),
+            Opcional("CondicionesEspTransp"),
+            Opcional("RegistroSanitarioFolioAutorizacion"),
+            Opcional("PermisoImportacion"),
+            Opcional("FolioImpoVUCEM"),
+            Opcional("NumCAS"),
+            Opcional("RazonSocialEmpImp"),
+            Opcional("NumRegSanPlagCOFEPRIS"),
+            Opcional("DatosFabricante"),
+            Opcional("DatosFormulador"),
+            Opcional("DatosMaquilador"),
+            Opcional("UsoAutorizado"),
+            Requerido("PesoEnKg"),
+            Opcional("ValorMercancia"),
+            Opcional("Moneda"),
+            Opcional("FraccionArancelaria"),
+            Opcional("UUIDComercioExt"),
+            Opcional("TipoMateria"),
+            Opcional("DescripcionMateria"),
+        ],
+        ("Mercancia", "Pedimentos") => &[Requerido("Pedimento")],
+        ("Mercancia", "DocumentacionAduanera") => &[
+            Requerido("TipoDocumento"),
+            Opcional("NumPedimento"),
+            Opcional("IdentDocAduanero"),
+            Opcional("RFCImpo"),
+        ],
+        ("Mercancia", "GuiasIdentificacion") => &[
+            Requerido("NumeroGuiaIdentificacion"),
+            Requerido("DescripGuiaIdentificacion"),
+            Requerido("PesoGuiaIdentificacion"),
+        ],
+        ("Mercancia", "CantidadTransporta") => &[
+            Requerido("Cantidad"),
+            Requerido("IDOrigen"),
+            Requerido("IDDestino"),
+            Opcional("CvesTransporte"),
+        ],
+        ("Mercancia", "DetalleMercancia") => &[
+            Requerido("UnidadPesoMerc"),
+            Requerido("PesoBruto"),
+            Requerido("PesoNeto"),
+            Requerido("PesoTara"),
+            Opcional("NumPiezas"),
+        ],
+        ("Mercancias", "Autotransporte") => &[Requerido("PermSCT"), Requerido("NumPermisoSCT")],
+        ("Autotransporte", "IdentificacionVehicular") => &[
+            Requerido("ConfigVehicular"),
+            Opcional("PesoBrutoVehicular"),
+            Requerido("PlacaVM"),
+            Requerido("AnioModeloVM"),
+        ],
+        ("Autotransporte", "Seguros") => &[
+            Requerido("AseguraRespCivil"),
+            Requerido("PolizaRespCivil"),
+            Opcional("AseguraMedAmbiente"),
+            Opcional("PolizaMedAmbiente"),
+            Opcional("AseguraCarga"),
+            Opcional("PolizaCarga"),
+            Opcional("PrimaSeguro"),
+        ],
+        ("Autotransporte", "Remolques") => &[],
+        ("Remolques", "Remolque") => &[Requerido("SubTipoRem"), Requerido("Placa")],
+        ("Mercancias", "TransporteMaritimo") => &[
+            Opcional("PermSCT"),
+            Opcional("NumPermisoSCT"),
+            Opcional("NombreAseg"),
+            Opcional("NumPolizaSeguro"),
+            Requerido("TipoEmbarcacion"),
+            Requerido("Matricula"),
+            Requerido("NumeroOMI"),
+            Opcional("AnioEmbarcacion"),
+            Opcional("NombreEmbarc"),
+            Requerido("NacionalidadEmbarc"),
+            Requerido("UnidadesDeArqBruto"),
+            Requerido("TipoCarga"),
+            Opcional("NumCertITC"),
+            Opcional("Eslora"),
+            Opcional("Manga"),
+            Opcional("Calado"),
+            Opcional("Puntal"),
+            Opcional("LineaNaviera"),
+            Requerido("NombreAgenteNaviero"),
+            Requerido("NumAutorizacionNaviero"),
+            Opcional("NumViaje"),
+            Opcional("NumConocEmbarc"),
+            Opcional("PermisoTempNavegacion"),
+        ],
+        ("TransporteMaritimo", "Contenedor") if version == "2.0" => &[
+            Requerido("MatriculaContenedor"),
+            Requerido("TipoContenedor"),
+            Opcional("NumPrecinto"),
+        ],
+        ("TransporteMaritimo", "Contenedor") => &[
+            Requerido("TipoContenedor"),
+            Opcional("MatriculaContenedor"),
+            Opcional("NumPrecinto"),
+            Opcional("IdCCPRelacionado"),
+            Opcional("PlacaVMCCP"),
+            Opcional("FechaCertificacionCCP"),
+        ],
+        ("Contenedor", "RemolquesCCP") => &[],
+        ("RemolquesCCP", "RemolqueCCP") => &[Requerido("SubTipoRemCCP"), Requerido("PlacaCCP")],
+        ("Mercancias", "TransporteAereo") => &[
+            Requerido("PermSCT"),
+            Requerido("NumPermisoSCT"),
+            Opcional("MatriculaAeronave"),
+            Opcional("NombreAseg"),
+            Opcional("NumPolizaSeguro"),
+            Requerido("NumeroGuia"),
+            Opcional("LugarContrato"),
+            Requerido("CodigoTransportista"),
+            Opcional("RFCEmbarcador"),
+            Opcional("NumRegIdTribEmbarc"),
+            Opcional("ResidenciaFiscalEmbarc"),
+            Opcional("NombreEmbarcador"),
+        ],
+        ("Mercancias", "TransporteFerroviario") => &[
+            Requerido("TipoDeServicio"),
+            Opcional("TipoDeTrafico"),
+            Opcional("NombreAseg"),
+            Opcional("NumPolizaSeguro"),
+        ],
+        ("TransporteFerroviario", "DerechosDePaso") => &[
+            Requerido("TipoDerechoDePaso"),
+            Requerido("KilometrajePagado"),
+        ],
+        ("TransporteFerroviario", "Carro") => &[
+            Requerido("TipoCarro"),
+            Requerido("MatriculaCarro"),
+            Requerido("GuiaCarro"),
+            Requerido("ToneladasNetasCarro"),
+        ],
+        ("Carro", "Contenedor") => &[
+            Requerido("TipoContenedor"),
+            Requerido("PesoContenedorVacio"),
+            Requerido("PesoNetoMercancia"),
+        ],
+        ("CartaPorte", "FiguraTransporte") => &[],
+        ("FiguraTransporte", "TiposFigura") => &[
+            Requerido("TipoFigura"),
+            Opcional("RFCFigura"),
+            Opcional("NumLicencia"),
+            Requerido("NombreFigura"),
+            Opcional("NumRegIdTribFigura"),
+            Opcional("ResidenciaFiscalFigura"),
+        ],
+        ("TiposFigura", "PartesTransporte") => &[Requerido("ParteTransporte")],
+        _ => return None,
+    };
+    Some(atributos)
+}
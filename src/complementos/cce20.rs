@@ -0,0 +1,339 @@
+//! Complemento de Comercio Exterior 2.0 (`cce20:ComercioExterior`).
+//!
+//! Cada [`Mercancia`] se relaciona con un [`Concepto`] del comprobante por medio de su
+//! `NoIdentificacion`, ver [`ComercioExterior::mercancias_de`].
+//!
+//! ```markdown
+//!|-ComercioExterior
+//!     |-- Emisor (opcional)
+//!          |-- Domicilio
+//!     |-- Propietario (0..n)
+//!     |-- Receptor (opcional)
+//!          |-- Domicilio (opcional)
+//!     |-- Destinatario (0..n)
+//!          |-- Domicilio (1..n)
+//!     |-- Mercancias
+//!          |-- Mercancia (1..n)
+//!               |-- DescripcionesEspecificas (0..n)
+//! ```
+//!
+//! ```rust
+//! let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4"
+//!     xmlns:cce20="http://www.sat.gob.mx/ComercioExterior20" Version="4.0"
+//!     Fecha="2024-04-02T12:00:00" SubTotal="2000.00" Moneda="MXN" Total="2000.00"
+//!     TipoDeComprobante="I" Exportacion="02" MetodoPago="PUE" LugarExpedicion="44100">
+//!   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//!   <cfdi:Receptor Rfc="XEXX010101000" Nombre="ACME INC" DomicilioFiscalReceptor="44100"
+//!       ResidenciaFiscal="USA" NumRegIdTrib="121585958" RegimenFiscalReceptor="616"
+//!       UsoCFDI="S01"/>
+//!   <cfdi:Conceptos>
+//!     <cfdi:Concepto ClaveProdServ="50161813" NoIdentificacion="CHOC-01" Cantidad="100"
+//!         ClaveUnidad="H87" Descripcion="Chocolate" ValorUnitario="20.00" Importe="2000.00"
+//!         ObjetoImp="01"/>
+//!   </cfdi:Conceptos>
+//!   <cfdi:Complemento>
+//!     <cce20:ComercioExterior Version="2.0" ClaveDePedimento="A1" CertificadoOrigen="0"
+//!         Incoterm="FOB" TipoCambioUSD="17.0000" TotalUSD="117.65">
+//!       <cce20:Emisor>
+//!         <cce20:Domicilio Calle="Av. Juárez" Estado="JAL" Pais="MEX" CodigoPostal="44100"/>
+//!       </cce20:Emisor>
+//!       <cce20:Receptor NumRegIdTrib="121585958">
+//!         <cce20:Domicilio Calle="Main St" Estado="TX" Pais="USA" CodigoPostal="78000"/>
+//!       </cce20:Receptor>
+//!       <cce20:Mercancias>
+//!         <cce20:Mercancia NoIdentificacion="CHOC-01" FraccionArancelaria="1806900100"
+//!             CantidadAduana="100" UnidadAduana="06" ValorUnitarioAduana="1.1765"
+//!             ValorDolares="117.65">
+//!           <cce20:DescripcionesEspecificas Marca="Kemper"/>
+//!         </cce20:Mercancia>
+//!       </cce20:Mercancias>
+//!     </cce20:ComercioExterior>
+//!   </cfdi:Complemento>
+//! </cfdi:Comprobante>"#;
+//!
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//! let cce = cfdi.complemento.as_ref().unwrap().comercio_exterior.as_ref().unwrap();
+//!
+//! assert_eq!(cce.incoterm.as_deref(), Some("FOB"));
+//! let concepto = &cfdi.conceptos.concepto[0];
+//! let mercancias = cce.mercancias_de(concepto);
+//! assert_eq!(mercancias[0].fraccion_arancelaria.as_deref(), Some("1806900100"));
+//! ```
+
+use crate::cadena::Atributo::{self, Opcional, Requerido};
+use crate::{Concepto, Decimal};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento de comercio exterior
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ComercioExterior {
+    /// Versión del complemento, "2.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Solo cuando el comprobante es de tipo traslado
+    #[serde(rename = "@MotivoTraslado")]
+    pub motivo_traslado: Option<String>,
+
+    #[serde(rename = "@ClaveDePedimento")]
+    pub clave_de_pedimento: String,
+
+    /// "0" si no funge como certificado de origen, "1" si sí
+    #[serde(rename = "@CertificadoOrigen")]
+    pub certificado_origen: String,
+
+    #[serde(rename = "@NumCertificadoOrigen")]
+    pub num_certificado_origen: Option<String>,
+
+    #[serde(rename = "@NumeroExportadorConfiable")]
+    pub numero_exportador_confiable: Option<String>,
+
+    /// Clave del INCOTERM aplicable -- Ver Catálogos en SAT
+    #[serde(rename = "@Incoterm")]
+    pub incoterm: Option<String>,
+
+    #[serde(rename = "@Observaciones")]
+    pub observaciones: Option<String>,
+
+    /// Tipo de cambio de pesos a dólares americanos
+    #[serde(rename = "@TipoCambioUSD")]
+    pub tipo_cambio_usd: Decimal,
+
+    /// Importe total de las mercancías en dólares americanos
+    #[serde(rename = "@TotalUSD")]
+    pub total_usd: Decimal,
+
+    #[serde(rename = "Emisor")]
+    pub emisor: Option<EmisorCce>,
+
+    #[serde(rename = "Propietario", default)]
+    pub propietario: Vec<Propietario>,
+
+    #[serde(rename = "Receptor")]
+    pub receptor: Option<ReceptorCce>,
+
+    #[serde(rename = "Destinatario", default)]
+    pub destinatario: Vec<Destinatario>,
+
+    #[serde(rename = "Mercancias")]
+    pub mercancias: Mercancias,
+}
+
+impl ComercioExterior {
+    /// Regresa un vector con las [`Mercancia`] del complemento
+    pub fn get_mercancias(&self) -> Vec<Mercancia> {
+        self.mercancias.mercancia.clone()
+    }
+
+    /// Mercancías cuyo `NoIdentificacion` coincide con el del [`Concepto`]. Si el concepto
+    /// no tiene `NoIdentificacion` el vector viene vacío.
+    pub fn mercancias_de(&self, concepto: &Concepto) -> Vec<&Mercancia> {
+        match &concepto.no_identificacion {
+            Some(no_identificacion) => self
+                .mercancias
+                .mercancia
+                .iter()
+                .filter(|m| &m.no_identificacion == no_identificacion)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// Datos complementarios del emisor
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmisorCce {
+    /// CURP del emisor, cuando es persona física
+    #[serde(rename = "@Curp")]
+    pub curp: Option<String>,
+
+    #[serde(rename = "Domicilio")]
+    pub domicilio: Option<Domicilio>,
+}
+
+/// Propietario de la mercancía, cuando el comprobante es de traslado
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Propietario {
+    #[serde(rename = "@NumRegIdTrib")]
+    pub num_reg_id_trib: String,
+
+    #[serde(rename = "@ResidenciaFiscal")]
+    pub residencia_fiscal: String,
+}
+
+/// Datos complementarios del receptor
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReceptorCce {
+    /// Número de registro de identidad fiscal en el extranjero
+    #[serde(rename = "@NumRegIdTrib")]
+    pub num_reg_id_trib: Option<String>,
+
+    #[serde(rename = "Domicilio")]
+    pub domicilio: Option<Domicilio>,
+}
+
+/// Destinatario de la mercancía, cuando es distinto del receptor
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Destinatario {
+    #[serde(rename = "@NumRegIdTrib")]
+    pub num_reg_id_trib: Option<String>,
+
+    #[serde(rename = "@Nombre")]
+    pub nombre: Option<String>,
+
+    #[serde(rename = "Domicilio")]
+    pub domicilio: Vec<Domicilio>,
+}
+
+/// Domicilio del emisor, receptor o destinatario
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Domicilio {
+    #[serde(rename = "@Calle")]
+    pub calle: String,
+
+    #[serde(rename = "@NumeroExterior")]
+    pub numero_exterior: Option<String>,
+
+    #[serde(rename = "@NumeroInterior")]
+    pub numero_interior: Option<String>,
+
+    #[serde(rename = "@Colonia")]
+    pub colonia: Option<String>,
+
+    #[serde(rename = "@Localidad")]
+    pub localidad: Option<String>,
+
+    #[serde(rename = "@Referencia")]
+    pub referencia: Option<String>,
+
+    #[serde(rename = "@Municipio")]
+    pub municipio: Option<String>,
+
+    #[serde(rename = "@Estado")]
+    pub estado: String,
+
+    #[serde(rename = "@Pais")]
+    pub pais: String,
+
+    #[serde(rename = "@CodigoPostal")]
+    pub codigo_postal: String,
+}
+
+/// Lista de [`Mercancia`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Mercancias {
+    #[serde(rename = "Mercancia")]
+    pub mercancia: Vec<Mercancia>,
+}
+
+/// Mercancía exportada. Se relaciona con un [`Concepto`] por su `NoIdentificacion`
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Mercancia {
+    #[serde(rename = "@NoIdentificacion")]
+    pub no_identificacion: String,
+
+    /// Clave de la fracción arancelaria -- Ver Catálogos en SAT
+    #[serde(rename = "@FraccionArancelaria")]
+    pub fraccion_arancelaria: Option<String>,
+
+    #[serde(rename = "@CantidadAduana")]
+    pub cantidad_aduana: Option<Decimal>,
+
+    #[serde(rename = "@UnidadAduana")]
+    pub unidad_aduana: Option<String>,
+
+    #[serde(rename = "@ValorUnitarioAduana")]
+    pub valor_unitario_aduana: Option<Decimal>,
+
+    /// Valor de la mercancía en dólares americanos
+    #[serde(rename = "@ValorDolares")]
+    pub valor_dolares: Decimal,
+
+    #[serde(rename = "DescripcionesEspecificas", default)]
+    pub descripciones_especificas: Vec<DescripcionesEspecificas>,
+}
+
+/// Marca, modelo y número de serie de la mercancía
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescripcionesEspecificas {
+    #[serde(rename = "@Marca")]
+    pub marca: String,
+
+    #[serde(rename = "@Modelo")]
+    pub modelo: Option<String>,
+
+    #[serde(rename = "@SubModelo")]
+    pub submodelo: Option<String>,
+
+    #[serde(rename = "@NumeroSerie")]
+    pub numero_serie: Option<String>,
+}
+
+const DOMICILIO: &[Atributo] = &[
+    Requerido("Calle"),
+    Opcional("NumeroExterior"),
+    Opcional("NumeroInterior"),
+    Opcional("Colonia"),
+    Opcional("Localidad"),
+    Opcional("Referencia"),
+    Opcional("Municipio"),
+    Requerido("Estado"),
+    Requerido("Pais"),
+    Requerido("CodigoPostal"),
+];
+
+/// Orden de los atributos en la cadena original, según `ComercioExterior20.xslt`
+pub(crate) fn atributos_cadena(
+    padre: &str,
+    nodo: &str,
+    _version: &str,
+) -> Option<&'static [Atributo]> {
+    let atributos: &[Atributo] = match (padre, nodo) {
+        ("", "ComercioExterior") => &[
+            Requerido("Version"),
+            Opcional("MotivoTraslado"),
+            Requerido("ClaveDePedimento"),
+            Requerido("CertificadoOrigen"),
+            Opcional("NumCertificadoOrigen"),
+            Opcional("NumeroExportadorConfiable"),
+            Opcional("Incoterm"),
+            Opcional("Observaciones"),
+            Requerido("TipoCambioUSD"),
+            Requerido("TotalUSD"),
+        ],
+        ("ComercioExterior", "Emisor") => &[Opcional("Curp")],
+        ("ComercioExterior", "Propietario") => {
+            &[Requerido("NumRegIdTrib"), Requerido("ResidenciaFiscal")]
+        }
+        ("ComercioExterior", "Receptor") => &[Opcional("NumRegIdTrib")],
+        ("ComercioExterior", "Destinatario") => &[Opcional("NumRegIdTrib"), Opcional("Nombre")],
+        ("Emisor", "Domicilio") | ("Receptor", "Domicilio") | ("Destinatario", "Domicilio") => {
+            DOMICILIO
+        }
+        ("ComercioExterior", "Mercancias") => &[],
+        ("Mercancias", "Mercancia") => &[
+            Requerido("NoIdentificacion"),
+            Opcional("FraccionArancelaria"),
+            Opcional("CantidadAduana"),
+            Opcional("UnidadAduana"),
+            Opcional("ValorUnitarioAduana"),
+            Requerido("ValorDolares"),
+        ],
+        ("Mercancia", "DescripcionesEspecificas") => &[
+            Requerido("Marca"),
+            Opcional("Modelo"),
+            Opcional("SubModelo"),
+            Opcional("NumeroSerie"),
+        ],
+        _ => return None,
+    };
+    Some(atributos)
+}
@@ -0,0 +1,10 @@
+//! Complementos del CFDI distintos al [`TimbreFiscalDigital`](crate::TimbreFiscalDigital).
+//!
+//! Cada complemento vive en su propio módulo, nombrado como el prefijo que usa el SAT
+//! en el xml (ej. `pago20:Pagos` -> [`pagos20`]). Cuando varias versiones de un complemento
+//! comparten estructura se leen en un solo módulo sin número de versión (ej. [`cartaporte`]).
+
+pub mod cartaporte;
+pub mod cce20;
+pub mod nomina12;
+pub mod pagos20;
@@ -0,0 +1,630 @@
+//! Complemento de Nómina 1.2, revisión E (`nomina12:Nomina`).
+//!
+//! Se incluye en los comprobantes de tipo "N". Un comprobante puede traer más de un
+//! complemento de nómina (ej. una nómina ordinaria y una extraordinaria), por eso
+//! [`Complemento::nomina`](crate::Complemento::nomina) es un vector.
+//!
+//! ```markdown
+//!|-Nomina
+//!     |-- Emisor (opcional)
+//!          |-- EntidadSNCF (opcional)
+//!     |-- Receptor
+//!          |-- SubContratacion (0..n)
+//!     |-- Percepciones (opcional)
+//!          |-- Percepcion (1..n)
+//!               |-- AccionesOTitulos (opcional)
+//!               |-- HorasExtra (0..n)
+//!          |-- JubilacionPensionRetiro (opcional)
+//!          |-- SeparacionIndemnizacion (opcional)
+//!     |-- Deducciones (opcional)
+//!          |-- Deduccion (1..n)
+//!     |-- OtrosPagos (opcional)
+//!          |-- OtroPago (1..n)
+//!               |-- SubsidioAlEmpleo (opcional)
+//!               |-- CompensacionSaldosAFavor (opcional)
+//!     |-- Incapacidades (opcional)
+//!          |-- Incapacidad (1..n)
+//! ```
+//!
+//! ```rust
+//! let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4"
+//!     xmlns:nomina12="http://www.sat.gob.mx/nomina12" Version="4.0"
+//!     Fecha="2024-01-15T18:00:00" SubTotal="10000.00" Descuento="1500.00" Moneda="MXN"
+//!     Total="8500.00" TipoDeComprobante="N" Exportacion="01" MetodoPago="PUE"
+//!     LugarExpedicion="44100">
+//!   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//!   <cfdi:Receptor Rfc="XOJI740919U48" Nombre="INGRID XODAR JIMENEZ"
+//!       DomicilioFiscalReceptor="88965" RegimenFiscalReceptor="605" UsoCFDI="CN01"/>
+//!   <cfdi:Conceptos>
+//!     <cfdi:Concepto ClaveProdServ="84111505" Cantidad="1" ClaveUnidad="ACT"
+//!         Descripcion="Pago de nómina" ValorUnitario="10000.00" Importe="10000.00"
+//!         Descuento="1500.00" ObjetoImp="01"/>
+//!   </cfdi:Conceptos>
+//!   <cfdi:Complemento>
+//!     <nomina12:Nomina Version="1.2" TipoNomina="O" FechaPago="2024-01-15"
+//!         FechaInicialPago="2024-01-01" FechaFinalPago="2024-01-15" NumDiasPagados="15"
+//!         TotalPercepciones="10000.00" TotalDeducciones="1500.00">
+//!       <nomina12:Emisor RegistroPatronal="B5510768108"/>
+//!       <nomina12:Receptor Curp="XOJI740919MDFDMN06" NumSeguridadSocial="12345678901"
+//!           FechaInicioRelLaboral="2020-01-01" Antigüedad="P210W" TipoContrato="01"
+//!           TipoRegimen="02" NumEmpleado="120" PeriodicidadPago="04" ClaveEntFed="JAL"/>
+//!       <nomina12:Percepciones TotalSueldos="10000.00" TotalGravado="9500.00"
+//!           TotalExento="500.00">
+//!         <nomina12:Percepcion TipoPercepcion="001" Clave="P001" Concepto="Sueldo"
+//!             ImporteGravado="9500.00" ImporteExento="0.00"/>
+//!         <nomina12:Percepcion TipoPercepcion="010" Clave="P010" Concepto="Puntualidad"
+//!             ImporteGravado="0.00" ImporteExento="500.00"/>
+//!       </nomina12:Percepciones>
+//!       <nomina12:Deducciones TotalOtrasDeducciones="300.00" TotalImpuestosRetenidos="1200.00">
+//!         <nomina12:Deduccion TipoDeduccion="002" Clave="D002" Concepto="ISR" Importe="1200.00"/>
+//!         <nomina12:Deduccion TipoDeduccion="001" Clave="D001" Concepto="IMSS" Importe="300.00"/>
+//!       </nomina12:Deducciones>
+//!     </nomina12:Nomina>
+//!   </cfdi:Complemento>
+//! </cfdi:Comprobante>"#;
+//!
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//! let nomina = &cfdi.complemento.unwrap().nomina[0];
+//!
+//! assert_eq!(nomina.receptor.num_empleado, "120");
+//! let percepciones = nomina.percepciones_por_clave();
+//! assert_eq!(percepciones["P001"].to_string(), "9500.00");
+//! assert_eq!(nomina.deducciones_por_clave()["D002"].to_string(), "1200.00");
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::cadena::Atributo::{self, Opcional, Requerido};
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento de nómina
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Nomina {
+    /// Versión del complemento, "1.2"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// "O" para nómina ordinaria, "E" para extraordinaria
+    #[serde(rename = "@TipoNomina")]
+    pub tipo_nomina: String,
+
+    #[serde(rename = "@FechaPago")]
+    pub fecha_pago: String,
+
+    #[serde(rename = "@FechaInicialPago")]
+    pub fecha_inicial_pago: String,
+
+    #[serde(rename = "@FechaFinalPago")]
+    pub fecha_final_pago: String,
+
+    #[serde(rename = "@NumDiasPagados")]
+    pub num_dias_pagados: Decimal,
+
+    #[serde(rename = "@TotalPercepciones")]
+    pub total_percepciones: Option<Decimal>,
+
+    #[serde(rename = "@TotalDeducciones")]
+    pub total_deducciones: Option<Decimal>,
+
+    #[serde(rename = "@TotalOtrosPagos")]
+    pub total_otros_pagos: Option<Decimal>,
+
+    #[serde(rename = "Emisor")]
+    pub emisor: Option<EmisorNomina>,
+
+    #[serde(rename = "Receptor")]
+    pub receptor: ReceptorNomina,
+
+    #[serde(rename = "Percepciones")]
+    pub percepciones: Option<Percepciones>,
+
+    #[serde(rename = "Deducciones")]
+    pub deducciones: Option<Deducciones>,
+
+    #[serde(rename = "OtrosPagos")]
+    pub otros_pagos: Option<OtrosPagos>,
+
+    #[serde(rename = "Incapacidades")]
+    pub incapacidades: Option<Incapacidades>,
+}
+
+impl Nomina {
+    /// Regresa un vector con las [`Percepcion`] de la nómina
+    pub fn get_percepciones(&self) -> Vec<Percepcion> {
+        self.percepciones
+            .as_ref()
+            .map(|p| p.percepcion.clone())
+            .unwrap_or_default()
+    }
+
+    /// Regresa un vector con las [`Deduccion`] de la nómina
+    pub fn get_deducciones(&self) -> Vec<Deduccion> {
+        self.deducciones
+            .as_ref()
+            .map(|d| d.deduccion.clone())
+            .unwrap_or_default()
+    }
+
+    /// Regresa un vector con los [`OtroPago`] de la nómina
+    pub fn get_otros_pagos(&self) -> Vec<OtroPago> {
+        self.otros_pagos
+            .as_ref()
+            .map(|o| o.otro_pago.clone())
+            .unwrap_or_default()
+    }
+
+    /// Suma de percepciones (gravado + exento) agrupadas por la `Clave` del patrón
+    pub fn percepciones_por_clave(&self) -> BTreeMap<String, Decimal> {
+        let mut res = BTreeMap::new();
+        for p in self.get_percepciones() {
+            *res.entry(p.clave.clone()).or_default() += p.importe();
+        }
+        res
+    }
+
+    /// Suma de deducciones agrupadas por la `Clave` del patrón
+    pub fn deducciones_por_clave(&self) -> BTreeMap<String, Decimal> {
+        let mut res = BTreeMap::new();
+        for d in self.get_deducciones() {
+            *res.entry(d.clave).or_default() += d.importe;
+        }
+        res
+    }
+}
+
+/// Datos del patrón
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmisorNomina {
+    /// CURP del patrón, cuando es persona física
+    #[serde(rename = "@Curp")]
+    pub curp: Option<String>,
+
+    #[serde(rename = "@RegistroPatronal")]
+    pub registro_patronal: Option<String>,
+
+    #[serde(rename = "@RfcPatronOrigen")]
+    pub rfc_patron_origen: Option<String>,
+
+    /// Solo para entidades del Sistema Nacional de Coordinación Fiscal
+    #[serde(rename = "EntidadSNCF")]
+    pub entidad_sncf: Option<EntidadSNCF>,
+}
+
+/// Origen de los recursos de entidades federativas, municipios, etc.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EntidadSNCF {
+    #[serde(rename = "@OrigenRecurso")]
+    pub origen_recurso: String,
+
+    #[serde(rename = "@MontoRecursoPropio")]
+    pub monto_recurso_propio: Option<Decimal>,
+}
+
+/// Datos del trabajador
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReceptorNomina {
+    #[serde(rename = "@Curp")]
+    pub curp: String,
+
+    #[serde(rename = "@NumSeguridadSocial")]
+    pub num_seguridad_social: Option<String>,
+
+    #[serde(rename = "@FechaInicioRelLaboral")]
+    pub fecha_inicio_rel_laboral: Option<String>,
+
+    /// Antigüedad en formato de duración ISO 8601 (ej. "P210W")
+    #[serde(rename = "@Antigüedad")]
+    pub antiguedad: Option<String>,
+
+    #[serde(rename = "@TipoContrato")]
+    pub tipo_contrato: String,
+
+    #[serde(rename = "@Sindicalizado")]
+    pub sindicalizado: Option<String>,
+
+    #[serde(rename = "@TipoJornada")]
+    pub tipo_jornada: Option<String>,
+
+    #[serde(rename = "@TipoRegimen")]
+    pub tipo_regimen: String,
+
+    #[serde(rename = "@NumEmpleado")]
+    pub num_empleado: String,
+
+    #[serde(rename = "@Departamento")]
+    pub departamento: Option<String>,
+
+    #[serde(rename = "@Puesto")]
+    pub puesto: Option<String>,
+
+    #[serde(rename = "@RiesgoPuesto")]
+    pub riesgo_puesto: Option<String>,
+
+    #[serde(rename = "@PeriodicidadPago")]
+    pub periodicidad_pago: String,
+
+    #[serde(rename = "@Banco")]
+    pub banco: Option<String>,
+
+    #[serde(rename = "@CuentaBancaria")]
+    pub cuenta_bancaria: Option<String>,
+
+    #[serde(rename = "@SalarioBaseCotApor")]
+    pub salario_base_cot_apor: Option<Decimal>,
+
+    #[serde(rename = "@SalarioDiarioIntegrado")]
+    pub salario_diario_integrado: Option<Decimal>,
+
+    /// Clave de la entidad federativa donde se prestó el servicio
+    #[serde(rename = "@ClaveEntFed")]
+    pub clave_ent_fed: String,
+
+    #[serde(rename = "SubContratacion", default)]
+    pub subcontratacion: Vec<SubContratacion>,
+}
+
+/// Personas para las que el trabajador prestó servicios por subcontratación
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubContratacion {
+    #[serde(rename = "@RfcLabora")]
+    pub rfc_labora: String,
+
+    #[serde(rename = "@PorcentajeTiempo")]
+    pub porcentaje_tiempo: Decimal,
+}
+
+/// Percepciones de la nómina y sus totales
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Percepciones {
+    #[serde(rename = "@TotalSueldos")]
+    pub total_sueldos: Option<Decimal>,
+
+    #[serde(rename = "@TotalSeparacionIndemnizacion")]
+    pub total_separacion_indemnizacion: Option<Decimal>,
+
+    #[serde(rename = "@TotalJubilacionPensionRetiro")]
+    pub total_jubilacion_pension_retiro: Option<Decimal>,
+
+    #[serde(rename = "@TotalGravado")]
+    pub total_gravado: Decimal,
+
+    #[serde(rename = "@TotalExento")]
+    pub total_exento: Decimal,
+
+    #[serde(rename = "Percepcion")]
+    pub percepcion: Vec<Percepcion>,
+
+    #[serde(rename = "JubilacionPensionRetiro")]
+    pub jubilacion_pension_retiro: Option<JubilacionPensionRetiro>,
+
+    #[serde(rename = "SeparacionIndemnizacion")]
+    pub separacion_indemnizacion: Option<SeparacionIndemnizacion>,
+}
+
+/// Una percepción del trabajador
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Percepcion {
+    /// Clave del catálogo c_TipoPercepcion -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoPercepcion")]
+    pub tipo_percepcion: String,
+
+    /// Clave interna del patrón
+    #[serde(rename = "@Clave")]
+    pub clave: String,
+
+    #[serde(rename = "@Concepto")]
+    pub concepto: String,
+
+    #[serde(rename = "@ImporteGravado")]
+    pub importe_gravado: Decimal,
+
+    #[serde(rename = "@ImporteExento")]
+    pub importe_exento: Decimal,
+
+    #[serde(rename = "AccionesOTitulos")]
+    pub acciones_o_titulos: Option<AccionesOTitulos>,
+
+    #[serde(rename = "HorasExtra", default)]
+    pub horas_extra: Vec<HorasExtra>,
+}
+
+impl Percepcion {
+    /// Importe gravado más importe exento
+    pub fn importe(&self) -> Decimal {
+        self.importe_gravado + self.importe_exento
+    }
+}
+
+/// Ingresos por acciones o títulos valor (tipo de percepción 045)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccionesOTitulos {
+    #[serde(rename = "@ValorMercado")]
+    pub valor_mercado: Decimal,
+
+    #[serde(rename = "@PrecioAlOtorgarse")]
+    pub precio_al_otorgarse: Decimal,
+}
+
+/// Horas extra pagadas (tipo de percepción 019)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HorasExtra {
+    #[serde(rename = "@Dias")]
+    pub dias: u32,
+
+    #[serde(rename = "@TipoHoras")]
+    pub tipo_horas: String,
+
+    #[serde(rename = "@HorasExtra")]
+    pub horas_extra: u32,
+
+    #[serde(rename = "@ImportePagado")]
+    pub importe_pagado: Decimal,
+}
+
+/// Pagos por jubilación, pensión o retiro
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JubilacionPensionRetiro {
+    #[serde(rename = "@TotalUnaExhibicion")]
+    pub total_una_exhibicion: Option<Decimal>,
+
+    #[serde(rename = "@TotalParcialidad")]
+    pub total_parcialidad: Option<Decimal>,
+
+    #[serde(rename = "@MontoDiario")]
+    pub monto_diario: Option<Decimal>,
+
+    #[serde(rename = "@IngresoAcumulable")]
+    pub ingreso_acumulable: Decimal,
+
+    #[serde(rename = "@IngresoNoAcumulable")]
+    pub ingreso_no_acumulable: Decimal,
+}
+
+/// Pagos por separación o indemnización
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SeparacionIndemnizacion {
+    #[serde(rename = "@TotalPagado")]
+    pub total_pagado: Decimal,
+
+    #[serde(rename = "@NumAñosServicio")]
+    pub num_anios_servicio: u32,
+
+    #[serde(rename = "@UltimoSueldoMensOrd")]
+    pub ultimo_sueldo_mens_ord: Decimal,
+
+    #[serde(rename = "@IngresoAcumulable")]
+    pub ingreso_acumulable: Decimal,
+
+    #[serde(rename = "@IngresoNoAcumulable")]
+    pub ingreso_no_acumulable: Decimal,
+}
+
+/// Deducciones de la nómina y sus totales
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Deducciones {
+    #[serde(rename = "@TotalOtrasDeducciones")]
+    pub total_otras_deducciones: Option<Decimal>,
+
+    #[serde(rename = "@TotalImpuestosRetenidos")]
+    pub total_impuestos_retenidos: Option<Decimal>,
+
+    #[serde(rename = "Deduccion")]
+    pub deduccion: Vec<Deduccion>,
+}
+
+/// Una deducción aplicada al trabajador
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Deduccion {
+    /// Clave del catálogo c_TipoDeduccion -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoDeduccion")]
+    pub tipo_deduccion: String,
+
+    /// Clave interna del patrón
+    #[serde(rename = "@Clave")]
+    pub clave: String,
+
+    #[serde(rename = "@Concepto")]
+    pub concepto: String,
+
+    #[serde(rename = "@Importe")]
+    pub importe: Decimal,
+}
+
+/// Lista de [`OtroPago`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtrosPagos {
+    #[serde(rename = "OtroPago")]
+    pub otro_pago: Vec<OtroPago>,
+}
+
+/// Otros pagos que no son percepciones (ej. subsidio para el empleo)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtroPago {
+    /// Clave del catálogo c_TipoOtroPago -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoOtroPago")]
+    pub tipo_otro_pago: String,
+
+    /// Clave interna del patrón
+    #[serde(rename = "@Clave")]
+    pub clave: String,
+
+    #[serde(rename = "@Concepto")]
+    pub concepto: String,
+
+    #[serde(rename = "@Importe")]
+    pub importe: Decimal,
+
+    #[serde(rename = "SubsidioAlEmpleo")]
+    pub subsidio_al_empleo: Option<SubsidioAlEmpleo>,
+
+    #[serde(rename = "CompensacionSaldosAFavor")]
+    pub compensacion_saldos_a_favor: Option<CompensacionSaldosAFavor>,
+}
+
+/// Subsidio para el empleo causado
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubsidioAlEmpleo {
+    #[serde(rename = "@SubsidioCausado")]
+    pub subsidio_causado: Decimal,
+}
+
+/// Compensación de saldos a favor del trabajador
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompensacionSaldosAFavor {
+    #[serde(rename = "@SaldoAFavor")]
+    pub saldo_a_favor: Decimal,
+
+    #[serde(rename = "@Año")]
+    pub anio: u16,
+
+    #[serde(rename = "@RemanenteSalFav")]
+    pub remanente_sal_fav: Decimal,
+}
+
+/// Lista de [`Incapacidad`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Incapacidades {
+    #[serde(rename = "Incapacidad")]
+    pub incapacidad: Vec<Incapacidad>,
+}
+
+/// Incapacidad del trabajador
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Incapacidad {
+    #[serde(rename = "@DiasIncapacidad")]
+    pub dias_incapacidad: u32,
+
+    /// Clave del catálogo c_TipoIncapacidad -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoIncapacidad")]
+    pub tipo_incapacidad: String,
+
+    #[serde(rename = "@ImporteMonetario")]
+    pub importe_monetario: Option<Decimal>,
+}
+
+/// Orden de los atributos en la cadena original, según `nomina12.xslt`
+pub(crate) fn atributos_cadena(
+    padre: &str,
+    nodo: &str,
+    _version: &str,
+) -> Option<&'static [Atributo]> {
+    let atributos: &[Atributo] = match (padre, nodo) {
+        ("", "Nomina") => &[
+            Requerido("Version"),
+            Requerido("TipoNomina"),
+            Requerido("FechaPago"),
+            Requerido("FechaInicialPago"),
+            Requerido("FechaFinalPago"),
+            Requerido("NumDiasPagados"),
+            Opcional("TotalPercepciones"),
+            Opcional("TotalDeducciones"),
+            Opcional("TotalOtrosPagos"),
+        ],
+        ("Nomina", "Emisor") => &[
+            Opcional("Curp"),
+            Opcional("RegistroPatronal"),
+            Opcional("RfcPatronOrigen"),
+        ],
+        ("Emisor", "EntidadSNCF") => &[Requerido("OrigenRecurso"), Opcional("MontoRecursoPropio")],
+        ("Nomina", "Receptor") => &[
+            Requerido("Curp"),
+            Opcional("NumSeguridadSocial"),
+            Opcional("FechaInicioRelLaboral"),
+            Opcional("Antigüedad"),
+            Requerido("TipoContrato"),
+            Opcional("Sindicalizado"),
+            Opcional("TipoJornada"),
+            Requerido("TipoRegimen"),
+            Requerido("NumEmpleado"),
+            Opcional("Departamento"),
+            Opcional("Puesto"),
+            Opcional("RiesgoPuesto"),
+            Requerido("PeriodicidadPago"),
+            Opcional("Banco"),
+            Opcional("CuentaBancaria"),
+            Opcional("SalarioBaseCotApor"),
+            Opcional("SalarioDiarioIntegrado"),
+            Requerido("ClaveEntFed"),
+        ],
+        ("Receptor", "SubContratacion") => &[Requerido("RfcLabora"), Requerido("PorcentajeTiempo")],
+        ("Nomina", "Percepciones") => &[
+            Opcional("TotalSueldos"),
+            Opcional("TotalSeparacionIndemnizacion"),
+            Opcional("TotalJubilacionPensionRetiro"),
+            Requerido("TotalGravado"),
+            Requerido("TotalExento"),
+        ],
+        ("Percepciones", "Percepcion") => &[
+            Requerido("TipoPercepcion"),
+            Requerido("Clave"),
+            Requerido("Concepto"),
+            Requerido("ImporteGravado"),
+            Requerido("ImporteExento"),
+        ],
+        ("Percepcion", "AccionesOTitulos") => {
+            &[Requerido("ValorMercado"), Requerido("PrecioAlOtorgarse")]
+        }
+        ("Percepcion", "HorasExtra") => &[
+            Requerido("Dias"),
+            Requerido("TipoHoras"),
+            Requerido("HorasExtra"),
+            Requerido("ImportePagado"),
+        ],
+        ("Percepciones", "JubilacionPensionRetiro") => &[
+            Opcional("TotalUnaExhibicion"),
+            Opcional("TotalParcialidad"),
+            Opcional("MontoDiario"),
+            Requerido("IngresoAcumulable"),
+            Requerido("IngresoNoAcumulable"),
+        ],
+        ("Percepciones", "SeparacionIndemnizacion") => &[
+            Requerido("TotalPagado"),
+            Requerido("NumAñosServicio"),
+            Requerido("UltimoSueldoMensOrd"),
+            Requerido("IngresoAcumulable"),
+            Requerido("IngresoNoAcumulable"),
+        ],
+        ("Nomina", "Deducciones") => &[
+            Opcional("TotalOtrasDeducciones"),
+            Opcional("TotalImpuestosRetenidos"),
+        ],
+        ("Deducciones", "Deduccion") => &[
+            Requerido("TipoDeduccion"),
+            Requerido("Clave"),
+            Requerido("Concepto"),
+            Requerido("Importe"),
+        ],
+        ("Nomina", "OtrosPagos") | ("Nomina", "Incapacidades") => &[],
+        ("OtrosPagos", "OtroPago") => &[
+            Requerido("TipoOtroPago"),
+            Requerido("Clave"),
+            Requerido("Concepto"),
+            Requerido("Importe"),
+        ],
+        ("OtroPago", "SubsidioAlEmpleo") => &[Requerido("SubsidioCausado")],
+        ("OtroPago", "CompensacionSaldosAFavor") => &[
+            Requerido("SaldoAFavor"),
+            Requerido("Año"),
+            Requerido("RemanenteSalFav"),
+        ],
+        ("Incapacidades", "Incapacidad") => &[
+            Requerido("DiasIncapacidad"),
+            Requerido("TipoIncapacidad"),
+            Opcional("ImporteMonetario"),
+        ],
+        _ => return None,
+    };
+    Some(atributos)
+}
@@ -0,0 +1,474 @@
+//! Complemento para Recepción de Pagos 2.0 (`pago20:Pagos`), y la versión 1.0 (`pago10`)
+//! de los CFDI 3.3.
+//!
+//! Se incluye en los comprobantes de tipo "P". Cada [`Pago`] indica a qué facturas (PPD)
+//! se aplica mediante sus [`DoctoRelacionado`]. Las dos versiones se leen en los mismos
+//! structs; los atributos que no existen en alguna versión son opcionales.
+//!
+//! ```markdown
+//!|-Pagos
+//!     |-- Totales
+//!     |-- Pago
+//!          |-- DoctoRelacionado
+//!               |-- ImpuestosDR (opcional)
+//!          |-- ImpuestosP (opcional)
+//! ```
+//!
+//! ```rust
+//! let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4"
+//!     xmlns:pago20="http://www.sat.gob.mx/Pagos20" Version="4.0"
+//!     Fecha="2024-02-01T09:00:00" SubTotal="0" Moneda="XXX" Total="0" TipoDeComprobante="P"
+//!     Exportacion="01" LugarExpedicion="44100">
+//!   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//!   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+//!       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="CP01"/>
+//!   <cfdi:Conceptos>
+//!     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+//!         Descripcion="Pago" ValorUnitario="0" Importe="0" ObjetoImp="01"/>
+//!   </cfdi:Conceptos>
+//!   <cfdi:Complemento>
+//!     <pago20:Pagos Version="2.0">
+//!       <pago20:Totales MontoTotalPagos="1160.00"/>
+//!       <pago20:Pago FechaPago="2024-01-31T12:00:00" FormaDePagoP="03" MonedaP="MXN"
+//!           TipoCambioP="1" Monto="1160.00">
+//!         <pago20:DoctoRelacionado IdDocumento="5FB2822E-396D-4725-8521-CDC4BDD20CCF"
+//!             MonedaDR="MXN" EquivalenciaDR="1" NumParcialidad="1" ImpSaldoAnt="1160.00"
+//!             ImpPagado="1160.00" ImpSaldoInsoluto="0.00" ObjetoImpDR="01"/>
+//!       </pago20:Pago>
+//!     </pago20:Pagos>
+//!   </cfdi:Complemento>
+//! </cfdi:Comprobante>"#;
+//!
+//! use cfdi::catalogos::FormaPago;
+//!
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//! let pagos = cfdi.complemento.unwrap().pagos.unwrap();
+//!
+//! let aplicados = pagos.buscar_documento("5fb2822e-396d-4725-8521-cdc4bdd20ccf");
+//! assert_eq!(aplicados.len(), 1);
+//! let (pago, docto) = aplicados[0];
+//! assert_eq!(pago.forma_de_pago, FormaPago::TransferenciaElectronica);
+//! assert_eq!(docto.imp_pagado.unwrap().to_string(), "1160.00");
+//! assert!(docto.imp_saldo_insoluto.unwrap().is_zero());
+//! ```
+
+use crate::cadena::Atributo::{self, Opcional, Requerido};
+use crate::catalogos::{FormaPago, Impuesto, MetodoPago, Moneda, ObjetoImp, TipoFactor};
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento de pagos
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Pagos {
+    /// Versión del complemento, "2.0" o "1.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Solo versión 2.0
+    #[serde(rename = "Totales")]
+    pub totales: Option<Totales>,
+
+    #[serde(rename = "Pago")]
+    pub pago: Vec<Pago>,
+}
+
+impl Pagos {
+    /// Regresa un vector con los [`Pago`] del complemento
+    pub fn get_pagos(&self) -> Vec<Pago> {
+        self.pago.clone()
+    }
+
+    /// Busca en todos los pagos los [`DoctoRelacionado`] cuyo `IdDocumento` (UUID de la
+    /// factura pagada) coincida con `id_documento`, sin importar mayúsculas o minúsculas.
+    pub fn buscar_documento(&self, id_documento: &str) -> Vec<(&Pago, &DoctoRelacionado)> {
+        self.pago
+            .iter()
+            .flat_map(|p| p.docto_relacionado.iter().map(move |d| (p, d)))
+            .filter(|(_, d)| d.id_documento.eq_ignore_ascii_case(id_documento))
+            .collect()
+    }
+}
+
+/// Totales de los pagos y sus impuestos, expresados en MXN
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Totales {
+    #[serde(rename = "@TotalRetencionesIVA")]
+    pub total_retenciones_iva: Option<Decimal>,
+
+    #[serde(rename = "@TotalRetencionesISR")]
+    pub total_retenciones_isr: Option<Decimal>,
+
+    #[serde(rename = "@TotalRetencionesIEPS")]
+    pub total_retenciones_ieps: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosBaseIVA16")]
+    pub total_traslados_base_iva16: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosImpuestoIVA16")]
+    pub total_traslados_impuesto_iva16: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosBaseIVA8")]
+    pub total_traslados_base_iva8: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosImpuestoIVA8")]
+    pub total_traslados_impuesto_iva8: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosBaseIVA0")]
+    pub total_traslados_base_iva0: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosImpuestoIVA0")]
+    pub total_traslados_impuesto_iva0: Option<Decimal>,
+
+    #[serde(rename = "@TotalTrasladosBaseIVAExento")]
+    pub total_traslados_base_iva_exento: Option<Decimal>,
+
+    /// Suma de los montos de todos los pagos, en MXN
+    #[serde(rename = "@MontoTotalPagos")]
+    pub monto_total_pagos: Decimal,
+}
+
+/// Un pago recibido
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Pago {
+    /// Fecha y hora en que se recibió el pago
+    #[serde(rename = "@FechaPago")]
+    pub fecha_pago: String,
+
+    /// Forma en que se recibió el pago
+    #[serde(rename = "@FormaDePagoP")]
+    pub forma_de_pago: FormaPago,
+
+    /// Moneda en que se recibió el pago
+    #[serde(rename = "@MonedaP")]
+    pub moneda: Moneda,
+
+    /// Tipo de cambio a MXN. Requerido cuando la moneda no es MXN.
+    #[serde(rename = "@TipoCambioP")]
+    pub tipo_cambio: Option<Decimal>,
+
+    /// Importe del pago, en la moneda `moneda`
+    #[serde(rename = "@Monto")]
+    pub monto: Decimal,
+
+    #[serde(rename = "@NumOperacion")]
+    pub num_operacion: Option<String>,
+
+    #[serde(rename = "@RfcEmisorCtaOrd")]
+    pub rfc_emisor_cta_ord: Option<String>,
+
+    #[serde(rename = "@NomBancoOrdExt")]
+    pub nom_banco_ord_ext: Option<String>,
+
+    #[serde(rename = "@CtaOrdenante")]
+    pub cta_ordenante: Option<String>,
+
+    #[serde(rename = "@RfcEmisorCtaBen")]
+    pub rfc_emisor_cta_ben: Option<String>,
+
+    #[serde(rename = "@CtaBeneficiario")]
+    pub cta_beneficiario: Option<String>,
+
+    #[serde(rename = "@TipoCadPago")]
+    pub tipo_cad_pago: Option<String>,
+
+    #[serde(rename = "@CertPago")]
+    pub cert_pago: Option<String>,
+
+    #[serde(rename = "@CadPago")]
+    pub cad_pago: Option<String>,
+
+    #[serde(rename = "@SelloPago")]
+    pub sello_pago: Option<String>,
+
+    /// Documentos (facturas) a los que se aplica el pago
+    #[serde(rename = "DoctoRelacionado")]
+    pub docto_relacionado: Vec<DoctoRelacionado>,
+
+    #[serde(rename = "ImpuestosP")]
+    pub impuestos: Option<ImpuestosP>,
+}
+
+impl Pago {
+    /// Regresa un vector con los [`DoctoRelacionado`] del pago
+    pub fn get_doctos_relacionados(&self) -> Vec<DoctoRelacionado> {
+        self.docto_relacionado.clone()
+    }
+}
+
+/// Documento (factura PPD) al que se aplica un [`Pago`]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DoctoRelacionado {
+    /// UUID de la factura pagada
+    #[serde(rename = "@IdDocumento")]
+    pub id_documento: String,
+
+    #[serde(rename = "@Serie")]
+    pub serie: Option<String>,
+
+    #[serde(rename = "@Folio")]
+    pub folio: Option<String>,
+
+    /// Moneda de la factura pagada
+    #[serde(rename = "@MonedaDR")]
+    pub moneda: Moneda,
+
+    /// Tipo de cambio entre la moneda del documento y la del pago. Solo versión 1.0, en 2.0
+    /// se usa `equivalencia`
+    #[serde(rename = "@TipoCambioDR")]
+    pub tipo_cambio: Option<Decimal>,
+
+    /// Método de pago de la factura pagada. Solo versión 1.0
+    #[serde(rename = "@MetodoDePagoDR")]
+    pub metodo_de_pago: Option<MetodoPago>,
+
+    /// Tipo de cambio entre la moneda del documento y la del pago
+    #[serde(rename = "@EquivalenciaDR")]
+    pub equivalencia: Option<Decimal>,
+
+    /// Número de parcialidad que corresponde al pago. Requerido en 2.0
+    #[serde(rename = "@NumParcialidad")]
+    pub num_parcialidad: Option<u32>,
+
+    /// Saldo de la factura antes del pago. Requerido en 2.0
+    #[serde(rename = "@ImpSaldoAnt")]
+    pub imp_saldo_ant: Option<Decimal>,
+
+    /// Importe pagado a la factura. Requerido en 2.0; en 1.0 se omite si el pago tiene un
+    /// solo documento relacionado por el `Monto` del pago
+    #[serde(rename = "@ImpPagado")]
+    pub imp_pagado: Option<Decimal>,
+
+    /// Saldo de la factura después del pago. Requerido en 2.0
+    #[serde(rename = "@ImpSaldoInsoluto")]
+    pub imp_saldo_insoluto: Option<Decimal>,
+
+    /// Si el pago es objeto de impuesto. Solo versión 2.0
+    #[serde(rename = "@ObjetoImpDR")]
+    pub objeto_imp: Option<ObjetoImp>,
+
+    #[serde(rename = "ImpuestosDR")]
+    pub impuestos: Option<ImpuestosDR>,
+}
+
+/// Impuestos del documento relacionado, proporcionales al pago
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpuestosDR {
+    #[serde(rename = "RetencionesDR")]
+    pub retenciones: Option<RetencionesDR>,
+
+    #[serde(rename = "TrasladosDR")]
+    pub traslados: Option<TrasladosDR>,
+}
+
+/// Lista de [`RetencionDR`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetencionesDR {
+    #[serde(rename = "RetencionDR")]
+    pub retencion: Vec<RetencionDR>,
+}
+
+/// Lista de [`TrasladoDR`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrasladosDR {
+    #[serde(rename = "TrasladoDR")]
+    pub traslado: Vec<TrasladoDR>,
+}
+
+/// Retención aplicable al documento relacionado
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetencionDR {
+    #[serde(rename = "@BaseDR")]
+    pub base: Decimal,
+
+    #[serde(rename = "@ImpuestoDR")]
+    pub impuesto: Impuesto,
+
+    #[serde(rename = "@TipoFactorDR")]
+    pub tipo_factor: TipoFactor,
+
+    #[serde(rename = "@TasaOCuotaDR")]
+    pub tasa_o_cuota: Decimal,
+
+    #[serde(rename = "@ImporteDR")]
+    pub importe: Decimal,
+}
+
+/// Traslado aplicable al documento relacionado
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrasladoDR {
+    #[serde(rename = "@BaseDR")]
+    pub base: Decimal,
+
+    #[serde(rename = "@ImpuestoDR")]
+    pub impuesto: Impuesto,
+
+    #[serde(rename = "@TipoFactorDR")]
+    pub tipo_factor: TipoFactor,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@TasaOCuotaDR")]
+    pub tasa_o_cuota: Option<Decimal>,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@ImporteDR")]
+    pub importe: Option<Decimal>,
+}
+
+/// Impuestos del pago, agrupados por impuesto, tipo de factor y tasa
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpuestosP {
+    #[serde(rename = "RetencionesP")]
+    pub retenciones: Option<RetencionesP>,
+
+    #[serde(rename = "TrasladosP")]
+    pub traslados: Option<TrasladosP>,
+}
+
+/// Lista de [`RetencionP`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetencionesP {
+    #[serde(rename = "RetencionP")]
+    pub retencion: Vec<RetencionP>,
+}
+
+/// Lista de [`TrasladoP`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrasladosP {
+    #[serde(rename = "TrasladoP")]
+    pub traslado: Vec<TrasladoP>,
+}
+
+/// Retención total del pago por impuesto
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetencionP {
+    #[serde(rename = "@ImpuestoP")]
+    pub impuesto: Impuesto,
+
+    #[serde(rename = "@ImporteP")]
+    pub importe: Decimal,
+}
+
+/// Traslado total del pago por impuesto, tipo de factor y tasa
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrasladoP {
+    #[serde(rename = "@BaseP")]
+    pub base: Decimal,
+
+    #[serde(rename = "@ImpuestoP")]
+    pub impuesto: Impuesto,
+
+    #[serde(rename = "@TipoFactorP")]
+    pub tipo_factor: TipoFactor,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@TasaOCuotaP")]
+    pub tasa_o_cuota: Option<Decimal>,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@ImporteP")]
+    pub importe: Option<Decimal>,
+}
+
+/// Orden de los atributos en la cadena original, según `Pagos20.xslt` (y `Pagos10.xslt`)
+pub(crate) fn atributos_cadena(
+    padre: &str,
+    nodo: &str,
+    version: &str,
+) -> Option<&'static [Atributo]> {
+    let atributos: &[Atributo] = match (padre, nodo) {
+        ("Pago", "DoctoRelacionado") if version == "1.0" => &[
+            Requerido("IdDocumento"),
+            Opcional("Serie"),
+            Opcional("Folio"),
+            Requerido("MonedaDR"),
+            Opcional("TipoCambioDR"),
+            Requerido("MetodoDePagoDR"),
+            Opcional("NumParcialidad"),
+            Opcional("ImpSaldoAnt"),
+            Opcional("ImpPagado"),
+            Opcional("ImpSaldoInsoluto"),
+        ],
+        ("", "Pagos") => &[Requerido("Version")],
+        ("Pagos", "Totales") => &[
+            Opcional("TotalRetencionesIVA"),
+            Opcional("TotalRetencionesISR"),
+            Opcional("TotalRetencionesIEPS"),
+            Opcional("TotalTrasladosBaseIVA16"),
+            Opcional("TotalTrasladosImpuestoIVA16"),
+            Opcional("TotalTrasladosBaseIVA8"),
+            Opcional("TotalTrasladosImpuestoIVA8"),
+            Opcional("TotalTrasladosBaseIVA0"),
+            Opcional("TotalTrasladosImpuestoIVA0"),
+            Opcional("TotalTrasladosBaseIVAExento"),
+            Requerido("MontoTotalPagos"),
+        ],
+        ("Pagos", "Pago") => &[
+            Requerido("FechaPago"),
+            Requerido("FormaDePagoP"),
+            Requerido("MonedaP"),
+            Opcional("TipoCambioP"),
+            Requerido("Monto"),
+            Opcional("NumOperacion"),
+            Opcional("RfcEmisorCtaOrd"),
+            Opcional("NomBancoOrdExt"),
+            Opcional("CtaOrdenante"),
+            Opcional("RfcEmisorCtaBen"),
+            Opcional("CtaBeneficiario"),
+            Opcional("TipoCadPago"),
+            Opcional("CertPago"),
+            Opcional("CadPago"),
+            Opcional("SelloPago"),
+        ],
+        ("Pago", "DoctoRelacionado") => &[
+            Requerido("IdDocumento"),
+            Opcional("Serie"),
+            Opcional("Folio"),
+            Requerido("MonedaDR"),
+            Opcional("EquivalenciaDR"),
+            Requerido("NumParcialidad"),
+            Requerido("ImpSaldoAnt"),
+            Requerido("ImpPagado"),
+            Requerido("ImpSaldoInsoluto"),
+            Requerido("ObjetoImpDR"),
+        ],
+        ("DoctoRelacionado", "ImpuestosDR")
+        | ("ImpuestosDR", "RetencionesDR")
+        | ("ImpuestosDR", "TrasladosDR")
+        | ("Pago", "ImpuestosP")
+        | ("ImpuestosP", "RetencionesP")
+        | ("ImpuestosP", "TrasladosP") => &[],
+        ("RetencionesDR", "RetencionDR") => &[
+            Requerido("BaseDR"),
+            Requerido("ImpuestoDR"),
+            Requerido("TipoFactorDR"),
+            Requerido("TasaOCuotaDR"),
+            Requerido("ImporteDR"),
+        ],
+        ("TrasladosDR", "TrasladoDR") => &[
+            Requerido("BaseDR"),
+            Requerido("ImpuestoDR"),
+            Requerido("TipoFactorDR"),
+            Opcional("TasaOCuotaDR"),
+            Opcional("ImporteDR"),
+        ],
+        ("RetencionesP", "RetencionP") => &[Requerido("ImpuestoP"), Requerido("ImporteP")],
+        ("TrasladosP", "TrasladoP") => &[
+            Requerido("BaseP"),
+            Requerido("ImpuestoP"),
+            Requerido("TipoFactorP"),
+            Opcional("TasaOCuotaP"),
+            Opcional("ImporteP"),
+        ],
+        _ => return None,
+    };
+    Some(atributos)
+}
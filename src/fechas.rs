@@ -0,0 +1,161 @@
+//! Fechas del comprobante y zonas horarias.
+//!
+//! La `Fecha` del comprobante y la `FechaTimbrado` del timbre se escriben como
+//! `AAAA-MM-DDThh:mm:ss`, sin zona horaria, y se leen como [`NaiveDateTime`]. Un comprobante
+//! con una fecha que no tenga ese formato no se puede leer con [`parse_cfdi`](crate::parse_cfdi).
+//!
+//! Según el Anexo 20, la `Fecha` es la hora local del lugar de expedición y la `FechaTimbrado`
+//! es la hora de la Zona Centro. [`zona_horaria`] obtiene la zona horaria de un código postal
+//! (`LugarExpedicion`), para poder comparar fechas de distintas zonas:
+//!
+//! ```rust
+//! # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+//! #     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1160.00"
+//! #     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="22000">
+//! #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//! #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+//! #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+//! #   <cfdi:Conceptos>
+//! #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+//! #         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="02"/>
+//! #   </cfdi:Conceptos>
+//! # </cfdi:Comprobante>"#;
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//!
+//! // LugarExpedicion="22000" (Tijuana)
+//! assert_eq!(cfdi.fecha.to_string(), "2024-01-15 10:00:00");
+//! assert_eq!(cfdi.fecha_con_zona().to_rfc3339(), "2024-01-15T10:00:00-08:00");
+//! assert_eq!(cfdi.periodo_fiscal(), (2024, 1));
+//! ```
+//!
+//! La zona horaria se determina por el rango del código postal: cada estado tiene sus propios
+//! rangos, y en los estados con más de una zona (ej. los municipios fronterizos de Chihuahua,
+//! Coahuila y Tamaulipas, o Bahía de Banderas en Nayarit) se usan los rangos de sus
+//! localidades principales. Los códigos postales no reconocidos usan la Zona Centro.
+
+use chrono::{DateTime, NaiveDateTime, TimeDelta, TimeZone};
+use chrono_tz::Tz;
+
+use crate::{Comprobante, TimbreFiscalDigital};
+
+/// Formato de las fechas en el xml
+pub const FORMATO: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Plazo máximo entre la `Fecha` del comprobante y su timbrado
+pub const PLAZO_TIMBRADO: TimeDelta = TimeDelta::hours(72);
+
+/// Zona horaria de un código postal de México. Los códigos no reconocidos usan la Zona Centro.
+///
+/// ```rust
+/// use cfdi::fechas::zona_horaria;
+///
+/// assert_eq!(zona_horaria("06600"), chrono_tz::America::Mexico_City);
+/// assert_eq!(zona_horaria("77500"), chrono_tz::America::Cancun);
+/// assert_eq!(zona_horaria("32000"), chrono_tz::America::Ciudad_Juarez);
+/// ```
+pub fn zona_horaria(codigo_postal: &str) -> Tz {
+    use chrono_tz::America;
+
+    let Ok(cp) = codigo_postal.trim().parse::<u32>() else {
+        return America::Mexico_City;
+    };
+    match cp {
+        // Baja California
+        21000..=22999 => America::Tijuana,
+        // Baja California Sur
+        23000..=23999 => America::Mazatlan,
+        // Coahuila: Piedras Negras y Ciudad Acuña
+        26000..=26099 | 26200..=26299 => America::Matamoros,
+        // Chihuahua: Ciudad Juárez, Ojinaga y el resto del estado
+        32000..=32699 => America::Ciudad_Juarez,
+        32880..=32889 => America::Ojinaga,
+        31000..=33999 => America::Chihuahua,
+        // Nayarit: Bahía de Banderas y el resto del estado
+        63700..=63739 => America::Bahia_Banderas,
+        63000..=63999 => America::Mazatlan,
+        // Quintana Roo
+        77000..=77999 => America::Cancun,
+        // Sinaloa
+        80000..=82999 => America::Mazatlan,
+        // Sonora
+        83000..=85999 => America::Hermosillo,
+        // Tamaulipas: Matamoros, Nuevo Laredo, Reynosa y Río Bravo
+        87300..=87499 | 88000..=88299 | 88500..=88799 | 88900..=88999 => America::Matamoros,
+        _ => America::Mexico_City,
+    }
+}
+
+/// Hora local `fecha` en la zona `zona`.
+///
+/// Si la hora se repite por el cambio de horario se toma la primera; si no existe (se adelantó
+/// el reloj) se toma la hora siguiente.
+fn en_zona(fecha: NaiveDateTime, zona: Tz) -> DateTime<Tz> {
+    zona.from_local_datetime(&fecha)
+        .earliest()
+        .or_else(|| {
+            zona.from_local_datetime(&(fecha + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .unwrap_or_else(|| zona.from_utc_datetime(&fecha))
+}
+
+impl Comprobante {
+    /// `Fecha` del comprobante en la zona horaria del `LugarExpedicion`
+    pub fn fecha_con_zona(&self) -> DateTime<Tz> {
+        en_zona(self.fecha, zona_horaria(&self.lugar_expedicion))
+    }
+
+    /// Periodo (año, mes) al que corresponde el comprobante, según su `Fecha`
+    pub fn periodo_fiscal(&self) -> (i32, u32) {
+        use chrono::Datelike;
+
+        (self.fecha.year(), self.fecha.month())
+    }
+
+    /// Tiempo entre la `Fecha` del comprobante y su timbrado, tomando en cuenta la zona
+    /// horaria de cada una. `None` si no está timbrado.
+    pub fn tiempo_para_timbrar(&self) -> Option<TimeDelta> {
+        let tfd = self.complemento.as_ref()?.timbre_fiscal_digital.as_ref()?;
+        Some(tfd.fecha_timbrado_con_zona() - self.fecha_con_zona())
+    }
+
+    /// `true` si se timbró dentro de las 72 horas siguientes a su `Fecha`. `None` si no está
+    /// timbrado.
+    pub fn timbrado_en_plazo(&self) -> Option<bool> {
+        let tiempo = self.tiempo_para_timbrar()?;
+        Some(tiempo >= TimeDelta::zero() && tiempo <= PLAZO_TIMBRADO)
+    }
+}
+
+impl TimbreFiscalDigital {
+    /// `FechaTimbrado` en la Zona Centro
+    pub fn fecha_timbrado_con_zona(&self) -> DateTime<Tz> {
+        en_zona(self.fecha_timbrado, chrono_tz::America::Mexico_City)
+    }
+}
+
+/// Lectura y escritura de las fechas con [`FORMATO`], para `#[serde(with)]`
+pub(crate) mod formato {
+    use chrono::NaiveDateTime;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::FORMATO;
+
+    pub fn serialize<S: Serializer>(
+        fecha: &NaiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&fecha.format(FORMATO))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NaiveDateTime, D::Error> {
+        let fecha = String::deserialize(deserializer)?;
+        // `parse_from_str` también acepta meses, días, etc. de un solo dígito
+        NaiveDateTime::parse_from_str(&fecha, FORMATO)
+            .ok()
+            .filter(|_| fecha.len() == 19)
+            .ok_or_else(|| de::Error::custom(format!("Fecha inválida: {}", fecha)))
+    }
+}
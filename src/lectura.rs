@@ -0,0 +1,656 @@
+//! Lectura de documentos xml y sus errores.
+//!
+//! Antes de deserializar se recorre el documento completo, para distinguir un xml mal formado,
+//! un nodo principal distinto al esperado o una versión no soportada. Los errores de la
+//! deserialización se ubican en el documento con la ruta de nodos donde ocurrieron.
+//!
+//! Después de deserializar, los complementos que no se leen en structs y la addenda se
+//! conservan como [`NodoXml`].
+
+use std::fmt;
+
+use quick_xml::de::{DeError, Deserializer};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+use serde_path_to_error::Segment;
+
+use crate::{Comprobante, NodoXml};
+
+/// Complementos que se leen en structs. En 3.2 solo se lee el timbre.
+const COMPLEMENTOS_LEIDOS: &[&str] = &[
+    "TimbreFiscalDigital",
+    "Pagos",
+    "Nomina",
+    "CartaPorte",
+    "ComercioExterior",
+];
+
+/// Error al leer un documento con [`parse_cfdi`](crate::parse_cfdi) o
+/// [`parse_retenciones`](crate::parse_retenciones).
+///
+/// Todas las variantes incluyen la `ruta` del nodo donde ocurrió el error (ej.
+/// `"Comprobante/Conceptos/Concepto[2]"`) y su `posicion`, en bytes desde el inicio del
+/// documento, del `<` con el que empieza el nodo.
+///
+/// ```rust
+/// use cfdi::ErrorLectura;
+///
+/// let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+///     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1000.00"
+///     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="45079">
+///   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+///   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+///       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+///   <cfdi:Conceptos>
+///     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+///         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="01"/>
+///   </cfdi:Conceptos>
+/// </cfdi:Comprobante>"#;
+///
+/// let invalido = xml.replace(r#" Total="1000.00""#, r#" Total="mil""#);
+/// match cfdi::parse_cfdi(&invalido).unwrap_err() {
+///     ErrorLectura::ValorInvalido { ruta, atributo, valor, .. } => {
+///         assert_eq!(ruta, "Comprobante");
+///         assert_eq!(atributo, "Total");
+///         assert_eq!(valor.as_deref(), Some("mil"));
+///     }
+///     otro => panic!("{otro}"),
+/// }
+///
+/// let error = cfdi::parse_cfdi(&xml.replace(r#" Rfc="URE180429TM6""#, "")).unwrap_err();
+/// assert_eq!(error.ruta(), "Comprobante/Receptor");
+/// assert_eq!(&xml[error.posicion()..][..14], "<cfdi:Receptor");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorLectura {
+    /// El texto no es un xml bien formado
+    XmlInvalido {
+        ruta: String,
+        posicion: usize,
+        mensaje: String,
+    },
+    /// El nodo principal no es el del documento esperado. `ruta` es el nodo que se encontró
+    /// (vacía si el documento no tiene nodos).
+    NoEsCfdi {
+        ruta: String,
+        posicion: usize,
+        esperado: &'static str,
+    },
+    /// El documento es de una versión que no se puede leer, o no tiene `Version`
+    VersionNoSoportada {
+        ruta: String,
+        posicion: usize,
+        version: Option<String>,
+    },
+    /// Falta un atributo requerido en el nodo de la `ruta`
+    AtributoFaltante {
+        ruta: String,
+        posicion: usize,
+        atributo: String,
+    },
+    /// Falta un nodo requerido dentro del nodo de la `ruta`
+    NodoFaltante {
+        ruta: String,
+        posicion: usize,
+        nodo: String,
+    },
+    /// Un atributo del nodo de la `ruta` no tiene un valor válido (ej. un importe que no es
+    /// número, una fecha o un RFC mal formados)
+    ValorInvalido {
+        ruta: String,
+        posicion: usize,
+        atributo: String,
+        valor: Option<String>,
+        mensaje: String,
+    },
+    /// El contenido del nodo de la `ruta` no corresponde a la estructura del documento
+    Estructura {
+        ruta: String,
+        posicion: usize,
+        mensaje: String,
+    },
+}
+
+impl ErrorLectura {
+    /// Ruta del nodo con el error (ej. `"Comprobante/Conceptos/Concepto[2]"`)
+    pub fn ruta(&self) -> &str {
+        match self {
+            ErrorLectura::XmlInvalido { ruta, .. }
+            | ErrorLectura::NoEsCfdi { ruta, .. }
+            | ErrorLectura::VersionNoSoportada { ruta, .. }
+            | ErrorLectura::AtributoFaltante { ruta, .. }
+            | ErrorLectura::NodoFaltante { ruta, .. }
+            | ErrorLectura::ValorInvalido { ruta, .. }
+            | ErrorLectura::Estructura { ruta, .. } => ruta,
+        }
+    }
+
+    /// Posición del nodo con el error, en bytes desde el inicio del documento
+    pub fn posicion(&self) -> usize {
+        match self {
+            ErrorLectura::XmlInvalido { posicion, .. }
+            | ErrorLectura::NoEsCfdi { posicion, .. }
+            | ErrorLectura::VersionNoSoportada { posicion, .. }
+            | ErrorLectura::AtributoFaltante { posicion, .. }
+            | ErrorLectura::NodoFaltante { posicion, .. }
+            | ErrorLectura::ValorInvalido { posicion, .. }
+            | ErrorLectura::Estructura { posicion, .. } => *posicion,
+        }
+    }
+}
+
+impl fmt::Display for ErrorLectura {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (byte {}): ", self.ruta(), self.posicion())?;
+        match self {
+            ErrorLectura::XmlInvalido { mensaje, .. } => write!(f, "xml inválido, {}", mensaje),
+            ErrorLectura::NoEsCfdi { esperado, .. } => {
+                write!(f, "el nodo principal debe ser {}", esperado)
+            }
+            ErrorLectura::VersionNoSoportada { version, .. } => match version {
+                Some(version) => write!(f, "la versión {} no está soportada", version),
+                None => write!(f, "el documento no tiene Version"),
+            },
+            ErrorLectura::AtributoFaltante { atributo, .. } => {
+                write!(f, "falta el atributo {}", atributo)
+            }
+            ErrorLectura::NodoFaltante { nodo, .. } => write!(f, "falta el nodo {}", nodo),
+            ErrorLectura::ValorInvalido {
+                atributo,
+                valor,
+                mensaje,
+                ..
+            } => write!(
+                f,
+                "valor inválido en {} ({}): {}",
+                atributo,
+                valor.as_deref().unwrap_or_default(),
+                mensaje
+            ),
+            ErrorLectura::Estructura { mensaje, .. } => write!(f, "{}", mensaje),
+        }
+    }
+}
+
+impl std::error::Error for ErrorLectura {}
+
+/// Revisa que `xml` esté bien formado, que su nodo principal sea `raiz` y que su `Version`
+/// (o `version` en 3.2) sea una de `versiones`. Regresa la versión.
+pub(crate) fn revisar(
+    xml: &str,
+    raiz: &'static str,
+    versiones: &[&str],
+) -> Result<String, ErrorLectura> {
+    let mut reader = Reader::from_str(xml);
+    let mut pila: Vec<String> = vec![];
+    let mut principal: Option<(String, usize, Option<String>)> = None;
+
+    let invalido = |pila: &[String], posicion: u64, mensaje: String| ErrorLectura::XmlInvalido {
+        ruta: pila.join("/"),
+        posicion: posicion as usize,
+        mensaje,
+    };
+
+    loop {
+        let inicio = reader.buffer_position() as usize;
+        let evento = reader
+            .read_event()
+            .map_err(|e| invalido(&pila, reader.error_position(), e.to_string()))?;
+        match evento {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let nombre = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                pila.push(nombre.clone());
+                for atributo in e.attributes() {
+                    atributo.map_err(|e| invalido(&pila, inicio as u64, e.to_string()))?;
+                }
+                if principal.is_none() {
+                    let version = ["Version", "version"]
+                        .iter()
+                        .find_map(|v| e.try_get_attribute(v).ok().flatten())
+                        .map(|v| String::from_utf8_lossy(&v.value).into_owned());
+                    principal = Some((nombre, inicio, version));
+                }
+                if matches!(evento, Event::Empty(_)) {
+                    pila.pop();
+                }
+            }
+            Event::End(_) => {
+                pila.pop();
+            }
+            Event::Eof if !pila.is_empty() => {
+                let mensaje = format!("falta cerrar el nodo {}", pila[pila.len() - 1]);
+                return Err(invalido(&pila, xml.len() as u64, mensaje));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let Some((nombre, posicion, version)) = principal else {
+        return Err(ErrorLectura::NoEsCfdi {
+            ruta: String::new(),
+            posicion: 0,
+            esperado: raiz,
+        });
+    };
+    if nombre != raiz {
+        return Err(ErrorLectura::NoEsCfdi {
+            ruta: nombre,
+            posicion,
+            esperado: raiz,
+        });
+    }
+    match version {
+        Some(version) if versiones.contains(&version.as_str()) => Ok(version),
+        version => Err(ErrorLectura::VersionNoSoportada {
+            ruta: nombre,
+            posicion,
+            version,
+        }),
+    }
+}
+
+/// Deserializa `xml`, cuyo nodo principal es `raiz`, ubicando en el documento el nodo donde
+/// ocurra un error.
+pub(crate) fn deserializar<T: DeserializeOwned>(xml: &str, raiz: &str) -> Result<T, ErrorLectura> {
+    let mut deserializer = Deserializer::from_str(xml);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+        // Nodos (nombre e índice si se repiten) y atributo de la ruta donde ocurrió el error
+        let mut nodos: Vec<(String, Option<usize>)> = vec![];
+        for segmento in error.path().iter() {
+            match segmento {
+                Segment::Map { key } if !key.starts_with('$') => nodos.push((key.clone(), None)),
+                Segment::Seq { index } => {
+                    if let Some(nodo) = nodos.last_mut() {
+                        nodo.1 = Some(*index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let atributo = match nodos.last() {
+            Some((nombre, _)) if nombre.starts_with('@') => {
+                nodos.pop().map(|n| n.0[1..].to_string())
+            }
+            _ => None,
+        };
+
+        let mut ruta = raiz.to_string();
+        for (nombre, indice) in &nodos {
+            ruta.push('/');
+            ruta.push_str(nombre);
+            if let Some(indice) = indice {
+                ruta.push_str(&format!("[{}]", indice + 1));
+            }
+        }
+        let (posicion, valor) = ubicar(xml, &nodos, atributo.as_deref());
+
+        let error = error.into_inner();
+        let faltante = match &error {
+            DeError::Custom(mensaje) => mensaje
+                .strip_prefix("missing field `")
+                .and_then(|m| m.strip_suffix('`')),
+            _ => None,
+        };
+        match (faltante, atributo) {
+            (Some(campo), _) => match campo.strip_prefix('@') {
+                Some(atributo) => ErrorLectura::AtributoFaltante {
+                    ruta,
+                    posicion,
+                    atributo: atributo.to_string(),
+                },
+                None => ErrorLectura::NodoFaltante {
+                    ruta,
+                    posicion,
+                    nodo: campo.to_string(),
+                },
+            },
+            (None, Some(atributo)) => ErrorLectura::ValorInvalido {
+                ruta,
+                posicion,
+                atributo,
+                valor,
+                mensaje: error.to_string(),
+            },
+            (None, None) => match error {
+                DeError::InvalidXml(e) => ErrorLectura::XmlInvalido {
+                    ruta,
+                    posicion,
+                    mensaje: e.to_string(),
+                },
+                error => ErrorLectura::Estructura {
+                    ruta,
+                    posicion,
+                    mensaje: error.to_string(),
+                },
+            },
+        }
+    })
+}
+
+/// Revisa los atributos que el esquema del CFDI 4.0 (o de Pagos 2.0 y del timbre 1.1) requiere,
+/// pero que se leen como `Option` porque no existen o son opcionales en las versiones
+/// anteriores.
+pub(crate) fn revisar_requeridos(xml: &str, cfdi: &Comprobante) -> Result<(), ErrorLectura> {
+    let faltante = |nodos: &[(&str, Option<usize>)], atributo: &str| {
+        let nodos: Vec<_> = nodos.iter().map(|(n, i)| (n.to_string(), *i)).collect();
+        let mut ruta = "Comprobante".to_string();
+        for (nombre, indice) in &nodos {
+            ruta.push('/');
+            ruta.push_str(nombre);
+            if let Some(indice) = indice {
+                ruta.push_str(&format!("[{}]", indice + 1));
+            }
+        }
+        Err(ErrorLectura::AtributoFaltante {
+            ruta,
+            posicion: ubicar(xml, &nodos, None).0,
+            atributo: atributo.to_string(),
+        })
+    };
+
+    if cfdi.version == "4.0" {
+        if cfdi.exportacion.is_none() {
+            return faltante(&[], "Exportacion");
+        }
+        if cfdi.emisor.nombre.is_none() {
+            return faltante(&[("Emisor", None)], "Nombre");
+        }
+        let receptor = &cfdi.receptor;
+        for (falta, atributo) in [
+            (receptor.nombre.is_none(), "Nombre"),
+            (
+                receptor.domicilio_fiscal.is_none(),
+                "DomicilioFiscalReceptor",
+            ),
+            (receptor.regimen_fiscal.is_none(), "RegimenFiscalReceptor"),
+        ] {
+            if falta {
+                return faltante(&[("Receptor", None)], atributo);
+            }
+        }
+        for (i, concepto) in cfdi.conceptos.concepto.iter().enumerate() {
+            if concepto.objeto_imp.is_none() {
+                return faltante(&[("Conceptos", None), ("Concepto", Some(i))], "ObjetoImp");
+            }
+        }
+        let traslados = cfdi.impuestos.iter().flat_map(|i| &i.traslados);
+        for (i, traslado) in traslados.flat_map(|t| &t.traslado).enumerate() {
+            if traslado.base.is_none() {
+                let nodos = [
+                    ("Impuestos", None),
+                    ("Traslados", None),
+                    ("Traslado", Some(i)),
+                ];
+                return faltante(&nodos, "Base");
+            }
+        }
+    }
+
+    let pagos = cfdi.complemento.as_ref().and_then(|c| c.pagos.as_ref());
+    if let Some(pagos) = pagos.filter(|p| p.version == "2.0") {
+        for (i, pago) in pagos.pago.iter().enumerate() {
+            for (j, docto) in pago.docto_relacionado.iter().enumerate() {
+                for (falta, atributo) in [
+                    (docto.num_parcialidad.is_none(), "NumParcialidad"),
+                    (docto.imp_saldo_ant.is_none(), "ImpSaldoAnt"),
+                    (docto.imp_pagado.is_none(), "ImpPagado"),
+                    (docto.imp_saldo_insoluto.is_none(), "ImpSaldoInsoluto"),
+                ] {
+                    if falta {
+                        let nodos = [
+                            ("Complemento", None),
+                            ("Pagos", None),
+                            ("Pago", Some(i)),
+                            ("DoctoRelacionado", Some(j)),
+                        ];
+                        return faltante(&nodos, atributo);
+                    }
+                }
+            }
+        }
+    }
+
+    let timbre = cfdi
+        .complemento
+        .as_ref()
+        .and_then(|c| c.timbre_fiscal_digital.as_ref());
+    if let Some(timbre) = timbre {
+        if timbre.version == "1.1" && timbre.rfc_prov_certif.is_none() {
+            let nodos = [("Complemento", None), ("TimbreFiscalDigital", None)];
+            return faltante(&nodos, "RfcProvCertif");
+        }
+    }
+    Ok(())
+}
+
+/// Posición del nodo al que lleva la ruta `nodos` (a partir del nodo principal), y el valor de
+/// su `atributo`. Si la ruta no se encuentra completa, regresa el último nodo encontrado.
+fn ubicar(
+    xml: &str,
+    nodos: &[(String, Option<usize>)],
+    atributo: Option<&str>,
+) -> (usize, Option<String>) {
+    let mut reader = Reader::from_str(xml);
+    let mut profundidad = 0;
+    // Nodos de la ruta encontrados, y veces que se ha visto el siguiente dentro del último
+    let mut nivel = 0;
+    let mut vistos = 0;
+    let mut posicion = 0;
+    let mut valor = None;
+
+    loop {
+        let inicio = reader.buffer_position() as usize;
+        let (e, vacio) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                profundidad -= 1;
+                if profundidad <= nivel {
+                    break;
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => continue,
+        };
+
+        let encontrado = if profundidad == 0 {
+            true
+        } else if profundidad == nivel + 1 && nivel < nodos.len() {
+            let (nombre, indice) = &nodos[nivel];
+            if e.local_name().as_ref() != nombre.as_bytes() {
+                false
+            } else if vistos == indice.unwrap_or(0) {
+                nivel += 1;
+                vistos = 0;
+                true
+            } else {
+                vistos += 1;
+                false
+            }
+        } else {
+            false
+        };
+
+        if encontrado {
+            posicion = inicio;
+            valor = atributo
+                .and_then(|a| e.try_get_attribute(a).ok().flatten())
+                .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()));
+            if nivel == nodos.len() || vacio {
+                break;
+            }
+        }
+        if !vacio {
+            profundidad += 1;
+        }
+    }
+    (posicion, valor)
+}
+
+/// Guarda en `cfdi` los complementos que no se leyeron y los nodos de la addenda
+pub(crate) fn conservar_nodos(xml: &str, cfdi: &mut Comprobante) {
+    let leidos = match cfdi.version.as_str() {
+        "3.2" => &COMPLEMENTOS_LEIDOS[..1],
+        _ => COMPLEMENTOS_LEIDOS,
+    };
+    if let Some(complemento) = cfdi.complemento.as_mut() {
+        complemento.otros = nodos_xml(xml, "Complemento", leidos);
+    }
+    cfdi.addenda = nodos_xml(xml, "Addenda", &[]);
+}
+
+/// Declaraciones `xmlns` (prefijo, uri) y `xsi:schemaLocation` (uri, esquema) de un nodo
+pub(crate) struct Ambito {
+    pub nombre: String,
+    xmlns: Vec<(String, String)>,
+    esquemas: Vec<(String, String)>,
+}
+
+impl Ambito {
+    pub fn de(e: &BytesStart) -> Ambito {
+        let mut ambito = Ambito {
+            nombre: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+            xmlns: vec![],
+            esquemas: vec![],
+        };
+        for atributo in e.attributes().flatten() {
+            let Ok(valor) = atributo.unescape_value() else {
+                continue;
+            };
+            let llave = atributo.key.as_ref();
+            if llave == b"xmlns" {
+                ambito.xmlns.push((String::new(), valor.into_owned()));
+            } else if let Some(prefijo) = llave.strip_prefix(b"xmlns:") {
+                let prefijo = String::from_utf8_lossy(prefijo).into_owned();
+                ambito.xmlns.push((prefijo, valor.into_owned()));
+            } else if atributo.key.local_name().as_ref() == b"schemaLocation" {
+                let partes: Vec<&str> = valor.split_whitespace().collect();
+                for par in partes.chunks_exact(2) {
+                    ambito
+                        .esquemas
+                        .push((par[0].to_string(), par[1].to_string()));
+                }
+            }
+        }
+        ambito
+    }
+}
+
+/// Hijos de los nodos `padre` (dentro del nodo principal) que no están en `leidos`, tal como
+/// están en el documento
+fn nodos_xml(xml: &str, padre: &str, leidos: &[&str]) -> Vec<NodoXml> {
+    let mut reader = Reader::from_str(xml);
+    let mut pila: Vec<Ambito> = vec![];
+    let mut nodos = vec![];
+
+    loop {
+        let inicio = reader.buffer_position() as usize;
+        let (e, vacio) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                pila.pop();
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => continue,
+        };
+
+        let ambito = Ambito::de(&e);
+        let en_padre = pila.len() == 2 && pila[1].nombre == padre;
+        if en_padre && !leidos.contains(&ambito.nombre.as_str()) {
+            if !vacio && reader.read_to_end(e.name()).is_err() {
+                break;
+            }
+            let fin = reader.buffer_position() as usize;
+            nodos.push(nodo_xml(&pila, &e, ambito, &xml[inicio..fin]));
+        } else if !vacio {
+            pila.push(ambito);
+        }
+    }
+    nodos
+}
+
+/// El nodo `e`, cuyo texto completo es `xml`, dentro de los nodos de la `pila`
+pub(crate) fn nodo_xml(pila: &[Ambito], e: &BytesStart, ambito: Ambito, xml: &str) -> NodoXml {
+    // Declaraciones heredadas, la más cercana de cada prefijo
+    let heredada = |prefijo: &str| {
+        pila.iter()
+            .rev()
+            .flat_map(|a| a.xmlns.iter())
+            .find(|(p, _)| p == prefijo)
+            .map(|(_, uri)| uri.clone())
+    };
+
+    let mut namespaces: Vec<(String, String)> = vec![];
+    for prefijo in prefijos_usados(xml) {
+        if ambito.xmlns.iter().any(|(p, _)| *p == prefijo)
+            || namespaces.iter().any(|(p, _)| *p == prefijo)
+        {
+            continue;
+        }
+        if let Some(uri) = heredada(&prefijo) {
+            namespaces.push((prefijo, uri));
+        }
+    }
+
+    let prefijo = e
+        .name()
+        .prefix()
+        .map(|p| String::from_utf8_lossy(p.as_ref()).into_owned())
+        .unwrap_or_default();
+    let namespace = ambito
+        .xmlns
+        .iter()
+        .find(|(p, _)| *p == prefijo)
+        .map(|(_, uri)| uri.clone())
+        .or_else(|| heredada(&prefijo));
+    let esquema = namespace.as_ref().and_then(|uri| {
+        pila.iter()
+            .rev()
+            .flat_map(|a| a.esquemas.iter())
+            .find(|(u, _)| u == uri)
+            .map(|(_, esquema)| esquema.clone())
+    });
+
+    NodoXml {
+        nombre: ambito.nombre,
+        namespace,
+        namespaces,
+        esquema,
+        xml: xml.to_string(),
+    }
+}
+
+/// Prefijos de los nodos y atributos de `xml`, en orden de aparición. Los nodos sin prefijo
+/// usan el namespace por omisión (prefijo vacío).
+fn prefijos_usados(xml: &str) -> Vec<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut prefijos: Vec<String> = vec![];
+    let mut agregar = |prefijo: &[u8]| {
+        let prefijo = String::from_utf8_lossy(prefijo).into_owned();
+        if !prefijos.contains(&prefijo) {
+            prefijos.push(prefijo);
+        }
+    };
+
+    while let Ok(evento) = reader.read_event() {
+        match evento {
+            Event::Start(e) | Event::Empty(e) => {
+                agregar(e.name().prefix().as_ref().map_or(&b""[..], |p| p.as_ref()));
+                for atributo in e.attributes().flatten() {
+                    match atributo.key.prefix() {
+                        Some(p) if !matches!(p.as_ref(), b"xmlns" | b"xml") => agregar(p.as_ref()),
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    prefijos
+}
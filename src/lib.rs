@@ -16,6 +16,8 @@
 //!
 //! ```markdown
 //!|-Comprobante
+//!     |-- InformacionGlobal (opcional) - Periodo de una factura global
+//!     |-- CfdiRelacionados (opcional) - Uno o más grupos, con el UUID de cada CFDI relacionado
 //!     |-- Emisor
 //!          |**-> rfc
 //!          |**-> nombre
@@ -26,14 +28,33 @@
 //!          |**-> regimen_fiscal
 //!          |**-> uso_cfdi
 //!     |-- Conceptos
-//!     |-- Complemento (opcional) - Incluye TimbreFiscalDigital
-//!     |**-> total
-//!     |**-> subtotal
+//!          |-- Concepto
+//!               |-- Impuestos (opcional) - Traslados y Retenciones del concepto
+//!     |-- Impuestos (opcional)
+//!          |-- Retenciones
+//!          |-- Traslados
+//!          |**-> total_impuestos_retenidos
+//!          |**-> total_impuestos_trasladados
+//!     |-- Complemento (opcional) - Incluye TimbreFiscalDigital, Pagos 2.0, Nómina 1.2,
+//!                                  Carta Porte y Comercio Exterior 2.0. Los demás
+//!                                  complementos se conservan como xml
+//!     |-- Addenda (opcional) - Se conserva como xml
+//!     |**-> version
+//!     |**-> serie (opcional)
+//!     |**-> folio (opcional)
 //!     |**-> fecha
-//!     |**-> forma_de_pago
-//!     |**-> descuento
+//!     |**-> forma_pago (opcional)
+//!     |**-> condiciones_de_pago (opcional)
+//!     |**-> subtotal
+//!     |**-> descuento (opcional)
+//!     |**-> moneda
+//!     |**-> tipo_cambio (opcional)
+//!     |**-> total
 //!     |**-> tipo_comprobante
-//!     |**-> forma_de_pago
+//!     |**-> exportacion
+//!     |**-> metodo_pago (opcional)
+//!     |**-> lugar_expedicion
+//!     |**-> confirmacion (opcional)
 //!
 //!
 //! |-- Representan subnodos
@@ -51,8 +72,8 @@
 //!
 //! #### Ejemplo:
 //! Usando [`Comprobante`]:
-//! ```rust
-//! use cfdi::{DatosPrincipales, get_datos_principales};
+//! ```rust,no_run
+//! use cfdi::parse_cfdi;
 //!
 //! let path = ("path/to/file.xml");
 //! let xml_string = std::fs::read_to_string(&path).unwrap();
@@ -60,25 +81,120 @@
 //!
 //! if let Ok(parsed) = parse_cfdi(&xml_string) {
 //!
-//!         println!("Emisor: {}, RFC: {}", parsed.emisor.nombre, parsed.emisor.rfc);
-//!         println!("Receptor: {}, RFC: {}", parsed.receptor.nombre, parsed.receptor.rfc);
+//!         println!("Emisor: {:?}, RFC: {}", parsed.emisor.nombre, parsed.emisor.rfc);
+//!         println!("Receptor: {:?}, RFC: {}", parsed.receptor.nombre, parsed.receptor.rfc);
 //!         println!("Subtotal:  {}", parsed.subtotal);
 //!         println!("Total:  {}", parsed.total);
 //!         println!("Fecha Factura:  {}", parsed.fecha);
-//!         println!("UUID:  {}", parsed.complemento);
-//!         println!("Fecha Timbrado:  {}", parsed.fecha_timbrado);
+//!         println!("UUID:  {:?}", parsed.get_uuid());
+//!         println!("Fecha Timbrado:  {:?}", parsed.get_fecha_timbrado());
 //!
 //!
 //!         // Otros datos no incluidos en [`DatosPrincipales`]:
-//!         match &parsed.complemento {
-//!             Some(c) => c.timbre_fiscal_digital
-//!                 .clone()
-//!                 .map(|tfd| println!("Certificado SAT: {}", tfd.no_certificado_sat),
-//!                 None => None,
-//!        }
+//!         if let Some(tfd) = parsed
+//!             .complemento
+//!             .as_ref()
+//!             .and_then(|c| c.timbre_fiscal_digital.as_ref())
+//!         {
+//!             println!("Certificado SAT: {}", tfd.no_certificado_sat);
+//!         }
 //! }
 //!```
 //!
+//! Los importes (`total`, `subtotal`, `importe`, `cantidad`, `valor_unitario`, `descuento`)
+//! se leen como [`Decimal`], que es exacto y conserva los decimales tal cual vienen en el xml
+//! (ej. `"1500.00"` se mantiene como `1500.00`):
+//!
+//! ```rust
+//! use cfdi::Decimal;
+//!
+//! let subtotal: Decimal = "1500.00".parse().unwrap();
+//! let descuento: Decimal = "0.10".parse().unwrap();
+//!
+//! assert_eq!(subtotal.to_string(), "1500.00");
+//! assert_eq!((subtotal - descuento).to_string(), "1499.90");
+//! assert!(subtotal > descuento);
+//! ```
+//!
+//!
+//! ## Catálogos
+//! Los atributos que toman su valor de un catálogo del SAT (forma de pago, uso del CFDI,
+//! régimen fiscal, moneda, etc.) se leen como enums del módulo [`catalogos`], que incluyen la
+//! descripción de cada clave:
+//!
+//! ```rust
+//! use cfdi::catalogos::FormaPago;
+//!
+//! let forma = FormaPago::from("01");
+//! assert_eq!(forma, FormaPago::Efectivo);
+//! assert_eq!(forma.descripcion(), Some("Efectivo"));
+//! ```
+//!
+//! Con el feature `catalogos-conceptos` también se incluyen los catálogos c_ClaveProdServ y
+//! c_ClaveUnidad (`Concepto::clave_prod_serv_sat` y `Concepto::clave_unidad_sat`).
+//!
+//!
+//! ## Validación
+//! [`Comprobante::validate`] revisa las reglas de la Matriz de errores del SAT (totales,
+//! tipo de cambio, importes de conceptos e impuestos, etc.) y regresa la clave de error, la
+//! ruta del nodo y un mensaje por cada regla que no se cumple. Ver el módulo [`validacion`].
+//!
+//! [`Comprobante::recalculate_totals`] calcula el `SubTotal`, el `Descuento`, los impuestos
+//! (agrupados por impuesto, tipo de factor y tasa) y el `Total` a partir de los conceptos, con
+//! los decimales de la moneda y los impuestos locales, y regresa las diferencias con los valores
+//! declarados sin modificarlos; [`Comprobante::update_totals`] además los reemplaza. Ver el
+//! módulo [`totales`].
+//!
+//! El RFC del emisor y del receptor se lee como [`rfc::Rfc`], que valida el formato y el dígito
+//! verificador, y distingue personas físicas, morales y los RFC genéricos.
+//!
+//!
+//! ## Fechas
+//! La `Fecha` y la `FechaTimbrado` se leen como [`NaiveDateTime`]. El módulo [`fechas`] obtiene
+//! la zona horaria del `LugarExpedicion`, y revisa que el comprobante se haya timbrado dentro de
+//! las 72 horas siguientes a su `Fecha` ([`Comprobante::timbrado_en_plazo`]).
+//!
+//!
+//! ## Versiones anteriores
+//! [`parse_cfdi`] también lee comprobantes 3.3 y 3.2, en el mismo [`Comprobante`]. Los atributos
+//! que solo existen en 4.0 (`Exportacion`, `ObjetoImp`, `RegimenFiscalReceptor`, etc.) son
+//! opcionales (aunque al leer un comprobante 4.0 se revisa que existan), y
+//! [`Comprobante::version`] indica cuál se recibió. Los comprobantes 3.3 se pueden
+//! escribir, sellar y verificar igual que los 4.0, incluyendo el complemento de Pagos 1.0.
+//!
+//! Los comprobantes 3.2 se convierten al leerse (sus atributos se escriben en minúsculas, y
+//! algunos tienen otro significado), por lo que solo se pueden leer.
+//!
+//!
+//! ## Retenciones
+//! [`parse_retenciones`] lee un CFDI de Retenciones e Información de Pagos 2.0, que es un
+//! documento distinto al comprobante, en [`retenciones::Retenciones`]. Incluye los complementos
+//! de Dividendos, Intereses, Enajenación de Acciones y Plataformas Tecnológicas, y el UUID del
+//! timbre con [`retenciones::Retenciones::get_uuid`].
+//!
+//!
+//! ## Crear un comprobante
+//! [`ComprobanteBuilder`] construye un comprobante 4.0 nuevo a partir de los datos del emisor,
+//! el receptor y los conceptos ([`ConceptoBuilder`]), y calcula los importes, los impuestos y
+//! los totales con las reglas de redondeo del SAT. Si falta un dato o el comprobante no cumple
+//! las reglas de [`Comprobante::validate`], regresa un [`ErrorConstruccion`].
+//!
+//!
+//! ## Generar el xml
+//! [`Comprobante::to_xml`] escribe el comprobante como un documento CFDI 4.0 válido, con los
+//! prefijos (`cfdi:`, `pago20:`, etc.), namespaces y `xsi:schemaLocation` de cada complemento.
+//! Los complementos que no se leen y la addenda se conservan como [`NodoXml`] y se vuelven a
+//! escribir sin cambios. Para volver a escribir un comprobante ya sellado sin invalidar su
+//! sello, se lee con [`parse_cfdi_sin_perdida`], que conserva el texto original de los atributos.
+//!
+//! [`cadena_original`] genera la cadena original de un xml (la que se firma en el `Sello`) sin
+//! depender de un procesador XSLT. También está disponible como [`Comprobante::cadena_original`].
+//!
+//! Para sellar el comprobante con el CSD del emisor (archivos `.cer` y `.key`) se usa
+//! [`Comprobante::sellar`], ver el módulo [`sello`]. Para revisar el sello de un comprobante
+//! recibido se usa [`sello::verificar`], y para el sello del SAT en el timbre
+//! [`Comprobante::verificar_timbre`].
+//!
 //!
 //! ## Datos Principales
 //! [`DatosPrincipales`] es un struct que facilita recopilar en 1 solo nivel los principales
@@ -88,8 +204,8 @@
 //!
 //!
 //!
-//! ```rust
-//! use cfdi::{DatosPrincipales, get_datos_principales};
+//! ```rust,no_run
+//! use cfdi::{parse_cfdi, DatosPrincipales};
 //!
 //! let path = ("path/to/file.xml");
 //! let xml_string = std::fs::read_to_string(&path).unwrap();
@@ -102,48 +218,145 @@
 //!         println!("Subtotal:  {}", datos.subtotal);
 //!         println!("Total:  {}", datos.total);
 //!         println!("Fecha Factura:  {}", datos.fecha);
-//!         println!("UUID:  {}", datos.get_uuid());
-//!         println!("Fecha Timbrado:  {}", datos.get_fecha_timbrado());
+//!         println!("UUID:  {:?}", datos.uuid);
+//!         println!("Fecha Timbrado:  {:?}", datos.fecha_timbrado);
 //!
 //! }
 //! ```
 
-use anyhow::Result;
-use quick_xml::de::from_str;
+mod builder;
+mod cadena;
+pub mod catalogos;
+mod cfdi32;
+pub mod complementos;
+pub mod fechas;
+mod lectura;
+mod original;
+pub mod retenciones;
+pub mod rfc;
+pub mod sello;
+pub mod totales;
+pub mod validacion;
+mod xml;
+
+pub use builder::{ComprobanteBuilder, ConceptoBuilder, ErrorConstruccion};
+pub use cadena::cadena_original;
+pub use lectura::ErrorLectura;
+pub use original::FormaOriginal;
+pub use retenciones::parse_retenciones;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use catalogos::{
+    Exportacion, FormaPago, Impuesto, Meses, MetodoPago, Moneda, ObjetoImp, Periodicidad,
+    RegimenFiscal, TipoDeComprobante, TipoFactor, TipoRelacion, UsoCfdi,
+};
+use complementos::cartaporte::CartaPorte;
+use complementos::cce20::ComercioExterior;
+use complementos::nomina12::Nomina;
+use complementos::pagos20::Pagos;
+use rfc::Rfc;
+
+/// Tipo decimal exacto usado para todos los importes y cantidades del CFDI.
+///
+/// Conserva la escala escrita en el xml (`"1500.00"` no es lo mismo que `"1500"` al
+/// mostrarse), y permite operaciones aritméticas y comparaciones sin perder centavos.
+pub use rust_decimal::Decimal;
+
+/// Fecha y hora sin zona horaria, usada para la `Fecha` y `FechaTimbrado` (ver [`fechas`]).
+pub use chrono::NaiveDateTime;
 
 /// Nodo principal del CFDI. De aqui se pueden obtener todos los demás subnodos.
+///
+/// Los campos están en el mismo orden que los atributos en el esquema del SAT, que es el
+/// orden en el que se escriben con [`Comprobante::to_xml`].
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Comprobante {
-    // #[serde(rename = "InformacionGlobal")]
-    //pub informacion_global: InformacionGlobal,
-    //
-    // #[serde(rename = "CfdiRelacionados")]
-    // pub cfdi_relacionados: CfdiRelacionados,
-    /// Total de la factura
-    #[serde(rename = "@Total")]
-    pub total: f32,
+    /// Versión del estándar del CFDI: "4.0" o "3.3" (ver [Versiones anteriores](crate#versiones-anteriores))
+    #[serde(rename = "@Version")]
+    pub version: String,
 
-    /// Subtotal de la factura
-    #[serde(rename = "@SubTotal")]
-    pub subtotal: f32,
+    /// Serie del comprobante, para control interno del emisor (hasta 25 caracteres)
+    #[serde(rename = "@Serie")]
+    pub serie: Option<String>,
+
+    /// Folio del comprobante, para control interno del emisor (hasta 40 caracteres)
+    #[serde(rename = "@Folio")]
+    pub folio: Option<String>,
+
+    /// Fecha de la factura, en la hora local del `LugarExpedicion` (ver [`fechas`])
+    #[serde(rename = "@Fecha", with = "fechas::formato")]
+    pub fecha: NaiveDateTime,
 
-    /// Fecha de la factura
-    #[serde(rename = "@Fecha")]
-    pub fecha: String,
+    /// Sello digital del emisor, en base64. Se genera con [`Comprobante::sellar`]
+    #[serde(rename = "@Sello")]
+    pub sello: Option<String>,
 
     /// Forma de pago
     #[serde(rename = "@FormaPago")]
-    pub forma_pago: Option<String>,
+    pub forma_pago: Option<FormaPago>,
+
+    /// Número de serie del certificado (CSD) con el que se selló el comprobante
+    #[serde(rename = "@NoCertificado")]
+    pub no_certificado: Option<String>,
+
+    /// Certificado (CSD) del emisor, en base64
+    #[serde(rename = "@Certificado")]
+    pub certificado: Option<String>,
+
+    /// Condiciones comerciales de pago (ej. "Contado", "30 días")
+    #[serde(rename = "@CondicionesDePago")]
+    pub condiciones_de_pago: Option<String>,
+
+    /// Subtotal de la factura
+    #[serde(rename = "@SubTotal")]
+    pub subtotal: Decimal,
 
-    //TODO: Enum para Forma de Pago
     /// Descuento de la factura
     #[serde(rename = "@Descuento")]
-    pub descuento: Option<String>,
+    pub descuento: Option<Decimal>,
+
+    /// Clave de la moneda (ej. "MXN", "USD")
+    #[serde(rename = "@Moneda")]
+    pub moneda: Moneda,
+
+    /// Tipo de cambio a MXN. Requerido cuando la moneda no es MXN ni XXX
+    #[serde(rename = "@TipoCambio")]
+    pub tipo_cambio: Option<Decimal>,
+
+    /// Total de la factura
+    #[serde(rename = "@Total")]
+    pub total: Decimal,
 
-    /// Tipo de comprobante:
+    /// Tipo de comprobante: Ingreso, Egreso, Traslado, Nómina o Pago
     #[serde(rename = "@TipoDeComprobante")]
-    pub tipo_comprobante: String,
+    pub tipo_comprobante: TipoDeComprobante,
+
+    /// Clave que indica si el comprobante ampara una exportación. Solo versión 4.0
+    #[serde(rename = "@Exportacion")]
+    pub exportacion: Option<Exportacion>,
+
+    /// "PUE" (pago en una exhibición) o "PPD" (pago en parcialidades o diferido)
+    #[serde(rename = "@MetodoPago")]
+    pub metodo_pago: Option<MetodoPago>,
+
+    /// Código postal del lugar de expedición
+    #[serde(rename = "@LugarExpedicion")]
+    pub lugar_expedicion: String,
+
+    /// Clave de confirmación del PAC, requerida cuando el total o el tipo de cambio están
+    /// fuera de los rangos permitidos
+    #[serde(rename = "@Confirmacion")]
+    pub confirmacion: Option<String>,
+
+    /// Periodo de las operaciones de una factura global. Solo versión 4.0
+    #[serde(rename = "InformacionGlobal")]
+    pub informacion_global: Option<InformacionGlobal>,
+
+    /// CFDI relacionados, agrupados por tipo de relación. En 3.3 solo puede haber un grupo
+    #[serde(rename = "CfdiRelacionados", default)]
+    pub cfdi_relacionados: Vec<CfdiRelacionados>,
 
     #[serde(rename = "Emisor")]
     pub emisor: Emisor,
@@ -153,56 +366,126 @@ pub struct Comprobante {
     #[serde(rename = "Conceptos")]
     pub conceptos: Conceptos,
 
+    /// Resumen de los impuestos trasladados y retenidos del comprobante
+    #[serde(rename = "Impuestos")]
+    pub impuestos: Option<Impuestos>,
+
     #[serde(rename = "Complemento")]
     pub complemento: Option<Complemento>,
+
+    /// Nodos dentro de `cfdi:Addenda` (ej. la addenda de una cadena comercial), como xml. No
+    /// forman parte de la cadena original ni del sello.
+    #[serde(skip)]
+    pub addenda: Vec<NodoXml>,
+
+    /// Forma original del documento, si se leyó con [`parse_cfdi_sin_perdida`]. Con ella,
+    /// [`Comprobante::to_xml`] escribe los valores tal como estaban en el documento.
+    #[serde(skip)]
+    pub forma_original: Option<FormaOriginal>,
+}
+
+/// Información de una factura global (a público en general), que ampara las operaciones con
+/// el público de un periodo
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InformacionGlobal {
+    /// Periodo de las operaciones: diario, semanal, quincenal, mensual o bimestral
+    #[serde(rename = "@Periodicidad")]
+    pub periodicidad: Periodicidad,
+
+    /// Mes de las operaciones, o bimestre (claves 13 a 18) si la periodicidad es bimestral
+    #[serde(rename = "@Meses")]
+    pub meses: Meses,
+
+    /// Año de las operaciones
+    #[serde(rename = "@Año")]
+    pub anio: u16,
+}
+
+/// Grupo de CFDI relacionados con el comprobante con un mismo tipo de relación (ej. las
+/// facturas a las que aplica una nota de crédito, o los CFDI que sustituye)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CfdiRelacionados {
+    #[serde(rename = "@TipoRelacion")]
+    pub tipo_relacion: TipoRelacion,
+
+    #[serde(rename = "CfdiRelacionado")]
+    pub cfdi_relacionado: Vec<CfdiRelacionado>,
+}
+
+/// CFDI relacionado, identificado por el UUID de su timbre
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CfdiRelacionado {
+    #[serde(rename = "@UUID")]
+    pub uuid: String,
 }
 
 /// Información del Contribuyente Emisor del Complemento
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Emisor {
     /// RFC del emisor del comprobante
     #[serde(rename = "@Rfc")]
-    pub rfc: String,
+    pub rfc: Rfc,
 
-    /// Nombre, Denominación o Razón Social del emisor del comprobante
+    /// Nombre, Denominación o Razón Social del emisor del comprobante. Requerido en 4.0
     #[serde(rename = "@Nombre")]
-    pub nombre: String,
+    pub nombre: Option<String>,
 
-    /// Clave del Régimen del Emisor -- Ver Catalogos en SAT
+    /// Clave del Régimen del Emisor
     #[serde(rename = "@RegimenFiscal")]
-    pub regimen_fiscal: String,
-    //TODO: agregar `FacAtrAdquiriente`
+    pub regimen_fiscal: RegimenFiscal,
+
+    /// Número de operación del adquirente, cuando el emisor es un coordinado (ej. PCGCFDISP)
+    #[serde(rename = "@FacAtrAdquirente")]
+    pub fac_atr_adquirente: Option<String>,
 }
 
 /// Información del Contribuyente Receptor del Complemento
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Receptor {
     /// RFC del receptor del comprobante
     #[serde(rename = "@Rfc")]
-    pub rfc: String,
+    pub rfc: Rfc,
 
-    /// Nombre, Denominación o Razón Social del receptor del comprobante
+    /// Nombre, Denominación o Razón Social del receptor del comprobante. Requerido en 4.0
     #[serde(rename = "@Nombre")]
-    pub nombre: String,
+    pub nombre: Option<String>,
+
+    /// Código postal del domicilio fiscal del receptor. Solo versión 4.0
+    #[serde(rename = "@DomicilioFiscalReceptor")]
+    pub domicilio_fiscal: Option<String>,
+
+    /// Clave del país de residencia, solo para receptores extranjeros
+    #[serde(rename = "@ResidenciaFiscal")]
+    pub residencia_fiscal: Option<String>,
 
-    /// Clave del Régimen del Emisor -- Ver Catálogos en SAT
+    /// Número de registro de identidad fiscal, solo para receptores extranjeros
+    #[serde(rename = "@NumRegIdTrib")]
+    pub num_reg_id_trib: Option<String>,
+
+    /// Clave del Régimen del Receptor. Solo versión 4.0
     #[serde(rename = "@RegimenFiscalReceptor")]
-    pub regimen_fiscal: String,
+    pub regimen_fiscal: Option<RegimenFiscal>,
 
-    /// Clave del uso que el receptor dará a este CFDI -- Ver Catálogos en SAT
+    /// Clave del uso que el receptor dará a este CFDI
     #[serde(rename = "@UsoCFDI")]
-    pub uso_cfdi: String,
-    // TODO:: Agregar `ResidenciaFiscal` y `NumRegIdTrib`
+    pub uso_cfdi: UsoCfdi,
 }
 
 /// Representa un concepto de la factura.
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Concepto {
     #[serde(rename = "@ClaveProdServ")]
     pub clave_product: String,
 
+    /// Número de parte, SKU o identificador del producto
+    #[serde(rename = "@NoIdentificacion")]
+    pub no_identificacion: Option<String>,
+
     #[serde(rename = "@Cantidad")]
-    pub cantidad: f32,
+    pub cantidad: Decimal,
 
     #[serde(rename = "@ClaveUnidad")]
     pub clave_unidad: String,
@@ -214,34 +497,298 @@ pub struct Concepto {
     pub descripcion: String,
 
     #[serde(rename = "@ValorUnitario")]
-    pub valor_unitario: String,
+    pub valor_unitario: Decimal,
 
     #[serde(rename = "@Importe")]
-    pub importe: f32,
+    pub importe: Decimal,
 
     #[serde(rename = "@Descuento")]
-    pub descuento: Option<f32>,
+    pub descuento: Option<Decimal>,
+
+    /// Clave que indica si la operación es objeto o no de impuesto. Solo versión 4.0
+    #[serde(rename = "@ObjetoImp")]
+    pub objeto_imp: Option<ObjetoImp>,
+
+    /// Impuestos trasladados y retenidos aplicables al concepto
+    #[serde(rename = "Impuestos")]
+    pub impuestos: Option<ImpuestosConcepto>,
+}
+
+impl Concepto {
+    /// Importe del concepto menos su descuento (si lo tiene)
+    pub fn importe_neto(&self) -> Decimal {
+        self.importe - self.descuento.unwrap_or_default()
+    }
+
+    /// Registro de la `ClaveProdServ` del concepto en el catálogo c_ClaveProdServ
+    #[cfg(feature = "catalogos-conceptos")]
+    pub fn clave_prod_serv_sat(&self) -> Option<&'static catalogos::conceptos::ClaveProdServ> {
+        catalogos::conceptos::ClaveProdServ::buscar(&self.clave_product)
+    }
+
+    /// Registro de la `ClaveUnidad` del concepto en el catálogo c_ClaveUnidad
+    #[cfg(feature = "catalogos-conceptos")]
+    pub fn clave_unidad_sat(&self) -> Option<&'static catalogos::conceptos::ClaveUnidad> {
+        catalogos::conceptos::ClaveUnidad::buscar(&self.clave_unidad)
+    }
+}
+
+/// Impuestos a nivel comprobante. Los traslados y retenciones vienen agrupados por
+/// impuesto, tipo de factor y tasa o cuota, por lo que ya representan los totales por tasa.
+///
+/// ```rust
+/// let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+///     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1060.00"
+///     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+///   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+///   <cfdi:Receptor Rfc="XAXX010101000" Nombre="PUBLICO EN GENERAL"
+///       DomicilioFiscalReceptor="44100" RegimenFiscalReceptor="616" UsoCFDI="S01"/>
+///   <cfdi:Conceptos>
+///     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+///         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="02">
+///       <cfdi:Impuestos>
+///         <cfdi:Traslados>
+///           <cfdi:Traslado Base="1000.00" Impuesto="002" TipoFactor="Tasa"
+///               TasaOCuota="0.160000" Importe="160.00"/>
+///         </cfdi:Traslados>
+///         <cfdi:Retenciones>
+///           <cfdi:Retencion Base="1000.00" Impuesto="001" TipoFactor="Tasa"
+///               TasaOCuota="0.100000" Importe="100.00"/>
+///         </cfdi:Retenciones>
+///       </cfdi:Impuestos>
+///     </cfdi:Concepto>
+///   </cfdi:Conceptos>
+///   <cfdi:Impuestos TotalImpuestosRetenidos="100.00" TotalImpuestosTrasladados="160.00">
+///     <cfdi:Retenciones>
+///       <cfdi:Retencion Impuesto="001" Importe="100.00"/>
+///     </cfdi:Retenciones>
+///     <cfdi:Traslados>
+///       <cfdi:Traslado Base="1000.00" Impuesto="002" TipoFactor="Tasa"
+///           TasaOCuota="0.160000" Importe="160.00"/>
+///     </cfdi:Traslados>
+///   </cfdi:Impuestos>
+/// </cfdi:Comprobante>"#;
+///
+/// let cfdi = cfdi::parse_cfdi(xml).unwrap();
+/// let impuestos = cfdi.impuestos.unwrap();
+///
+/// for traslado in impuestos.get_traslados() {
+///     println!("{} al {:?}: {:?}", traslado.impuesto, traslado.tasa_o_cuota, traslado.importe);
+/// }
+/// assert_eq!(impuestos.total_impuestos_trasladados.unwrap().to_string(), "160.00");
+/// assert_eq!(impuestos.get_retenciones()[0].importe.to_string(), "100.00");
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Impuestos {
+    /// Suma de los importes de las retenciones
+    #[serde(rename = "@TotalImpuestosRetenidos")]
+    pub total_impuestos_retenidos: Option<Decimal>,
+
+    /// Suma de los importes de los traslados
+    #[serde(rename = "@TotalImpuestosTrasladados")]
+    pub total_impuestos_trasladados: Option<Decimal>,
+
+    #[serde(rename = "Retenciones")]
+    pub retenciones: Option<Retenciones>,
+
+    #[serde(rename = "Traslados")]
+    pub traslados: Option<Traslados>,
+}
+
+impl Impuestos {
+    /// Regresa un vector con los [`Traslado`] del comprobante
+    pub fn get_traslados(&self) -> Vec<Traslado> {
+        self.traslados
+            .as_ref()
+            .map(|t| t.traslado.clone())
+            .unwrap_or_default()
+    }
+
+    /// Regresa un vector con las [`Retencion`] del comprobante
+    pub fn get_retenciones(&self) -> Vec<Retencion> {
+        self.retenciones
+            .as_ref()
+            .map(|r| r.retencion.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Impuestos de un [`Concepto`]. A diferencia de [`Impuestos`], primero vienen los traslados.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpuestosConcepto {
+    #[serde(rename = "Traslados")]
+    pub traslados: Option<Traslados>,
+
+    #[serde(rename = "Retenciones")]
+    pub retenciones: Option<Retenciones>,
+}
+
+impl ImpuestosConcepto {
+    /// Regresa un vector con los [`Traslado`] del concepto
+    pub fn get_traslados(&self) -> Vec<Traslado> {
+        self.traslados
+            .as_ref()
+            .map(|t| t.traslado.clone())
+            .unwrap_or_default()
+    }
+
+    /// Regresa un vector con las [`Retencion`] del concepto
+    pub fn get_retenciones(&self) -> Vec<Retencion> {
+        self.retenciones
+            .as_ref()
+            .map(|r| r.retencion.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Lista de [`Traslado`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Traslados {
+    #[serde(rename = "Traslado")]
+    pub traslado: Vec<Traslado>,
+}
+
+/// Lista de [`Retencion`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Retenciones {
+    #[serde(rename = "Retencion")]
+    pub retencion: Vec<Retencion>,
+}
+
+/// Impuesto trasladado (IVA, IEPS)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Traslado {
+    /// Base para el cálculo del impuesto. En la versión 3.3 solo existe a nivel concepto
+    #[serde(rename = "@Base")]
+    pub base: Option<Decimal>,
+
+    /// Clave del impuesto (001 ISR, 002 IVA, 003 IEPS)
+    #[serde(rename = "@Impuesto")]
+    pub impuesto: Impuesto,
+
+    /// Tasa, Cuota o Exento
+    #[serde(rename = "@TipoFactor")]
+    pub tipo_factor: TipoFactor,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@TasaOCuota")]
+    pub tasa_o_cuota: Option<Decimal>,
+
+    /// No existe cuando el tipo de factor es Exento
+    #[serde(rename = "@Importe")]
+    pub importe: Option<Decimal>,
+}
+
+/// Impuesto retenido (ISR, IVA). A nivel comprobante solo trae `impuesto` e `importe`.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Retencion {
+    /// Base para el cálculo del impuesto. Solo a nivel concepto.
+    #[serde(rename = "@Base")]
+    pub base: Option<Decimal>,
+
+    /// Clave del impuesto (001 ISR, 002 IVA, 003 IEPS)
+    #[serde(rename = "@Impuesto")]
+    pub impuesto: Impuesto,
+
+    /// Solo a nivel concepto.
+    #[serde(rename = "@TipoFactor")]
+    pub tipo_factor: Option<TipoFactor>,
+
+    /// Solo a nivel concepto.
+    #[serde(rename = "@TasaOCuota")]
+    pub tasa_o_cuota: Option<Decimal>,
+
+    #[serde(rename = "@Importe")]
+    pub importe: Decimal,
 }
 
 /// Comlemento de la factura. Incluye Timbre Fiscal (si se encuentra)
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Complemento {
     #[serde(rename = "TimbreFiscalDigital")]
     pub timbre_fiscal_digital: Option<TimbreFiscalDigital>,
+
+    /// Complemento para Recepción de Pagos 2.0 (o 1.0 en CFDI 3.3), en comprobantes tipo "P"
+    #[serde(rename = "Pagos")]
+    pub pagos: Option<Pagos>,
+
+    /// Complementos de Nómina 1.2, en comprobantes tipo "N"
+    #[serde(rename = "Nomina", default)]
+    pub nomina: Vec<Nomina>,
+
+    /// Complemento Carta Porte (versiones 2.0, 3.0 y 3.1)
+    #[serde(rename = "CartaPorte")]
+    pub carta_porte: Option<CartaPorte>,
+
+    /// Complemento de Comercio Exterior 2.0
+    #[serde(rename = "ComercioExterior")]
+    pub comercio_exterior: Option<ComercioExterior>,
+
+    /// Complementos que no se leen en structs (ej. `implocal:ImpuestosLocales`), como xml.
+    /// Se vuelven a escribir con [`Comprobante::to_xml`], pero no forman parte de la cadena
+    /// original.
+    #[serde(skip)]
+    pub otros: Vec<NodoXml>,
+}
+
+/// Nodo que se conserva tal como estaba en el documento, para los complementos que no se leen
+/// y la addenda (ver [`Complemento::otros`] y [`Comprobante::addenda`]).
+///
+/// Los prefijos que usa el nodo normalmente se declaran en el nodo principal del documento;
+/// esas declaraciones se guardan en `namespaces`, y [`NodoXml::documento`] regresa el nodo
+/// como un documento independiente.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodoXml {
+    /// Nombre local del nodo (ej. `"ImpuestosLocales"`)
+    pub nombre: String,
+    /// Namespace del nodo, si tiene
+    pub namespace: Option<String>,
+    /// Declaraciones `xmlns` (prefijo, uri) de los nodos superiores que se usan dentro del
+    /// nodo. El prefijo vacío es el namespace por omisión.
+    pub namespaces: Vec<(String, String)>,
+    /// Ubicación del esquema (xsd) del namespace del nodo, según el `xsi:schemaLocation` de
+    /// los nodos superiores
+    pub esquema: Option<String>,
+    /// El nodo completo, tal como estaba en el documento
+    pub xml: String,
 }
 
 /// Representa el Timbre Fiscal, incluye el UUID, certificado SAT, etc.
+///
+/// El sello del SAT se puede verificar con [`Comprobante::verificar_timbre`].
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TimbreFiscalDigital {
     #[serde(rename = "@Version")]
     pub version: String,
     #[serde(rename = "@UUID")]
     pub uuid: String,
-    #[serde(rename = "@FechaTimbrado")]
-    pub fecha_timbrado: String,
+    /// Fecha de timbrado, en la hora de la Zona Centro
+    #[serde(rename = "@FechaTimbrado", with = "fechas::formato")]
+    pub fecha_timbrado: NaiveDateTime,
+
+    /// RFC del proveedor de certificación (PAC) que timbró el comprobante. Solo versión 1.1
+    #[serde(rename = "@RfcProvCertif")]
+    pub rfc_prov_certif: Option<String>,
+
+    #[serde(rename = "@Leyenda")]
+    pub leyenda: Option<String>,
+
+    /// Copia del `Sello` del comprobante que se timbró
+    #[serde(rename = "@SelloCFD")]
+    pub sello_cfd: String,
 
     #[serde(rename = "@NoCertificadoSAT")]
     pub no_certificado_sat: String,
+
+    /// Sello del SAT, firma de [`TimbreFiscalDigital::cadena_original`]
+    #[serde(rename = "@SelloSAT")]
+    pub sello_sat: String,
 }
 
 /// Lista de [`Concepto`]
@@ -252,23 +799,81 @@ pub struct Conceptos {
 }
 
 /// Intenta generar un objeto de tipo `Comprobante` a partir de un texto (&str)
-pub fn parse_cfdi(xml_content: &str) -> Result<Comprobante> {
-    let res: Comprobante = from_str(xml_content)?;
-    Ok(res)
+///
+/// Se aceptan comprobantes 4.0, 3.3 y 3.2 (ver [Versiones anteriores](crate#versiones-anteriores)).
+/// Si no se puede leer, el [`ErrorLectura`] indica el motivo y el nodo donde ocurrió.
+///
+/// Los atributos que solo existen desde la versión 4.0 (ej. `Exportacion` u `ObjetoImp`) se
+/// leen como `Option`, pero en un comprobante 4.0 son requeridos: si faltan se regresa
+/// [`ErrorLectura::AtributoFaltante`]. Lo mismo con el `RfcProvCertif` del timbre 1.1.
+pub fn parse_cfdi(xml_content: &str) -> Result<Comprobante, ErrorLectura> {
+    let version = lectura::revisar(xml_content, "Comprobante", &["4.0", "3.3", "3.2"])?;
+    let mut cfdi = if version == "3.2" {
+        cfdi32::parse(xml_content)?
+    } else {
+        lectura::deserializar(xml_content, "Comprobante")?
+    };
+    lectura::revisar_requeridos(xml_content, &cfdi)?;
+    lectura::conservar_nodos(xml_content, &mut cfdi);
+    Ok(cfdi)
+}
+
+/// Igual que [`parse_cfdi`], pero guarda la [`forma_original`](Comprobante::forma_original) del
+/// documento: el texto de los atributos tal como está escrito, y los atributos y nodos que no
+/// se leen en structs (ej. la `InformacionAduanera` de los conceptos).
+///
+/// Al volver a escribirlo con [`Comprobante::to_xml`], los atributos que no se modificaron
+/// conservan su texto original y los nodos que no se leen se escriben en su lugar, por lo que
+/// la cadena original (y el sello) es la misma que la del documento leído. El orden de los
+/// atributos y los espacios entre nodos pueden cambiar.
+///
+/// Los comprobantes 3.2 no se pueden escribir, y se leen igual que con [`parse_cfdi`].
+///
+/// ```rust
+/// # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+/// #     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1000.00"
+/// #     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="45079">
+/// #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+/// #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+/// #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+/// #   <cfdi:Conceptos>
+/// <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="H87"
+///     Descripcion="Libro 'Rust'" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="01">
+///   <cfdi:CuentaPredial Numero="1234567"/>
+/// </cfdi:Concepto>
+/// #   </cfdi:Conceptos>
+/// # </cfdi:Comprobante>"#;
+/// let cfdi = cfdi::parse_cfdi_sin_perdida(xml).unwrap();
+/// let generado = cfdi.to_xml().unwrap();
+///
+/// assert!(generado.contains(r#"Descripcion="Libro 'Rust'""#));
+/// assert!(generado.contains(r#"<cfdi:CuentaPredial Numero="1234567"/>"#));
+/// assert_eq!(
+///     cfdi::cadena_original(&generado).unwrap(),
+///     cfdi::cadena_original(xml).unwrap()
+/// );
+/// ```
+pub fn parse_cfdi_sin_perdida(xml_content: &str) -> Result<Comprobante, ErrorLectura> {
+    let mut cfdi = parse_cfdi(xml_content)?;
+    if let Ok(generado) = cfdi.to_xml() {
+        cfdi.forma_original = Some(FormaOriginal::comparar(xml_content, &generado));
+    }
+    Ok(cfdi)
 }
 
 /// Utility Struct - para guardar datos principales de un comprobante en 1 solo struct
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatosPrincipales {
-    pub total: f32,
-    pub subtotal: f32,
-    pub fecha: String,
+    pub total: Decimal,
+    pub subtotal: Decimal,
+    pub fecha: NaiveDateTime,
     pub emisor_nombre: String,
     pub emisor_rfc: String,
     pub receptor_nombre: String,
     pub receptor_rfc: String,
     pub uuid: Option<String>,
-    pub fecha_timbrado: Option<String>,
+    pub fecha_timbrado: Option<NaiveDateTime>,
     pub conceptos: Vec<Concepto>,
 }
 
@@ -278,6 +883,54 @@ impl Comprobante {
         self.conceptos.concepto.clone()
     }
 
+    /// Suma de los importes de todos los [`Concepto`] (antes de descuentos)
+    pub fn suma_importes(&self) -> Decimal {
+        self.conceptos.concepto.iter().map(|c| c.importe).sum()
+    }
+
+    /// Descuento del comprobante, o cero si no tiene
+    pub fn descuento_o_cero(&self) -> Decimal {
+        self.descuento.unwrap_or_default()
+    }
+
+    /// UUID de los CFDI relacionados con `tipo_relacion`, de todos los grupos de
+    /// [`CfdiRelacionados`] (ej. las facturas a las que aplica una nota de crédito, con
+    /// [`TipoRelacion::NotaDeCredito`])
+    ///
+    /// ```rust
+    /// # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+    /// #     Fecha="2024-01-15T10:00:00" FormaPago="03" SubTotal="100.00" Moneda="MXN"
+    /// #     Total="100.00" TipoDeComprobante="E" Exportacion="01" MetodoPago="PUE"
+    /// #     LugarExpedicion="44100">
+    /// <cfdi:CfdiRelacionados TipoRelacion="01">
+    ///   <cfdi:CfdiRelacionado UUID="5FB2822E-396D-4725-8521-CDC4BDD20CCF"/>
+    ///   <cfdi:CfdiRelacionado UUID="9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D"/>
+    /// </cfdi:CfdiRelacionados>
+    /// #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+    /// #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+    /// #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G02"/>
+    /// #   <cfdi:Conceptos>
+    /// #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+    /// #         Descripcion="Bonificación" ValorUnitario="100.00" Importe="100.00" ObjetoImp="01"/>
+    /// #   </cfdi:Conceptos>
+    /// # </cfdi:Comprobante>"#;
+    /// use cfdi::catalogos::TipoRelacion;
+    ///
+    /// let nota = cfdi::parse_cfdi(xml).unwrap();
+    /// assert_eq!(
+    ///     nota.uuids_relacionados(&TipoRelacion::NotaDeCredito),
+    ///     ["5FB2822E-396D-4725-8521-CDC4BDD20CCF", "9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D"]
+    /// );
+    /// assert!(nota.uuids_relacionados(&TipoRelacion::Sustitucion).is_empty());
+    /// ```
+    pub fn uuids_relacionados(&self, tipo_relacion: &TipoRelacion) -> Vec<&str> {
+        self.cfdi_relacionados
+            .iter()
+            .filter(|r| r.tipo_relacion == *tipo_relacion)
+            .flat_map(|r| r.cfdi_relacionado.iter().map(|c| c.uuid.as_str()))
+            .collect()
+    }
+
     /// Regresa un `Option<String>`, con el UUID dentro del Some si la factura tiene Complemento
     pub fn get_uuid(&self) -> Option<String> {
         match &self.complemento {
@@ -286,8 +939,8 @@ impl Comprobante {
         }
     }
 
-    /// Regresa un `Option<String>`, con la fecha de timbrado dentro del Some si la factura tiene Complemento
-    pub fn get_fecha_timbrado(&self) -> Option<String> {
+    /// Regresa un `Option<NaiveDateTime>`, con la fecha de timbrado dentro del Some si la factura tiene Complemento
+    pub fn get_fecha_timbrado(&self) -> Option<NaiveDateTime> {
         match &self.complemento {
             Some(c) => c
                 .timbre_fiscal_digital
@@ -301,11 +954,11 @@ impl Comprobante {
     pub fn get_datos_principales(self) -> DatosPrincipales {
         let total = self.total;
         let subtotal = self.subtotal;
-        let fecha = self.fecha.clone();
-        let emisor_nombre = self.emisor.nombre.clone();
-        let emisor_rfc = self.emisor.rfc.clone();
-        let receptor_nombre = self.receptor.nombre.clone();
-        let receptor_rfc = self.receptor.rfc.clone();
+        let fecha = self.fecha;
+        let emisor_nombre = self.emisor.nombre.clone().unwrap_or_default();
+        let emisor_rfc = self.emisor.rfc.to_string();
+        let receptor_nombre = self.receptor.nombre.clone().unwrap_or_default();
+        let receptor_rfc = self.receptor.rfc.to_string();
         let uuid = self.get_uuid();
         let fecha_timbrado = self.get_fecha_timbrado();
         let conceptos = self.get_conceptos();
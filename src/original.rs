@@ -0,0 +1,290 @@
+//! Forma original de un documento leído con
+//! [`parse_cfdi_sin_perdida`](crate::parse_cfdi_sin_perdida).
+//!
+//! Al leer el documento se vuelve a escribir con [`Comprobante::to_xml`](crate::Comprobante::to_xml)
+//! y se compara con el original, nodo por nodo. Se guardan:
+//!
+//! - El texto original de los atributos que se escribirían distinto (ej. `O'Reilly`, que se
+//!   escribe como `O&apos;Reilly`, o un `xsi:schemaLocation` con otros esquemas).
+//! - Los atributos y nodos que no se leen en structs, con la posición de cada nodo entre sus
+//!   hermanos.
+//!
+//! Al escribir, cada atributo conserva su texto original mientras su valor no cambie, y los
+//! atributos y nodos que no se leen se agregan en su lugar. Los nodos se identifican por su
+//! ruta (ej. `Comprobante/Conceptos/Concepto[2]`), así que si se agregan o quitan nodos los
+//! datos originales se aplican por posición.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use anyhow::Result;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::{Reader, Writer};
+
+use crate::lectura::{nodo_xml, Ambito};
+use crate::NodoXml;
+
+/// Lo que se necesita para volver a escribir un comprobante leído con
+/// [`parse_cfdi_sin_perdida`](crate::parse_cfdi_sin_perdida) sin cambiar sus valores, ver
+/// [`Comprobante::forma_original`](crate::Comprobante::forma_original).
+#[derive(Debug, Clone, Default)]
+pub struct FormaOriginal {
+    atributos: Vec<AtributoOriginal>,
+    nodos: Vec<NodoOriginal>,
+}
+
+/// Atributo que se escribe distinto al original, o que no se lee (`generado` vacío)
+#[derive(Debug, Clone)]
+struct AtributoOriginal {
+    ruta: String,
+    nombre: String,
+    original: String,
+    generado: Option<String>,
+}
+
+/// Nodo que no se lee, con la ruta de su padre y su posición entre sus hermanos
+#[derive(Debug, Clone)]
+struct NodoOriginal {
+    padre: String,
+    indice: usize,
+    nodo: NodoXml,
+}
+
+/// Rutas de los nodos abiertos mientras se recorre un documento
+#[derive(Default)]
+struct Rutas {
+    /// Ruta de cada nodo abierto, cuántos hijos de cada nombre tiene y cuántos en total
+    pila: Vec<(String, HashMap<String, usize>, usize)>,
+}
+
+impl Rutas {
+    /// Ruta del nodo `nombre` que empieza dentro del último nodo abierto
+    fn hijo(&mut self, nombre: &str) -> String {
+        match self.pila.last_mut() {
+            Some((padre, nombres, hijos)) => {
+                let n = nombres.entry(nombre.to_string()).or_insert(0);
+                *n += 1;
+                *hijos += 1;
+                format!("{}/{}[{}]", padre, nombre, n)
+            }
+            None => format!("{}[1]", nombre),
+        }
+    }
+
+    /// Ruta y número de hijos del último nodo abierto
+    fn padre(&self) -> Option<(&str, usize)> {
+        self.pila
+            .last()
+            .map(|(ruta, _, hijos)| (ruta.as_str(), *hijos))
+    }
+
+    fn abrir(&mut self, ruta: String) {
+        self.pila.push((ruta, HashMap::new(), 0));
+    }
+
+    fn cerrar(&mut self) {
+        self.pila.pop();
+    }
+}
+
+fn nombre_local(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+/// Atributos (nombre, texto sin cambiar escapes) de un nodo
+fn atributos(e: &BytesStart) -> Vec<(String, String)> {
+    e.attributes()
+        .with_checks(false)
+        .flatten()
+        .map(|a| {
+            let nombre = String::from_utf8_lossy(a.key.as_ref()).into_owned();
+            (nombre, String::from_utf8_lossy(&a.value).into_owned())
+        })
+        .collect()
+}
+
+impl FormaOriginal {
+    /// Compara el documento `original` con el que se `generado` al leerlo y volverlo a escribir
+    pub(crate) fn comparar(original: &str, generado: &str) -> FormaOriginal {
+        let mut generados: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        let mut reader = Reader::from_str(generado);
+        let mut rutas = Rutas::default();
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    let ruta = rutas.hijo(&nombre_local(&e));
+                    generados.insert(ruta.clone(), atributos(&e));
+                    rutas.abrir(ruta);
+                }
+                Ok(Event::Empty(e)) => {
+                    let ruta = rutas.hijo(&nombre_local(&e));
+                    generados.insert(ruta, atributos(&e));
+                }
+                Ok(Event::End(_)) => rutas.cerrar(),
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+
+        let mut forma = FormaOriginal::default();
+        let mut reader = Reader::from_str(original);
+        let mut rutas = Rutas::default();
+        let mut ambitos: Vec<Ambito> = vec![];
+        loop {
+            let inicio = reader.buffer_position() as usize;
+            let (e, vacio) = match reader.read_event() {
+                Ok(Event::Start(e)) => (e, false),
+                Ok(Event::Empty(e)) => (e, true),
+                Ok(Event::End(_)) => {
+                    rutas.cerrar();
+                    ambitos.pop();
+                    continue;
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => continue,
+            };
+
+            let indice = rutas.padre().map_or(0, |(_, hijos)| hijos);
+            let ruta = rutas.hijo(&nombre_local(&e));
+            let ambito = Ambito::de(&e);
+
+            let Some(escritos) = generados.get(&ruta) else {
+                // Nodo que no se lee: se guarda completo, con sus hijos
+                if !vacio && reader.read_to_end(e.name()).is_err() {
+                    break;
+                }
+                let fin = reader.buffer_position() as usize;
+                if let Some((padre, _)) = rutas.padre() {
+                    forma.nodos.push(NodoOriginal {
+                        padre: padre.to_string(),
+                        indice,
+                        nodo: nodo_xml(&ambitos, &e, ambito, &original[inicio..fin]),
+                    });
+                }
+                continue;
+            };
+
+            for (nombre, texto) in atributos(&e) {
+                let generado = escritos.iter().find(|(n, _)| *n == nombre).map(|a| &a.1);
+                if generado != Some(&texto) {
+                    forma.atributos.push(AtributoOriginal {
+                        ruta: ruta.clone(),
+                        nombre,
+                        original: texto,
+                        generado: generado.cloned(),
+                    });
+                }
+            }
+            if !vacio {
+                rutas.abrir(ruta);
+                ambitos.push(ambito);
+            }
+        }
+        forma
+    }
+
+    /// Aplica la forma original al documento `xml` generado con `to_xml`
+    pub(crate) fn restaurar(&self, xml: &str) -> Result<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut writer = Writer::new(Vec::new());
+        let mut rutas = Rutas::default();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => {
+                    self.insertar_nodos(&mut writer, &mut rutas, false)?;
+                    let ruta = rutas.hijo(&nombre_local(&e));
+                    writer.write_event(Event::Start(self.inicio(&e, &ruta)))?;
+                    rutas.abrir(ruta);
+                }
+                Event::Empty(e) => {
+                    self.insertar_nodos(&mut writer, &mut rutas, false)?;
+                    let ruta = rutas.hijo(&nombre_local(&e));
+                    let inicio = self.inicio(&e, &ruta);
+                    if self.nodos.iter().any(|n| n.padre == ruta) {
+                        // Nodo vacío al que le faltan hijos que no se leen
+                        writer.write_event(Event::Start(inicio))?;
+                        rutas.abrir(ruta);
+                        self.insertar_nodos(&mut writer, &mut rutas, true)?;
+                        rutas.cerrar();
+                        let nombre = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                        writer.write_event(Event::End(BytesEnd::new(nombre)))?;
+                    } else {
+                        writer.write_event(Event::Empty(inicio))?;
+                    }
+                }
+                Event::End(e) => {
+                    self.insertar_nodos(&mut writer, &mut rutas, true)?;
+                    rutas.cerrar();
+                    writer.write_event(Event::End(e))?;
+                }
+                Event::Eof => break,
+                e => writer.write_event(e)?,
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner())?)
+    }
+
+    /// Nodo de inicio con el texto original de sus atributos
+    fn inicio<'a>(&'a self, e: &'a BytesStart, ruta: &str) -> BytesStart<'a> {
+        let mut inicio = BytesStart::new(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+        let originales: Vec<&AtributoOriginal> =
+            self.atributos.iter().filter(|a| a.ruta == ruta).collect();
+
+        for atributo in e.attributes().with_checks(false).flatten() {
+            let nombre = String::from_utf8_lossy(atributo.key.as_ref());
+            let texto = String::from_utf8_lossy(&atributo.value);
+            let valor = originales
+                .iter()
+                .find(|a| a.nombre == nombre && a.generado.as_deref() == Some(&texto))
+                .map_or(atributo.value.clone(), |a| {
+                    Cow::Borrowed(a.original.as_bytes())
+                });
+            inicio.push_attribute(Attribute {
+                key: QName(atributo.key.into_inner()),
+                value: valor,
+            });
+        }
+        for atributo in originales.iter().filter(|a| a.generado.is_none()) {
+            if e.try_get_attribute(atributo.nombre.as_str())
+                .ok()
+                .flatten()
+                .is_none()
+            {
+                inicio.push_attribute(Attribute {
+                    key: QName(atributo.nombre.as_bytes()),
+                    value: Cow::Borrowed(atributo.original.as_bytes()),
+                });
+            }
+        }
+        inicio
+    }
+
+    /// Escribe los nodos que no se leen del último nodo abierto que van en la posición actual,
+    /// o todos los que faltan si el nodo se está `cerrando`
+    fn insertar_nodos(
+        &self,
+        writer: &mut Writer<Vec<u8>>,
+        rutas: &mut Rutas,
+        cerrando: bool,
+    ) -> Result<()> {
+        loop {
+            let Some((padre, hijos)) = rutas.padre() else {
+                return Ok(());
+            };
+            let siguiente = self
+                .nodos
+                .iter()
+                .filter(|n| n.padre == padre && n.indice >= hijos)
+                .min_by_key(|n| n.indice)
+                .filter(|n| cerrando || n.indice == hijos);
+            let Some(nodo) = siguiente else {
+                return Ok(());
+            };
+            rutas.hijo(&nodo.nodo.nombre);
+            writer.get_mut().extend_from_slice(nodo.nodo.xml.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Complemento de Dividendos 1.0 (`dividendos:Dividendos`), para la retención por pago de
+//! dividendos o utilidades distribuidas.
+//!
+//! ```markdown
+//!|-Dividendos
+//!     |-- DividOUtil (opcional)
+//!     |-- Remanente (opcional)
+//! ```
+
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento de dividendos
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Dividendos {
+    /// Versión del complemento, "1.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    #[serde(rename = "DividOUtil")]
+    pub divid_o_util: Option<DividOUtil>,
+
+    #[serde(rename = "Remanente")]
+    pub remanente: Option<Remanente>,
+}
+
+/// Dividendos o utilidades distribuidas
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DividOUtil {
+    /// Clave del tipo de dividendo o utilidad -- Ver Catálogos en SAT
+    #[serde(rename = "@CveTipDivOUtil")]
+    pub cve_tip_div_o_util: String,
+
+    /// ISR acreditable retenido en territorio nacional
+    #[serde(rename = "@MontISRAcredRetMexico")]
+    pub mont_isr_acred_ret_mexico: Decimal,
+
+    /// ISR acreditable retenido en el extranjero
+    #[serde(rename = "@MontISRAcredRetExtranjero")]
+    pub mont_isr_acred_ret_extranjero: Decimal,
+
+    /// Retención en el extranjero sobre dividendos del extranjero
+    #[serde(rename = "@MontRetExtDivExt")]
+    pub mont_ret_ext_div_ext: Option<Decimal>,
+
+    /// "Sociedad Nacional" o "Sociedad Extranjera"
+    #[serde(rename = "@TipoSocDistrDiv")]
+    pub tipo_soc_distr_div: String,
+
+    /// ISR acreditable nacional
+    #[serde(rename = "@MontISRAcredNal")]
+    pub mont_isr_acred_nal: Option<Decimal>,
+
+    /// Dividendo acumulable nacional
+    #[serde(rename = "@MontDivAcumNal")]
+    pub mont_div_acum_nal: Option<Decimal>,
+
+    /// Dividendo acumulable extranjero
+    #[serde(rename = "@MontDivAcumExt")]
+    pub mont_div_acum_ext: Option<Decimal>,
+}
+
+/// Remanente distribuible
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Remanente {
+    /// Proporción de participación en el remanente distribuible
+    #[serde(rename = "@ProporcionRem")]
+    pub proporcion_rem: Option<Decimal>,
+}
@@ -0,0 +1,23 @@
+//! Complemento de Enajenación de Acciones 1.0 (`enajenaciondeacciones:EnajenaciondeAcciones`),
+//! para la ganancia o pérdida por la venta de acciones u operaciones de valores.
+
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Nodo principal del complemento de enajenación de acciones
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnajenaciondeAcciones {
+    /// Versión del complemento, "1.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Descripción del contrato de intermediación
+    #[serde(rename = "@ContratoIntermediacion")]
+    pub contrato_intermediacion: String,
+
+    #[serde(rename = "@Ganancia")]
+    pub ganancia: Decimal,
+
+    #[serde(rename = "@Perdida")]
+    pub perdida: Decimal,
+}
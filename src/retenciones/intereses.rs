@@ -0,0 +1,36 @@
+//! Complemento de Intereses 1.0 (`intereses:Intereses`), para los intereses obtenidos en el
+//! periodo o ejercicio.
+
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Nodo principal del complemento de intereses
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Intereses {
+    /// Versión del complemento, "1.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// "SI" o "NO", si los intereses provienen del sistema financiero
+    #[serde(rename = "@SistFinanciero")]
+    pub sist_financiero: String,
+
+    /// "SI" o "NO", si los intereses se retiraron en el periodo o ejercicio
+    #[serde(rename = "@RetiroAORESRetInt")]
+    pub retiro_aores_ret_int: String,
+
+    /// "SI" o "NO", si corresponden a operaciones financieras derivadas
+    #[serde(rename = "@OperFinancDerivad")]
+    pub oper_financ_derivad: String,
+
+    /// Interés nominal
+    #[serde(rename = "@MontIntNominal")]
+    pub mont_int_nominal: Decimal,
+
+    /// Interés real
+    #[serde(rename = "@MontIntReal")]
+    pub mont_int_real: Decimal,
+
+    #[serde(rename = "@Perdida")]
+    pub perdida: Decimal,
+}
@@ -0,0 +1,332 @@
+//! CFDI de Retenciones e Información de Pagos 2.0 (`retenciones:Retenciones`).
+//!
+//! Es un documento distinto al [`Comprobante`](crate::Comprobante), que emiten los bancos,
+//! plataformas, etc. para informar las retenciones que hicieron en un periodo. Se lee con
+//! [`parse_retenciones`].
+//!
+//! ```markdown
+//!|-Retenciones
+//!     |-- CfdiRetenRelacionados (opcional)
+//!     |-- Emisor
+//!     |-- Receptor
+//!          |-- Nacional o Extranjero
+//!     |-- Periodo
+//!     |-- Totales
+//!          |-- ImpRetenidos (0..n)
+//!     |-- Complemento (opcional) - Incluye TimbreFiscalDigital, Dividendos, Intereses,
+//!                                  Enajenación de Acciones y Plataformas Tecnológicas
+//! ```
+//!
+//! ```rust
+//! let xml = r#"<retenciones:Retenciones
+//!     xmlns:retenciones="http://www.sat.gob.mx/esquemas/retencionpago/2"
+//!     xmlns:intereses="http://www.sat.gob.mx/esquemas/retencionpago/1/intereses"
+//!     xmlns:tfd="http://www.sat.gob.mx/TimbreFiscalDigital" Version="2.0"
+//!     FechaExp="2024-01-10T09:00:00" LugarExpRetenc="06600" CveRetenc="16">
+//!   <retenciones:Emisor RfcE="EKU9003173C9" NomDenRazSocE="ESCUELA KEMPER URGATE"
+//!       RegimenFiscalE="601"/>
+//!   <retenciones:Receptor NacionalidadR="Nacional">
+//!     <retenciones:Nacional RfcR="XOJI740919U48" NomDenRazSocR="INGRID XODAR JIMENEZ"
+//!         DomicilioFiscalR="88965"/>
+//!   </retenciones:Receptor>
+//!   <retenciones:Periodo MesIni="01" MesFin="12" Ejercicio="2023"/>
+//!   <retenciones:Totales MontoTotOperacion="5000.00" MontoTotGrav="5000.00"
+//!       MontoTotExent="0.00" MontoTotRet="45.00">
+//!     <retenciones:ImpRetenidos BaseRet="5000.00" ImpuestoRet="001" MontoRet="45.00"
+//!         TipoPagoRet="01"/>
+//!   </retenciones:Totales>
+//!   <retenciones:Complemento>
+//!     <intereses:Intereses Version="1.0" SistFinanciero="SI" RetiroAORESRetInt="NO"
+//!         OperFinancDerivad="NO" MontIntNominal="5000.00" MontIntReal="1200.00" Perdida="0"/>
+//!     <tfd:TimbreFiscalDigital Version="1.1" UUID="9E2D5A61-8F3B-4C27-A1D0-6B4E3F2A1C5D"
+//!         FechaTimbrado="2024-01-10T09:01:00" RfcProvCertif="SPR190613I52" SelloCFD="AAAA"
+//!         NoCertificadoSAT="00001000000509846663" SelloSAT="BBBB"/>
+//!   </retenciones:Complemento>
+//! </retenciones:Retenciones>"#;
+//!
+//! let retenciones = cfdi::parse_retenciones(xml).unwrap();
+//!
+//! assert_eq!(retenciones.receptor.rfc().unwrap(), "XOJI740919U48");
+//! assert_eq!(retenciones.totales.monto_tot_ret.to_string(), "45.00");
+//! assert_eq!(
+//!     retenciones.get_uuid().as_deref(),
+//!     Some("9E2D5A61-8F3B-4C27-A1D0-6B4E3F2A1C5D")
+//! );
+//!
+//! let intereses = retenciones.complemento.unwrap().intereses.unwrap();
+//! assert_eq!(intereses.mont_int_real.to_string(), "1200.00");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::catalogos::{Impuesto, RegimenFiscal};
+use crate::lectura::{self, ErrorLectura};
+use crate::rfc::Rfc;
+use crate::{fechas, Decimal, NaiveDateTime, TimbreFiscalDigital};
+
+pub mod dividendos;
+pub mod enajenaciondeacciones;
+pub mod intereses;
+pub mod plataformas_tecnologicas;
+
+use dividendos::Dividendos;
+use enajenaciondeacciones::EnajenaciondeAcciones;
+use intereses::Intereses;
+use plataformas_tecnologicas::ServiciosPlataformasTecnologicas;
+
+/// Intenta generar un objeto de tipo [`Retenciones`] a partir de un texto (&str)
+///
+/// Solo se acepta la versión 2.0. Si no se puede leer, el [`ErrorLectura`] indica el motivo y
+/// el nodo donde ocurrió.
+pub fn parse_retenciones(xml_content: &str) -> Result<Retenciones, ErrorLectura> {
+    lectura::revisar(xml_content, "Retenciones", &["2.0"])?;
+    lectura::deserializar(xml_content, "Retenciones")
+}
+
+/// Nodo principal del CFDI de retenciones
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Retenciones {
+    /// Versión del estándar, "2.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Folio interno del emisor
+    #[serde(rename = "@FolioInt")]
+    pub folio_int: Option<String>,
+
+    /// Sello digital del emisor, en base64
+    #[serde(rename = "@Sello")]
+    pub sello: Option<String>,
+
+    /// Número de serie del certificado (CSD) con el que se selló el documento
+    #[serde(rename = "@NoCertificado")]
+    pub no_certificado: Option<String>,
+
+    /// Certificado (CSD) del emisor, en base64
+    #[serde(rename = "@Certificado")]
+    pub certificado: Option<String>,
+
+    /// Fecha de expedición, en la hora local del `LugarExpRetenc`
+    #[serde(rename = "@FechaExp", with = "fechas::formato")]
+    pub fecha_exp: NaiveDateTime,
+
+    /// Código postal del lugar de expedición
+    #[serde(rename = "@LugarExpRetenc")]
+    pub lugar_exp_retenc: String,
+
+    /// Clave de la retención o información de pagos -- Ver Catálogos en SAT (c_CveRetenc)
+    #[serde(rename = "@CveRetenc")]
+    pub cve_retenc: String,
+
+    /// Descripción de la retención, cuando la clave es "25" (Otro tipo de retenciones)
+    #[serde(rename = "@DescRetenc")]
+    pub desc_retenc: Option<String>,
+
+    #[serde(rename = "CfdiRetenRelacionados")]
+    pub cfdi_reten_relacionados: Option<CfdiRetenRelacionados>,
+
+    #[serde(rename = "Emisor")]
+    pub emisor: Emisor,
+
+    #[serde(rename = "Receptor")]
+    pub receptor: Receptor,
+
+    #[serde(rename = "Periodo")]
+    pub periodo: Periodo,
+
+    #[serde(rename = "Totales")]
+    pub totales: Totales,
+
+    #[serde(rename = "Complemento")]
+    pub complemento: Option<Complemento>,
+}
+
+impl Retenciones {
+    /// Regresa un `Option<String>`, con el UUID dentro del Some si el documento tiene Complemento
+    pub fn get_uuid(&self) -> Option<String> {
+        self.timbre_fiscal_digital().map(|tfd| tfd.uuid.clone())
+    }
+
+    /// Regresa un `Option<NaiveDateTime>`, con la fecha de timbrado dentro del Some si el documento tiene Complemento
+    pub fn get_fecha_timbrado(&self) -> Option<NaiveDateTime> {
+        self.timbre_fiscal_digital().map(|tfd| tfd.fecha_timbrado)
+    }
+
+    /// Timbre fiscal del documento, si ya fue timbrado
+    pub fn timbre_fiscal_digital(&self) -> Option<&TimbreFiscalDigital> {
+        self.complemento.as_ref()?.timbre_fiscal_digital.as_ref()
+    }
+}
+
+/// CFDI de retenciones que sustituye este documento
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CfdiRetenRelacionados {
+    /// Clave del tipo de relación -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoRelacion")]
+    pub tipo_relacion: String,
+
+    #[serde(rename = "@UUID")]
+    pub uuid: String,
+}
+
+/// Quien hizo las retenciones
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Emisor {
+    #[serde(rename = "@RfcE")]
+    pub rfc: Rfc,
+
+    /// Nombre, denominación o razón social
+    #[serde(rename = "@NomDenRazSocE")]
+    pub nombre: String,
+
+    #[serde(rename = "@RegimenFiscalE")]
+    pub regimen_fiscal: RegimenFiscal,
+}
+
+/// A quien se le hicieron las retenciones. Según `nacionalidad`, trae los datos de
+/// `nacional` o de `extranjero`.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Receptor {
+    /// "Nacional" o "Extranjero"
+    #[serde(rename = "@NacionalidadR")]
+    pub nacionalidad: String,
+
+    #[serde(rename = "Nacional")]
+    pub nacional: Option<Nacional>,
+
+    #[serde(rename = "Extranjero")]
+    pub extranjero: Option<Extranjero>,
+}
+
+impl Receptor {
+    /// RFC del receptor, solo si es nacional
+    pub fn rfc(&self) -> Option<&Rfc> {
+        self.nacional.as_ref().map(|n| &n.rfc)
+    }
+
+    /// Nombre, denominación o razón social del receptor, nacional o extranjero
+    pub fn nombre(&self) -> Option<&str> {
+        match (&self.nacional, &self.extranjero) {
+            (Some(nacional), _) => Some(&nacional.nombre),
+            (_, Some(extranjero)) => Some(&extranjero.nombre),
+            _ => None,
+        }
+    }
+}
+
+/// Receptor residente en México
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Nacional {
+    #[serde(rename = "@RfcR")]
+    pub rfc: Rfc,
+
+    #[serde(rename = "@NomDenRazSocR")]
+    pub nombre: String,
+
+    #[serde(rename = "@CurpR")]
+    pub curp: Option<String>,
+
+    /// Código postal del domicilio fiscal
+    #[serde(rename = "@DomicilioFiscalR")]
+    pub domicilio_fiscal: String,
+}
+
+/// Receptor residente en el extranjero
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Extranjero {
+    /// Número de registro de identidad fiscal
+    #[serde(rename = "@NumRegIdTribR")]
+    pub num_reg_id_trib: Option<String>,
+
+    #[serde(rename = "@NomDenRazSocR")]
+    pub nombre: String,
+}
+
+/// Periodo (meses de un ejercicio) que ampara el documento
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Periodo {
+    /// Mes inicial, "01" a "12"
+    #[serde(rename = "@MesIni")]
+    pub mes_ini: String,
+
+    /// Mes final, "01" a "12"
+    #[serde(rename = "@MesFin")]
+    pub mes_fin: String,
+
+    /// Año del ejercicio fiscal
+    #[serde(rename = "@Ejercicio")]
+    pub ejercicio: String,
+}
+
+/// Montos totales de las operaciones y de las retenciones
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Totales {
+    #[serde(rename = "@MontoTotOperacion")]
+    pub monto_tot_operacion: Decimal,
+
+    /// Monto total gravado
+    #[serde(rename = "@MontoTotGrav")]
+    pub monto_tot_grav: Decimal,
+
+    /// Monto total exento
+    #[serde(rename = "@MontoTotExent")]
+    pub monto_tot_exent: Decimal,
+
+    /// Monto total retenido, suma de `imp_retenidos`
+    #[serde(rename = "@MontoTotRet")]
+    pub monto_tot_ret: Decimal,
+
+    /// Solo para la retención por pago de dividendos de sociedades cooperativas
+    #[serde(rename = "@UtilidadBimestral")]
+    pub utilidad_bimestral: Option<Decimal>,
+
+    #[serde(rename = "@ISRCorrespondiente")]
+    pub isr_correspondiente: Option<Decimal>,
+
+    #[serde(rename = "ImpRetenidos", default)]
+    pub imp_retenidos: Vec<ImpRetenidos>,
+}
+
+/// Impuesto retenido
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpRetenidos {
+    #[serde(rename = "@BaseRet")]
+    pub base: Option<Decimal>,
+
+    #[serde(rename = "@ImpuestoRet")]
+    pub impuesto: Option<Impuesto>,
+
+    #[serde(rename = "@MontoRet")]
+    pub monto: Decimal,
+
+    /// Si el impuesto retenido es pago definitivo o provisional -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoPagoRet")]
+    pub tipo_pago: String,
+}
+
+/// Complementos del CFDI de retenciones. Incluye Timbre Fiscal (si se encuentra)
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Complemento {
+    #[serde(rename = "TimbreFiscalDigital")]
+    pub timbre_fiscal_digital: Option<TimbreFiscalDigital>,
+
+    #[serde(rename = "Dividendos")]
+    pub dividendos: Option<Dividendos>,
+
+    #[serde(rename = "Intereses")]
+    pub intereses: Option<Intereses>,
+
+    #[serde(rename = "EnajenaciondeAcciones")]
+    pub enajenacion_de_acciones: Option<EnajenaciondeAcciones>,
+
+    #[serde(rename = "ServiciosPlataformasTecnologicas")]
+    pub plataformas_tecnologicas: Option<ServiciosPlataformasTecnologicas>,
+}
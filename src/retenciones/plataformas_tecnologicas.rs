@@ -0,0 +1,148 @@
+//! Complemento de Servicios de Plataformas Tecnológicas 2.0
+//! (`plataformasTecnologicas:ServiciosPlataformasTecnologicas`), para las retenciones a
+//! quienes prestan servicios u ofrecen bienes por medio de plataformas digitales.
+//!
+//! ```markdown
+//!|-ServiciosPlataformasTecnologicas
+//!     |-- Servicios
+//!          |-- DetallesDelServicio (1..n)
+//!               |-- ImpuestosTrasladadosdelServicio
+//!               |-- ContribucionGubernamental (opcional)
+//!               |-- ComisionDelServicio (opcional)
+//! ```
+
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Nodo principal del complemento de plataformas tecnológicas
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServiciosPlataformasTecnologicas {
+    /// Versión del complemento, "2.0"
+    #[serde(rename = "@Version")]
+    pub version: String,
+
+    /// Periodicidad de las retenciones -- Ver Catálogos en SAT
+    #[serde(rename = "@Periodicidad")]
+    pub periodicidad: String,
+
+    /// Número de servicios prestados en el periodo
+    #[serde(rename = "@NumServ")]
+    pub num_serv: u32,
+
+    /// Monto total de los servicios, sin IVA
+    #[serde(rename = "@MonTotServSIVA")]
+    pub mon_tot_serv_siva: Decimal,
+
+    #[serde(rename = "@TotalIVATrasladado")]
+    pub total_iva_trasladado: Decimal,
+
+    #[serde(rename = "@TotalIVARetenido")]
+    pub total_iva_retenido: Decimal,
+
+    #[serde(rename = "@TotalISRRetenido")]
+    pub total_isr_retenido: Decimal,
+
+    /// Diferencia entre el IVA trasladado y el IVA retenido que se entrega al prestador
+    #[serde(rename = "@DifIVAEntregadoPrestServ")]
+    pub dif_iva_entregado_prest_serv: Decimal,
+
+    /// Monto total que cobra la plataforma por su uso
+    #[serde(rename = "@MonTotalporUsoPlataforma")]
+    pub mon_total_por_uso_plataforma: Decimal,
+
+    #[serde(rename = "@MonTotalContribucionGubernamental")]
+    pub mon_total_contribucion_gubernamental: Option<Decimal>,
+
+    #[serde(rename = "Servicios")]
+    pub servicios: Servicios,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Servicios {
+    #[serde(rename = "DetallesDelServicio")]
+    pub detalles_del_servicio: Vec<DetallesDelServicio>,
+}
+
+/// Un servicio prestado por medio de la plataforma
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DetallesDelServicio {
+    /// Forma de pago del servicio -- Ver Catálogos en SAT
+    #[serde(rename = "@FormaPagoServ")]
+    pub forma_pago_serv: String,
+
+    /// Tipo de servicio -- Ver Catálogos en SAT
+    #[serde(rename = "@TipoDeServ")]
+    pub tipo_de_serv: String,
+
+    /// Subtipo de servicio -- Ver Catálogos en SAT
+    #[serde(rename = "@SubTipServ")]
+    pub sub_tip_serv: Option<String>,
+
+    /// RFC del tercero autorizado, si el servicio se prestó por medio de uno
+    #[serde(rename = "@RFCTerceroAutorizado")]
+    pub rfc_tercero_autorizado: Option<String>,
+
+    /// Fecha del servicio, como `AAAA-MM-DD`
+    #[serde(rename = "@FechaServ")]
+    pub fecha_serv: String,
+
+    #[serde(rename = "@PrecioServSinIVA")]
+    pub precio_serv_sin_iva: Decimal,
+
+    #[serde(rename = "ImpuestosTrasladadosdelServicio")]
+    pub impuestos_trasladados: ImpuestosTrasladadosdelServicio,
+
+    #[serde(rename = "ContribucionGubernamental")]
+    pub contribucion_gubernamental: Option<ContribucionGubernamental>,
+
+    #[serde(rename = "ComisionDelServicio")]
+    pub comision_del_servicio: Option<ComisionDelServicio>,
+}
+
+/// IVA trasladado por el servicio
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpuestosTrasladadosdelServicio {
+    #[serde(rename = "@Base")]
+    pub base: Decimal,
+
+    /// Clave del impuesto, "002" (IVA)
+    #[serde(rename = "@Impuesto")]
+    pub impuesto: String,
+
+    #[serde(rename = "@TipoFactor")]
+    pub tipo_factor: String,
+
+    #[serde(rename = "@TasaCuota")]
+    pub tasa_cuota: Decimal,
+
+    #[serde(rename = "@Importe")]
+    pub importe: Decimal,
+}
+
+/// Contribución gubernamental pagada por el servicio
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContribucionGubernamental {
+    #[serde(rename = "@ImpContrib")]
+    pub imp_contrib: Decimal,
+
+    /// Clave de la entidad federativa -- Ver Catálogos en SAT
+    #[serde(rename = "@EntidadDondePagaLaContribucion")]
+    pub entidad_donde_paga_la_contribucion: String,
+}
+
+/// Comisión que cobra la plataforma por el servicio
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ComisionDelServicio {
+    #[serde(rename = "@Base")]
+    pub base: Option<Decimal>,
+
+    #[serde(rename = "@Porcentaje")]
+    pub porcentaje: Option<Decimal>,
+
+    #[serde(rename = "@Importe")]
+    pub importe: Option<Decimal>,
+}
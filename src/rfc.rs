@@ -0,0 +1,242 @@
+//! Registro Federal de Contribuyentes.
+//!
+//! [`Rfc`] guarda la clave tal cual viene en el xml, aunque no sea válida, para poder leer
+//! cualquier comprobante. La validación se hace con [`Rfc::validar`], o al crearlo con
+//! `parse()`:
+//!
+//! ```rust
+//! use cfdi::rfc::{Rfc, TipoPersona};
+//!
+//! let rfc: Rfc = "EKU9003173C9".parse().unwrap();
+//! assert_eq!(rfc.tipo_persona(), Some(TipoPersona::Moral));
+//! assert_eq!(rfc.fecha(), Some((1990, 3, 17)));
+//!
+//! assert!("EKU9003173C8".parse::<Rfc>().is_err());
+//! assert!(Rfc::new("XAXX010101000").es_generico());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// RFC genérico para operaciones con el público en general
+pub const GENERICO_NACIONAL: &str = "XAXX010101000";
+
+/// RFC genérico para operaciones con residentes en el extranjero
+pub const GENERICO_EXTRANJERO: &str = "XEXX010101000";
+
+/// Valores de cada caracter para el cálculo del dígito verificador
+const VALORES: &str = "0123456789ABCDEFGHIJKLMN&OPQRSTUVWXYZ Ñ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TipoPersona {
+    /// RFC de 13 caracteres
+    Fisica,
+    /// RFC de 12 caracteres
+    Moral,
+}
+
+/// Motivo por el que un RFC no es válido
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorRfc {
+    /// No tiene 12 (persona moral) ni 13 (persona física) caracteres
+    Longitud(usize),
+    /// No cumple con el patrón de letras, fecha y homoclave
+    Formato,
+    /// La fecha (AAMMDD) no existe
+    Fecha,
+    /// El último caracter no es el dígito verificador de los anteriores
+    DigitoVerificador { esperado: char, encontrado: char },
+}
+
+impl fmt::Display for ErrorRfc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorRfc::Longitud(n) => write!(f, "El RFC debe tener 12 o 13 caracteres, tiene {}", n),
+            ErrorRfc::Formato => write!(f, "El RFC no cumple con el patrón requerido"),
+            ErrorRfc::Fecha => write!(f, "La fecha del RFC no es válida"),
+            ErrorRfc::DigitoVerificador {
+                esperado,
+                encontrado,
+            } => write!(
+                f,
+                "El dígito verificador del RFC debe ser {} y es {}",
+                esperado, encontrado
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorRfc {}
+
+/// Clave del RFC de un contribuyente
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rfc(String);
+
+impl Rfc {
+    /// Crea el RFC sin validarlo
+    pub fn new(rfc: impl Into<String>) -> Self {
+        Rfc(rfc.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// `true` si es alguno de los RFC genéricos (XAXX010101000 o XEXX010101000)
+    pub fn es_generico(&self) -> bool {
+        self.0 == GENERICO_NACIONAL || self.0 == GENERICO_EXTRANJERO
+    }
+
+    /// Persona física o moral, según la longitud del RFC. `None` si la longitud no es válida
+    pub fn tipo_persona(&self) -> Option<TipoPersona> {
+        match self.0.chars().count() {
+            12 => Some(TipoPersona::Moral),
+            13 => Some(TipoPersona::Fisica),
+            _ => None,
+        }
+    }
+
+    /// Fecha de nacimiento o constitución (año, mes, día) incluida en el RFC.
+    ///
+    /// El RFC solo incluye 2 dígitos del año: los años posteriores al actual se consideran
+    /// del siglo pasado. `None` para los RFC genéricos o si la fecha no es válida.
+    pub fn fecha(&self) -> Option<(u16, u8, u8)> {
+        if self.es_generico() {
+            return None;
+        }
+        let fecha = self.fecha_completa()?;
+        Some((fecha.year() as u16, fecha.month() as u8, fecha.day() as u8))
+    }
+
+    /// Dígito verificador que le corresponde a los primeros 11 (o 12) caracteres del RFC
+    pub fn digito_verificador(&self) -> Option<char> {
+        let caracteres: Vec<char> = self.0.chars().collect();
+        let clave = match caracteres.len() {
+            12 => std::iter::once(' ')
+                .chain(caracteres[..11].iter().copied())
+                .collect::<Vec<_>>(),
+            13 => caracteres[..12].to_vec(),
+            _ => return None,
+        };
+        let mut suma = 0;
+        for (i, c) in clave.iter().enumerate() {
+            suma += VALORES.chars().position(|v| v == *c)? * (13 - i);
+        }
+        Some(match 11 - suma % 11 {
+            11 => '0',
+            10 => 'A',
+            n => char::from_digit(n as u32, 10)?,
+        })
+    }
+
+    /// Valida el formato, la fecha y el dígito verificador. Los RFC genéricos son válidos.
+    pub fn validar(&self) -> Result<(), ErrorRfc> {
+        if self.es_generico() {
+            return Ok(());
+        }
+        let caracteres: Vec<char> = self.0.chars().collect();
+        let letras = match caracteres.len() {
+            12 => 3,
+            13 => 4,
+            n => return Err(ErrorRfc::Longitud(n)),
+        };
+        let (nombre, resto) = caracteres.split_at(letras);
+        let (fecha, homoclave) = resto.split_at(6);
+        let formato = nombre
+            .iter()
+            .all(|c| c.is_ascii_uppercase() || *c == '&' || *c == 'Ñ')
+            && fecha.iter().all(char::is_ascii_digit)
+            && homoclave[..2]
+                .iter()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && (homoclave[2].is_ascii_digit() || homoclave[2] == 'A');
+        if !formato {
+            return Err(ErrorRfc::Formato);
+        }
+        if self.fecha_completa().is_none() {
+            return Err(ErrorRfc::Fecha);
+        }
+        let esperado = self.digito_verificador().ok_or(ErrorRfc::Formato)?;
+        if esperado != homoclave[2] {
+            return Err(ErrorRfc::DigitoVerificador {
+                esperado,
+                encontrado: homoclave[2],
+            });
+        }
+        Ok(())
+    }
+
+    pub fn es_valido(&self) -> bool {
+        self.validar().is_ok()
+    }
+
+    /// Fecha AAMMDD del RFC, con el año completo. `None` si no es una fecha válida
+    fn fecha_completa(&self) -> Option<NaiveDate> {
+        let letras = match self.tipo_persona()? {
+            TipoPersona::Moral => 3,
+            TipoPersona::Fisica => 4,
+        };
+        let fecha: String = self.0.chars().skip(letras).take(6).collect();
+        let numero = |i: usize| fecha.get(i..i + 2)?.parse::<u32>().ok();
+        let (aa, mm, dd) = (numero(0)?, numero(2)?, numero(4)?);
+
+        let actual = anio_actual() as u32 % 100;
+        let anio = if aa > actual { 1900 + aa } else { 2000 + aa };
+        NaiveDate::from_ymd_opt(anio as i32, mm, dd)
+    }
+}
+
+fn anio_actual() -> u16 {
+    let segundos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    // Año gregoriano promedio: 365.2425 días
+    (1970 + segundos / 31_556_952) as u16
+}
+
+/// Crea el RFC validándolo
+impl FromStr for Rfc {
+    type Err = ErrorRfc;
+
+    fn from_str(rfc: &str) -> Result<Self, Self::Err> {
+        let rfc = Rfc::new(rfc);
+        rfc.validar()?;
+        Ok(rfc)
+    }
+}
+
+impl fmt::Display for Rfc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for Rfc {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Rfc {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Serialize for Rfc {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Se lee sin validar, para no rechazar comprobantes con un RFC mal formado
+impl<'de> Deserialize<'de> for Rfc {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Rfc(String::deserialize(deserializer)?))
+    }
+}
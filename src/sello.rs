@@ -0,0 +1,397 @@
+//! Sello digital del comprobante con el Certificado de Sello Digital (CSD) del emisor.
+//!
+//! El SAT entrega el CSD como 2 archivos: el certificado (`.cer`, X.509 en DER) y la llave
+//! privada (`.key`, PKCS#8 en DER cifrado con la contraseña del CSD). El sello es la firma
+//! SHA256withRSA de la cadena original, en base64.
+//!
+//! ```rust,no_run
+//! use cfdi::sello::{Certificado, LlavePrivada};
+//!
+//! let certificado = Certificado::from_der(&std::fs::read("csd.cer").unwrap()).unwrap();
+//! let llave = LlavePrivada::from_der(&std::fs::read("csd.key").unwrap(), "contraseña").unwrap();
+//!
+//! let xml = std::fs::read_to_string("sin_sellar.xml").unwrap();
+//! let mut cfdi = cfdi::parse_cfdi(&xml).unwrap();
+//! cfdi.sellar(&certificado, &llave).unwrap();
+//!
+//! std::fs::write("sellado.xml", cfdi.to_xml().unwrap()).unwrap();
+//! ```
+//!
+//! Para comprobantes recibidos, [`verificar`] revisa que el sello corresponda al contenido
+//! del xml y al certificado que trae incluido:
+//!
+//! ```rust,no_run
+//! use cfdi::sello::{verificar, Verificacion};
+//!
+//! let xml = std::fs::read_to_string("proveedor.xml").unwrap();
+//! match verificar(&xml).unwrap() {
+//!     Verificacion::Valido => println!("Sello válido"),
+//!     otro => println!("Rechazado: {:?}", otro),
+//! }
+//! ```
+//!
+//! El sello del SAT en el [`TimbreFiscalDigital`](crate::TimbreFiscalDigital) se verifica con
+//! [`Comprobante::verificar_timbre`], usando los certificados del SAT que se tengan
+//! descargados localmente ([`CertificadosSat`]):
+//!
+//! ```rust,no_run
+//! use cfdi::sello::{CertificadosSat, VerificacionTimbre};
+//!
+//! let certificados = CertificadosSat::leer_directorio("certificados_sat/").unwrap();
+//!
+//! let xml = std::fs::read_to_string("proveedor.xml").unwrap();
+//! let cfdi = cfdi::parse_cfdi(&xml).unwrap();
+//! assert_eq!(cfdi.verificar_timbre(&certificados).unwrap(), VerificacionTimbre::Valido);
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::sha2::Sha256;
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::der::{Decode, Encode};
+
+use crate::cadena::complemento_no_soportado;
+use crate::{cadena_original, parse_cfdi, Comprobante};
+
+/// x500UniqueIdentifier, donde el SAT pone el RFC del titular del certificado
+const OID_RFC: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.45");
+
+/// Certificado de Sello Digital (archivo `.cer`)
+#[derive(Debug, Clone)]
+pub struct Certificado {
+    der: Vec<u8>,
+    certificado: x509_cert::Certificate,
+}
+
+impl Certificado {
+    /// Lee el certificado en formato DER, tal como viene en el archivo `.cer`
+    pub fn from_der(der: &[u8]) -> Result<Self> {
+        let certificado = x509_cert::Certificate::from_der(der)
+            .map_err(|e| anyhow!("No se pudo leer el certificado: {}", e))?;
+        Ok(Certificado {
+            der: der.to_vec(),
+            certificado,
+        })
+    }
+
+    /// Lee el certificado en base64, como viene en el atributo `Certificado` de un CFDI
+    pub fn from_base64(base64: &str) -> Result<Self> {
+        let der = STANDARD
+            .decode(base64.trim())
+            .map_err(|e| anyhow!("El certificado no es base64 válido: {}", e))?;
+        Self::from_der(&der)
+    }
+
+    /// Número de certificado (ej. "30001000000500003416").
+    ///
+    /// El SAT lo codifica en el número de serie del certificado, un dígito por byte en ASCII.
+    pub fn no_certificado(&self) -> String {
+        let serie = self.certificado.tbs_certificate.serial_number.as_bytes();
+        if serie.iter().all(u8::is_ascii_digit) {
+            serie.iter().map(|b| *b as char).collect()
+        } else {
+            serie.iter().map(|b| format!("{:02X}", b)).collect()
+        }
+    }
+
+    /// Certificado en base64, para el atributo `Certificado` del comprobante
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(&self.der)
+    }
+
+    /// RFC del titular del certificado.
+    ///
+    /// En los certificados de personas morales el SAT incluye también el RFC del representante
+    /// legal (ej. `"EKU9003173C9 / VADA800927DJ3"`); aquí solo se regresa el primero.
+    pub fn rfc(&self) -> Option<String> {
+        let valor = self
+            .certificado
+            .tbs_certificate
+            .subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .find(|atributo| atributo.oid == OID_RFC)?
+            .value
+            .value();
+        let valor = std::str::from_utf8(valor).ok()?;
+        valor.split('/').next().map(|rfc| rfc.trim().to_string())
+    }
+
+    /// Inicio y fin de la vigencia del certificado
+    pub fn vigencia(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        let validez = &self.certificado.tbs_certificate.validity;
+        let utc = |tiempo: x509_cert::time::Time| {
+            let segundos = tiempo.to_unix_duration().as_secs() as i64;
+            DateTime::from_timestamp(segundos, 0).unwrap_or_default()
+        };
+        (utc(validez.not_before), utc(validez.not_after))
+    }
+
+    /// `true` si `sello` (en base64) es la firma SHA256withRSA de `cadena_original` hecha
+    /// con la llave privada de este certificado
+    pub fn verificar(&self, cadena_original: &str, sello: &str) -> bool {
+        let Ok(llave) = self.llave_publica() else {
+            return false;
+        };
+        let Ok(firma) = STANDARD.decode(sello.trim()) else {
+            return false;
+        };
+        let Ok(firma) = Signature::try_from(firma.as_slice()) else {
+            return false;
+        };
+        VerifyingKey::<Sha256>::new(llave)
+            .verify(cadena_original.as_bytes(), &firma)
+            .is_ok()
+    }
+
+    pub(crate) fn llave_publica(&self) -> Result<RsaPublicKey> {
+        let spki = self
+            .certificado
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .map_err(|e| anyhow!("No se pudo leer la llave pública del certificado: {}", e))?;
+        RsaPublicKey::from_public_key_der(&spki)
+            .map_err(|e| anyhow!("La llave pública del certificado no es RSA: {}", e))
+    }
+}
+
+/// Llave privada del Certificado de Sello Digital (archivo `.key`)
+#[derive(Debug, Clone)]
+pub struct LlavePrivada {
+    llave: RsaPrivateKey,
+}
+
+impl LlavePrivada {
+    /// Lee la llave privada cifrada (PKCS#8 en DER, tal como viene en el archivo `.key`)
+    /// usando la contraseña del CSD
+    pub fn from_der(der: &[u8], contrasena: &str) -> Result<Self> {
+        let llave = RsaPrivateKey::from_pkcs8_encrypted_der(der, contrasena).map_err(|e| {
+            anyhow!(
+                "No se pudo leer la llave privada (¿contraseña incorrecta?): {}",
+                e
+            )
+        })?;
+        Ok(LlavePrivada { llave })
+    }
+
+    /// Firma la cadena original con SHA256withRSA y regresa el sello en base64
+    pub fn sellar(&self, cadena_original: &str) -> String {
+        let firma = SigningKey::<Sha256>::new(self.llave.clone()).sign(cadena_original.as_bytes());
+        STANDARD.encode(firma.to_bytes())
+    }
+
+    /// `true` si la llave corresponde al certificado
+    pub fn corresponde_a(&self, certificado: &Certificado) -> bool {
+        certificado
+            .llave_publica()
+            .is_ok_and(|publica| publica == RsaPublicKey::from(&self.llave))
+    }
+}
+
+impl Comprobante {
+    /// Sella el comprobante: llena `NoCertificado` y `Certificado` con los datos del CSD,
+    /// calcula la cadena original y guarda su firma en `Sello`.
+    ///
+    /// Cualquier cambio posterior al comprobante invalida el sello, por lo que debe ser
+    /// lo último que se haga antes de generar el xml con [`Comprobante::to_xml`].
+    pub fn sellar(&mut self, certificado: &Certificado, llave: &LlavePrivada) -> Result<()> {
+        if !llave.corresponde_a(certificado) {
+            bail!("La llave privada no corresponde al certificado");
+        }
+        self.no_certificado = Some(certificado.no_certificado());
+        self.certificado = Some(certificado.to_base64());
+        self.sello = Some(llave.sellar(&self.cadena_original()?));
+        Ok(())
+    }
+}
+
+/// Certificados del SAT con los que se firman los timbres, indexados por su número de
+/// certificado (el `NoCertificadoSAT` del timbre).
+///
+/// El SAT publica sus certificados en su portal; este crate no los descarga, cada quien
+/// mantiene su copia local.
+#[derive(Debug, Clone, Default)]
+pub struct CertificadosSat {
+    certificados: HashMap<String, Certificado>,
+}
+
+impl CertificadosSat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lee todos los archivos `.cer` de un directorio
+    pub fn leer_directorio(directorio: impl AsRef<Path>) -> Result<Self> {
+        let mut certificados = Self::new();
+        for archivo in std::fs::read_dir(directorio)? {
+            let ruta = archivo?.path();
+            let es_cer = ruta
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("cer"));
+            if ruta.is_file() && es_cer {
+                let certificado = Certificado::from_der(&std::fs::read(&ruta)?)
+                    .map_err(|e| anyhow!("{}: {}", ruta.display(), e))?;
+                certificados.agregar(certificado);
+            }
+        }
+        Ok(certificados)
+    }
+
+    pub fn agregar(&mut self, certificado: Certificado) {
+        self.certificados
+            .insert(certificado.no_certificado(), certificado);
+    }
+
+    /// Certificado con el número `no_certificado`, si se tiene
+    pub fn get(&self, no_certificado: &str) -> Option<&Certificado> {
+        self.certificados.get(no_certificado)
+    }
+}
+
+/// Resultado de verificar el timbre de un comprobante con [`Comprobante::verificar_timbre`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificacionTimbre {
+    /// El `SelloSAT` es válido y el timbre corresponde al sello del comprobante
+    Valido,
+
+    /// No se tiene el certificado del SAT con el que se firmó el timbre
+    CertificadoSatDesconocido { no_certificado_sat: String },
+
+    /// El `SelloSAT` no corresponde a la cadena original del timbre
+    SelloSatInvalido,
+
+    /// El `SelloCFD` del timbre no es el `Sello` del comprobante: el timbre es de otro
+    /// comprobante, o el comprobante se volvió a sellar después de timbrarse
+    SelloCfdNoCorresponde,
+}
+
+impl Comprobante {
+    /// Verifica el [`TimbreFiscalDigital`](crate::TimbreFiscalDigital): que el `SelloSAT` sea
+    /// la firma de la cadena original del timbre con el certificado `NoCertificadoSAT`, y que
+    /// el `SelloCFD` sea el `Sello` del comprobante.
+    ///
+    /// Regresa error si el comprobante no está timbrado.
+    pub fn verificar_timbre(&self, certificados: &CertificadosSat) -> Result<VerificacionTimbre> {
+        let timbre = self
+            .complemento
+            .as_ref()
+            .and_then(|c| c.timbre_fiscal_digital.as_ref())
+            .ok_or_else(|| anyhow!("El comprobante no tiene TimbreFiscalDigital"))?;
+
+        let Some(certificado) = certificados.get(&timbre.no_certificado_sat) else {
+            return Ok(VerificacionTimbre::CertificadoSatDesconocido {
+                no_certificado_sat: timbre.no_certificado_sat.clone(),
+            });
+        };
+        if !certificado.verificar(&timbre.cadena_original(), &timbre.sello_sat) {
+            return Ok(VerificacionTimbre::SelloSatInvalido);
+        }
+        if self.sello.as_deref() != Some(timbre.sello_cfd.as_str()) {
+            return Ok(VerificacionTimbre::SelloCfdNoCorresponde);
+        }
+        Ok(VerificacionTimbre::Valido)
+    }
+}
+
+/// Resultado de verificar el sello de un comprobante con [`verificar`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verificacion {
+    /// El sello corresponde a la cadena original y al certificado del emisor
+    Valido,
+
+    /// El sello no corresponde a la cadena original: el xml fue modificado después de sellarse,
+    /// o se selló con otra llave
+    SelloInvalido,
+
+    /// El comprobante tiene un complemento que no se incluye en la cadena original (ej.
+    /// `ImpuestosLocales`), así que no se puede saber si el sello es válido
+    ComplementoNoSoportado(String),
+
+    /// El `NoCertificado` del comprobante no es el del certificado incluido
+    CertificadoNoCorresponde {
+        no_certificado: String,
+        certificado: String,
+    },
+
+    /// El RFC del emisor no es el del titular del certificado
+    RfcNoCorresponde { emisor: String, certificado: String },
+
+    /// El certificado no estaba vigente en la `Fecha` del comprobante
+    CertificadoVencido {
+        /// `Fecha` del comprobante, en la zona horaria del `LugarExpedicion`
+        fecha: DateTime<Tz>,
+        inicio: DateTime<Utc>,
+        fin: DateTime<Utc>,
+    },
+}
+
+/// Verifica el sello del emisor de un comprobante recibido.
+///
+/// Lee el certificado incluido en el atributo `Certificado`, calcula la cadena original del
+/// xml (tal cual viene escrito) y revisa que el `Sello` sea su firma. Después revisa que el
+/// certificado sea el del `NoCertificado`, que pertenezca al RFC del emisor y que estuviera
+/// vigente en la `Fecha` del comprobante. Se regresa el primer problema encontrado.
+///
+/// Si el comprobante tiene complementos que no se incluyen en la cadena original (ver
+/// [`cadena_original`]) no se revisa el sello y se regresa
+/// [`Verificacion::ComplementoNoSoportado`].
+///
+/// Regresa error si el xml no se puede leer, o si no tiene `Sello` o `Certificado`.
+///
+/// La `Fecha` se compara en la zona horaria del `LugarExpedicion` (ver
+/// [`Comprobante::fecha_con_zona`]).
+pub fn verificar(xml: &str) -> Result<Verificacion> {
+    let cfdi = parse_cfdi(xml)?;
+    let sello = cfdi
+        .sello
+        .as_deref()
+        .ok_or_else(|| anyhow!("El comprobante no tiene Sello"))?;
+    let certificado = cfdi
+        .certificado
+        .as_deref()
+        .ok_or_else(|| anyhow!("El comprobante no tiene Certificado"))?;
+    let certificado = Certificado::from_base64(certificado)?;
+
+    if let Some(complemento) = complemento_no_soportado(xml)? {
+        return Ok(Verificacion::ComplementoNoSoportado(complemento));
+    }
+    if !certificado.verificar(&cadena_original(xml)?, sello) {
+        return Ok(Verificacion::SelloInvalido);
+    }
+
+    let no_certificado = certificado.no_certificado();
+    if cfdi.no_certificado.as_deref() != Some(no_certificado.as_str()) {
+        return Ok(Verificacion::CertificadoNoCorresponde {
+            no_certificado: cfdi.no_certificado.unwrap_or_default(),
+            certificado: no_certificado,
+        });
+    }
+
+    let rfc = certificado.rfc().unwrap_or_default();
+    if !rfc.eq_ignore_ascii_case(cfdi.emisor.rfc.as_str()) {
+        return Ok(Verificacion::RfcNoCorresponde {
+            emisor: cfdi.emisor.rfc.to_string(),
+            certificado: rfc,
+        });
+    }
+
+    let (inicio, fin) = certificado.vigencia();
+    let fecha = cfdi.fecha_con_zona();
+    if fecha < inicio || fecha > fin {
+        return Ok(Verificacion::CertificadoVencido { fecha, inicio, fin });
+    }
+
+    Ok(Verificacion::Valido)
+}
@@ -0,0 +1,462 @@
+//! Cálculo de los totales del comprobante a partir de sus conceptos.
+//!
+//! [`Comprobante::recalculate_totals`] calcula el `SubTotal`, el `Descuento`, los impuestos y
+//! el `Total` con los importes de los conceptos, como lo indica el Anexo 20, y regresa una
+//! [`Diferencia`] por cada valor declarado que no coincide con el calculado, sin modificar el
+//! comprobante. [`Comprobante::update_totals`] además reemplaza los valores del comprobante con
+//! los calculados (ej. después de modificar sus conceptos).
+//!
+//! - El `SubTotal` es la suma de los `Importe` de los conceptos, y el `Descuento` la suma de
+//!   sus descuentos. Si ningún concepto tiene descuento, se conserva un `Descuento` en cero.
+//! - Los traslados se agrupan por `Impuesto`, `TipoFactor` y `TasaOCuota`, sumando su `Base` e
+//!   `Importe`, y las retenciones por `Impuesto`. `TotalImpuestosTrasladados` y
+//!   `TotalImpuestosRetenidos` son la suma de cada grupo.
+//! - El `Total` es `SubTotal` - `Descuento` + trasladados - retenidos. Si el comprobante tiene
+//!   el complemento de impuestos locales (`implocal:ImpuestosLocales`, que se conserva como xml
+//!   en [`Complemento::otros`](crate::Complemento::otros)), también se suma su
+//!   `TotaldeTraslados` y se resta su `TotaldeRetenciones`, tal como se declaran.
+//!
+//! Las sumas se redondean a los decimales de la moneda (c_Moneda), la mitad hacia arriba, y los
+//! valores del comprobante se comparan exactamente con las sumas redondeadas, como en
+//! [`validacion`](crate::validacion).
+//!
+//! Los importes de los conceptos no se modifican, pero se revisan con la tolerancia que permite
+//! el Anexo 20 (ver [`validacion`](crate::validacion)): el `Importe` del concepto contra
+//! `Cantidad` x `ValorUnitario`, y el de cada impuesto contra `Base` x `TasaOCuota`. Si quedan
+//! fuera de los límites, la diferencia trae el producto redondeado a los decimales del importe
+//! declarado.
+//!
+//! En comprobantes 3.3 los traslados del comprobante no llevan `Base` y no incluyen los
+//! exentos. Los conceptos de los comprobantes 3.2 no tienen impuestos, por lo que se conservan
+//! los del comprobante y solo se recalculan el `SubTotal`, el `Descuento` y el `Total`.
+//!
+//! ```rust
+//! # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+//! #     Fecha="2024-01-15T10:00:00" FormaPago="03" SubTotal="1000.00" Moneda="MXN"
+//! #     Total="1000.00" TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE"
+//! #     LugarExpedicion="44100">
+//! #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//! #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+//! #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+//! #   <cfdi:Conceptos>
+//! #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+//! #         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="02">
+//! #       <cfdi:Impuestos>
+//! #         <cfdi:Traslados>
+//! #           <cfdi:Traslado Base="1000.00" Impuesto="002" TipoFactor="Tasa"
+//! #               TasaOCuota="0.160000" Importe="160.00"/>
+//! #         </cfdi:Traslados>
+//! #       </cfdi:Impuestos>
+//! #     </cfdi:Concepto>
+//! #   </cfdi:Conceptos>
+//! # </cfdi:Comprobante>"#;
+//! let mut cfdi = cfdi::parse_cfdi(xml).unwrap();
+//! assert!(cfdi.impuestos.is_none());
+//!
+//! let diferencias = cfdi.recalculate_totals();
+//! for diferencia in &diferencias {
+//!     println!("{}", diferencia);
+//! }
+//! assert_eq!(
+//!     diferencias[0].to_string(),
+//!     "Comprobante (Total): declarado 1000.00, calculado 1160.00"
+//! );
+//! assert_eq!(cfdi.total.to_string(), "1000.00");
+//!
+//! assert_eq!(cfdi.update_totals(), diferencias);
+//! assert_eq!(cfdi.total.to_string(), "1160.00");
+//! assert_eq!(
+//!     cfdi.impuestos.unwrap().total_impuestos_trasladados.unwrap().to_string(),
+//!     "160.00"
+//! );
+//! ```
+
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rust_decimal::RoundingStrategy;
+
+use crate::catalogos::{Moneda, TipoFactor};
+use crate::validacion::{limites, limites_impuesto};
+use crate::{
+    Comprobante, Concepto, Decimal, Impuestos, Retencion, Retenciones, Traslado, Traslados,
+};
+
+/// Valor del comprobante distinto del calculado con [`Comprobante::recalculate_totals`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diferencia {
+    /// Ruta del nodo (ej. `"Comprobante/Impuestos/Traslados/Traslado[1]"`). En los impuestos
+    /// del comprobante es la del nodo declarado, o la del calculado si no se había declarado.
+    pub ruta: String,
+    /// Atributo con la diferencia (ej. `"Importe"`)
+    pub atributo: &'static str,
+    /// Valor en el comprobante. `None` si no tenía el atributo
+    pub declarado: Option<Decimal>,
+    /// Valor calculado. `None` si el atributo no debe existir
+    pub calculado: Option<Decimal>,
+}
+
+impl fmt::Display for Diferencia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let valor = |v: Option<Decimal>| v.map_or("sin valor".to_string(), |v| v.to_string());
+        write!(
+            f,
+            "{} ({}): declarado {}, calculado {}",
+            self.ruta,
+            self.atributo,
+            valor(self.declarado),
+            valor(self.calculado)
+        )
+    }
+}
+
+impl Comprobante {
+    /// Calcula el `SubTotal`, `Descuento`, impuestos y `Total` del comprobante a partir de sus
+    /// conceptos, y regresa las diferencias con los valores declarados, sin modificarlos. Ver
+    /// el módulo [`totales`](crate::totales).
+    pub fn recalculate_totals(&self) -> Vec<Diferencia> {
+        self.comparar_totales().0
+    }
+
+    /// Igual que [`Comprobante::recalculate_totals`], pero además reemplaza el `SubTotal`,
+    /// `Descuento`, impuestos y `Total` del comprobante con los valores calculados.
+    pub fn update_totals(&mut self) -> Vec<Diferencia> {
+        let (diferencias, calculados) = self.comparar_totales();
+        self.subtotal = calculados.subtotal;
+        self.descuento = calculados.descuento;
+        self.impuestos = calculados.impuestos;
+        self.total = calculados.total;
+        diferencias
+    }
+
+    fn comparar_totales(&self) -> (Vec<Diferencia>, Totales) {
+        let conceptos = &self.conceptos.concepto;
+        let mut diferencias = Diferencias::default();
+        for (i, concepto) in conceptos.iter().enumerate() {
+            let ruta = format!("Comprobante/Conceptos/Concepto[{}]", i + 1);
+            diferencias.concepto(concepto, &ruta);
+        }
+
+        let mut calculados = calcular(conceptos, &self.moneda);
+        match self.version.as_str() {
+            "3.2" => calculados.impuestos = self.impuestos.clone(),
+            "3.3" => calculados.impuestos = calculados.impuestos.and_then(impuestos_33),
+            _ => {}
+        }
+        if calculados.descuento.is_none() && self.descuento.is_some_and(|d| d.is_zero()) {
+            calculados.descuento = self.descuento;
+        }
+        let (locales_trasladados, locales_retenidos) = impuestos_locales(self);
+        calculados.total = total(
+            calculados.subtotal,
+            calculados.descuento,
+            calculados.impuestos.as_ref(),
+        ) + locales_trasladados
+            - locales_retenidos;
+
+        let ruta = "Comprobante";
+        diferencias.comparar(
+            ruta,
+            "SubTotal",
+            Some(self.subtotal),
+            Some(calculados.subtotal),
+        );
+        diferencias.comparar(ruta, "Descuento", self.descuento, calculados.descuento);
+        diferencias.comparar(ruta, "Total", Some(self.total), Some(calculados.total));
+        diferencias.impuestos(self.impuestos.as_ref(), calculados.impuestos.as_ref());
+        (diferencias.0, calculados)
+    }
+}
+
+/// Totales del comprobante calculados a partir de sus conceptos
+pub(crate) struct Totales {
+    pub subtotal: Decimal,
+    pub descuento: Option<Decimal>,
+    pub impuestos: Option<Impuestos>,
+    pub total: Decimal,
+}
+
+/// Calcula los totales de un comprobante 4.0 con los importes de `conceptos`
+pub(crate) fn calcular(conceptos: &[Concepto], moneda: &Moneda) -> Totales {
+    let decimales = decimales(moneda);
+    let subtotal = redondear(conceptos.iter().map(|c| c.importe).sum(), decimales);
+    let descuento = conceptos.iter().any(|c| c.descuento.is_some()).then(|| {
+        redondear(
+            conceptos.iter().filter_map(|c| c.descuento).sum(),
+            decimales,
+        )
+    });
+    let impuestos = agrupar_impuestos(conceptos, decimales);
+    let total = total(subtotal, descuento, impuestos.as_ref());
+    Totales {
+        subtotal,
+        descuento,
+        impuestos,
+        total,
+    }
+}
+
+fn total(subtotal: Decimal, descuento: Option<Decimal>, impuestos: Option<&Impuestos>) -> Decimal {
+    let (trasladados, retenidos) = impuestos
+        .map(|i| {
+            (
+                i.total_impuestos_trasladados.unwrap_or_default(),
+                i.total_impuestos_retenidos.unwrap_or_default(),
+            )
+        })
+        .unwrap_or_default();
+    subtotal - descuento.unwrap_or_default() + trasladados - retenidos
+}
+
+/// Total de impuestos locales trasladados y retenidos, según el complemento
+/// `implocal:ImpuestosLocales`. Cero si el comprobante no lo tiene.
+pub(crate) fn impuestos_locales(cfdi: &Comprobante) -> (Decimal, Decimal) {
+    let nodos = cfdi.complemento.iter().flat_map(|c| &c.otros).filter(|n| {
+        n.nombre == "ImpuestosLocales" && n.namespace.as_deref() == Some(NAMESPACE_IMPLOCAL)
+    });
+    let mut totales = (Decimal::ZERO, Decimal::ZERO);
+    for nodo in nodos {
+        let mut reader = Reader::from_str(&nodo.xml);
+        let Ok(Event::Start(e) | Event::Empty(e)) = reader.read_event() else {
+            continue;
+        };
+        let atributo = |nombre: &str| -> Decimal {
+            e.try_get_attribute(nombre)
+                .ok()
+                .flatten()
+                .and_then(|a| String::from_utf8_lossy(&a.value).trim().parse().ok())
+                .unwrap_or_default()
+        };
+        totales.0 += atributo("TotaldeTraslados");
+        totales.1 += atributo("TotaldeRetenciones");
+    }
+    totales
+}
+
+/// Decimales de los importes en la moneda. Las monedas que no están en el catálogo usan 2.
+pub(crate) fn decimales(moneda: &Moneda) -> u32 {
+    moneda.decimales().unwrap_or(2)
+}
+
+/// Redondea a `decimales` decimales, la mitad hacia arriba, y escribe siempre esos decimales
+/// (ej. `1000` como `1000.00`)
+pub(crate) fn redondear(valor: Decimal, decimales: u32) -> Decimal {
+    let mut valor = valor.round_dp_with_strategy(decimales, RoundingStrategy::MidpointAwayFromZero);
+    valor.rescale(decimales);
+    valor
+}
+
+/// Impuestos del comprobante a partir de los de sus conceptos: los traslados agrupados por
+/// `Impuesto`, `TipoFactor` y `TasaOCuota`, y las retenciones por `Impuesto`, con sus bases e
+/// importes sumados y redondeados a `decimales`. `None` si ningún concepto tiene impuestos.
+fn agrupar_impuestos(conceptos: &[Concepto], decimales: u32) -> Option<Impuestos> {
+    let mut traslados: Vec<Traslado> = vec![];
+    let mut retenciones: Vec<Retencion> = vec![];
+
+    for impuestos in conceptos.iter().filter_map(|c| c.impuestos.as_ref()) {
+        for traslado in impuestos.get_traslados() {
+            match traslados.iter_mut().find(|g| mismo_grupo(g, &traslado)) {
+                Some(grupo) => {
+                    grupo.base = sumar(grupo.base, traslado.base);
+                    grupo.importe = sumar(grupo.importe, traslado.importe);
+                }
+                None => traslados.push(traslado),
+            }
+        }
+        for retencion in impuestos.get_retenciones() {
+            match retenciones
+                .iter_mut()
+                .find(|g| g.impuesto == retencion.impuesto)
+            {
+                Some(grupo) => grupo.importe += retencion.importe,
+                None => retenciones.push(Retencion {
+                    base: None,
+                    impuesto: retencion.impuesto,
+                    tipo_factor: None,
+                    tasa_o_cuota: None,
+                    importe: retencion.importe,
+                }),
+            }
+        }
+    }
+
+    for traslado in &mut traslados {
+        traslado.base = traslado.base.map(|b| redondear(b, decimales));
+        traslado.importe = traslado.importe.map(|i| redondear(i, decimales));
+    }
+    for retencion in &mut retenciones {
+        retencion.importe = redondear(retencion.importe, decimales);
+    }
+    nodo_impuestos(traslados, retenciones)
+}
+
+/// En 3.3 los traslados del comprobante no tienen `Base`, y no se incluyen los exentos
+fn impuestos_33(impuestos: Impuestos) -> Option<Impuestos> {
+    let traslados = impuestos
+        .get_traslados()
+        .into_iter()
+        .filter(|t| t.tipo_factor != TipoFactor::Exento)
+        .map(|t| Traslado { base: None, ..t })
+        .collect();
+    nodo_impuestos(traslados, impuestos.get_retenciones())
+}
+
+/// Nodo `Impuestos` con los grupos de traslados y retenciones, y sus totales
+fn nodo_impuestos(traslados: Vec<Traslado>, retenciones: Vec<Retencion>) -> Option<Impuestos> {
+    if traslados.is_empty() && retenciones.is_empty() {
+        return None;
+    }
+    let total_impuestos_trasladados = traslados
+        .iter()
+        .any(|t| t.importe.is_some())
+        .then(|| traslados.iter().filter_map(|t| t.importe).sum());
+    let total_impuestos_retenidos =
+        (!retenciones.is_empty()).then(|| retenciones.iter().map(|r| r.importe).sum());
+
+    Some(Impuestos {
+        total_impuestos_retenidos,
+        total_impuestos_trasladados,
+        retenciones: (!retenciones.is_empty()).then_some(Retenciones {
+            retencion: retenciones,
+        }),
+        traslados: (!traslados.is_empty()).then_some(Traslados {
+            traslado: traslados,
+        }),
+    })
+}
+
+fn mismo_grupo(a: &Traslado, b: &Traslado) -> bool {
+    a.impuesto == b.impuesto && a.tipo_factor == b.tipo_factor && a.tasa_o_cuota == b.tasa_o_cuota
+}
+
+fn sumar(a: Option<Decimal>, b: Option<Decimal>) -> Option<Decimal> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or_default() + b.unwrap_or_default()),
+    }
+}
+
+const NAMESPACE_IMPLOCAL: &str = "http://www.sat.gob.mx/implocal";
+
+#[derive(Default)]
+struct Diferencias(Vec<Diferencia>);
+
+impl Diferencias {
+    fn comparar(
+        &mut self,
+        ruta: &str,
+        atributo: &'static str,
+        declarado: Option<Decimal>,
+        calculado: Option<Decimal>,
+    ) {
+        if declarado != calculado {
+            self.0.push(Diferencia {
+                ruta: ruta.to_string(),
+                atributo,
+                declarado,
+                calculado,
+            });
+        }
+    }
+
+    /// Revisa los importes del concepto y de sus impuestos dentro de los límites del Anexo 20
+    fn concepto(&mut self, concepto: &Concepto, ruta: &str) {
+        let decimales = concepto.importe.scale();
+        let (inferior, superior) = limites(concepto.cantidad, concepto.valor_unitario, decimales);
+        if concepto.importe < inferior || concepto.importe > superior {
+            let calculado = redondear(concepto.cantidad * concepto.valor_unitario, decimales);
+            self.comparar(ruta, "Importe", Some(concepto.importe), Some(calculado));
+        }
+
+        let Some(impuestos) = &concepto.impuestos else {
+            return;
+        };
+        for (i, traslado) in impuestos.get_traslados().iter().enumerate() {
+            if let (Some(base), Some(tasa), Some(importe)) =
+                (traslado.base, traslado.tasa_o_cuota, traslado.importe)
+            {
+                let ruta = format!("{}/Impuestos/Traslados/Traslado[{}]", ruta, i + 1);
+                self.impuesto(&ruta, base, tasa, importe);
+            }
+        }
+        for (i, retencion) in impuestos.get_retenciones().iter().enumerate() {
+            if let (Some(base), Some(tasa)) = (retencion.base, retencion.tasa_o_cuota) {
+                let ruta = format!("{}/Impuestos/Retenciones/Retencion[{}]", ruta, i + 1);
+                self.impuesto(&ruta, base, tasa, retencion.importe);
+            }
+        }
+    }
+
+    fn impuesto(&mut self, ruta: &str, base: Decimal, tasa: Decimal, importe: Decimal) {
+        let (inferior, superior) = limites_impuesto(base, tasa, importe.scale());
+        if importe < inferior || importe > superior {
+            let calculado = redondear(base * tasa, importe.scale());
+            self.comparar(ruta, "Importe", Some(importe), Some(calculado));
+        }
+    }
+
+    fn impuestos(&mut self, declarados: Option<&Impuestos>, calculados: Option<&Impuestos>) {
+        let ruta = "Comprobante/Impuestos";
+        self.comparar(
+            ruta,
+            "TotalImpuestosRetenidos",
+            declarados.and_then(|i| i.total_impuestos_retenidos),
+            calculados.and_then(|i| i.total_impuestos_retenidos),
+        );
+        self.comparar(
+            ruta,
+            "TotalImpuestosTrasladados",
+            declarados.and_then(|i| i.total_impuestos_trasladados),
+            calculados.and_then(|i| i.total_impuestos_trasladados),
+        );
+
+        let declaradas = declarados
+            .map(Impuestos::get_retenciones)
+            .unwrap_or_default();
+        let calculadas = calculados
+            .map(Impuestos::get_retenciones)
+            .unwrap_or_default();
+        for (i, calculada) in calculadas.iter().enumerate() {
+            let j = declaradas
+                .iter()
+                .position(|d| d.impuesto == calculada.impuesto);
+            let ruta = format!("{}/Retenciones/Retencion[{}]", ruta, j.unwrap_or(i) + 1);
+            let declarado = j.map(|j| declaradas[j].importe);
+            self.comparar(&ruta, "Importe", declarado, Some(calculada.importe));
+        }
+        for (j, declarada) in declaradas.iter().enumerate() {
+            if !calculadas.iter().any(|c| c.impuesto == declarada.impuesto) {
+                let ruta = format!("{}/Retenciones/Retencion[{}]", ruta, j + 1);
+                self.comparar(&ruta, "Importe", Some(declarada.importe), None);
+            }
+        }
+
+        let declarados = declarados.map(Impuestos::get_traslados).unwrap_or_default();
+        let calculados = calculados.map(Impuestos::get_traslados).unwrap_or_default();
+        for (i, calculado) in calculados.iter().enumerate() {
+            let j = declarados.iter().position(|d| mismo_grupo(d, calculado));
+            let ruta = format!("{}/Traslados/Traslado[{}]", ruta, j.unwrap_or(i) + 1);
+            let declarado = j.map(|j| &declarados[j]);
+            self.comparar(
+                &ruta,
+                "Base",
+                declarado.and_then(|d| d.base),
+                calculado.base,
+            );
+            self.comparar(
+                &ruta,
+                "Importe",
+                declarado.and_then(|d| d.importe),
+                calculado.importe,
+            );
+        }
+        for (j, declarado) in declarados.iter().enumerate() {
+            if !calculados.iter().any(|c| mismo_grupo(c, declarado)) {
+                let ruta = format!("{}/Traslados/Traslado[{}]", ruta, j + 1);
+                self.comparar(&ruta, "Base", declarado.base, None);
+                self.comparar(&ruta, "Importe", declarado.importe, None);
+            }
+        }
+    }
+}
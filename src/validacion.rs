@@ -0,0 +1,517 @@
+//! Validación de las reglas de negocio del CFDI 4.0, antes de enviarlo a timbrar.
+//!
+//! [`Comprobante::validate`] revisa las reglas de la Matriz de errores del Anexo 20 que se
+//! pueden comprobar con el contenido del comprobante (sin consultar al SAT ni sus listas de
+//! contribuyentes), y regresa un [`ErrorValidacion`] por cada regla que no se cumple, con la
+//! clave de error que regresaría el PAC:
+//!
+//! | Clave     | Regla |
+//! |-----------|-------|
+//! | CFDI40103 | Sin `FormaPago` en comprobantes de tipo Pago |
+//! | CFDI40107 | `SubTotal` con los decimales de la moneda |
+//! | CFDI40108 | `SubTotal` igual a la suma de los importes de los conceptos (I, E, N) |
+//! | CFDI40109 | `SubTotal` en cero en comprobantes de tipo T y P |
+//! | CFDI40110 | `Descuento` menor o igual al `SubTotal` |
+//! | CFDI40111 | `Descuento` con los decimales de la moneda |
+//! | CFDI40113 | `TipoCambio` igual a 1 cuando la moneda es MXN |
+//! | CFDI40114 | `TipoCambio` requerido cuando la moneda no es MXN ni XXX |
+//! | CFDI40115 | Sin `TipoCambio` cuando la moneda es XXX |
+//! | CFDI40118 | `Total` = `SubTotal` - `Descuento` + trasladados - retenidos |
+//! | CFDI40124 | Sin `MetodoPago` en comprobantes de tipo T y P |
+//! | CFDI40125 | `FormaPago` 99 cuando el `MetodoPago` es PPD |
+//! | CFDI40131 | `Exportacion` 02 si y solo si existe el complemento de Comercio Exterior |
+//! | CFDI40138 | `RegimenFiscal` del emisor que aplique a su tipo de persona |
+//! | CFDI40157 | `RegimenFiscalReceptor` que aplique al tipo de persona del receptor |
+//! | CFDI40166 | `Importe` del concepto dentro de los límites de `Cantidad` x `ValorUnitario` |
+//! | CFDI40167 | `Descuento` del concepto menor o igual a su `Importe` |
+//! | CFDI40169 | Conceptos con `ObjetoImp` 02 con nodo `Impuestos` |
+//! | CFDI40170 | Conceptos con `ObjetoImp` 01, 03, 04 o 05 sin nodo `Impuestos` |
+//! | CFDI40174 | Traslados Exento sin `TasaOCuota` ni `Importe` |
+//! | CFDI40175 | Traslados Tasa o Cuota con `TasaOCuota` e `Importe` |
+//! | CFDI40179 | `Importe` del traslado dentro de los límites de `Base` x `TasaOCuota` |
+//! | CFDI40190 | `Importe` de la retención dentro de los límites de `Base` x `TasaOCuota` |
+//! | CFDI40202 | `TotalImpuestosRetenidos` igual a la suma de las retenciones |
+//! | CFDI40208 | `TotalImpuestosTrasladados` igual a la suma de los traslados |
+//!
+//! El patrón de la `Fecha` (CFDI40101) no se revisa aquí, porque un comprobante con una fecha
+//! mal formada no se puede leer (ver [`fechas`](crate::fechas)). En comprobantes de versiones
+//! anteriores no se revisan las reglas de los atributos que no existen en esa versión (ej.
+//! `Exportacion` u `ObjetoImp` en 3.3), ni en 3.2 los totales de impuestos (CFDI40202 y
+//! CFDI40208), porque sus conceptos no tienen impuestos.
+//!
+//! Los límites de los importes se calculan como lo indica el Anexo 20: se toma la mitad de la
+//! última posición decimal de cada factor (la `TasaOCuota` se considera exacta) hacia abajo
+//! truncando, y hacia arriba redondeando hacia arriba, a los decimales del importe.
+//!
+//! El `Total` (CFDI40118) incluye los impuestos locales del complemento
+//! `implocal:ImpuestosLocales`: se suma su `TotaldeTraslados` y se resta su
+//! `TotaldeRetenciones`.
+//!
+//! Los totales de impuestos (CFDI40202 y CFDI40208) se comparan con la suma de los impuestos de
+//! los conceptos agrupados y redondeados a los decimales de la moneda, como se calculan en
+//! [`totales`].
+//!
+//! ```rust
+//! # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+//! #     Fecha="2024-01-15T10:00:00" FormaPago="03" SubTotal="1000.00" Moneda="USD" Total="1200.00"
+//! #     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+//! #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+//! #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+//! #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+//! #   <cfdi:Conceptos>
+//! #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+//! #         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="01"/>
+//! #   </cfdi:Conceptos>
+//! # </cfdi:Comprobante>"#;
+//! let cfdi = cfdi::parse_cfdi(xml).unwrap();
+//!
+//! let errores = cfdi.validate();
+//! let claves: Vec<_> = errores.iter().map(|e| e.codigo).collect();
+//! assert_eq!(claves, ["CFDI40114", "CFDI40118"]);
+//! assert_eq!(errores[0].ruta, "Comprobante");
+//! ```
+
+use std::fmt;
+
+use rust_decimal::RoundingStrategy;
+
+use crate::catalogos::{
+    Exportacion, FormaPago, MetodoPago, Moneda, ObjetoImp, RegimenFiscal, TipoDeComprobante,
+    TipoFactor,
+};
+use crate::rfc::{Rfc, TipoPersona};
+use crate::totales;
+use crate::{Comprobante, Concepto, Decimal, Retencion, Traslado};
+
+/// Regla de la Matriz de errores que no se cumple
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorValidacion {
+    /// Clave de error del SAT (ej. "CFDI40108")
+    pub codigo: &'static str,
+    /// Ruta del nodo con el error (ej. `"Comprobante/Conceptos/Concepto[2]"`)
+    pub ruta: String,
+    pub mensaje: String,
+}
+
+impl fmt::Display for ErrorValidacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.codigo, self.ruta, self.mensaje)
+    }
+}
+
+impl std::error::Error for ErrorValidacion {}
+
+impl Comprobante {
+    /// Revisa las reglas de la Matriz de errores del SAT. Regresa un vector vacío si el
+    /// comprobante las cumple todas. Ver el módulo [`validacion`](crate::validacion).
+    pub fn validate(&self) -> Vec<ErrorValidacion> {
+        let mut v = Validacion::default();
+        v.comprobante(self);
+        v.errores
+    }
+}
+
+#[derive(Default)]
+struct Validacion {
+    errores: Vec<ErrorValidacion>,
+}
+
+impl Validacion {
+    fn error(&mut self, codigo: &'static str, ruta: &str, mensaje: String) {
+        self.errores.push(ErrorValidacion {
+            codigo,
+            ruta: ruta.to_string(),
+            mensaje,
+        });
+    }
+
+    fn comprobante(&mut self, cfdi: &Comprobante) {
+        let ruta = "Comprobante";
+        use TipoDeComprobante as Tipo;
+
+        if cfdi.tipo_comprobante == Tipo::Pago && cfdi.forma_pago.is_some() {
+            self.error(
+                "CFDI40103",
+                ruta,
+                "En comprobantes de tipo Pago no debe existir FormaPago".to_string(),
+            );
+        }
+
+        let decimales = cfdi.moneda.decimales();
+        if excede_decimales(cfdi.subtotal, decimales) {
+            self.error(
+                "CFDI40107",
+                ruta,
+                format!(
+                    "El SubTotal {} excede los decimales de la moneda {}",
+                    cfdi.subtotal, cfdi.moneda
+                ),
+            );
+        }
+
+        let suma_importes: Decimal = cfdi.get_conceptos().iter().map(|c| c.importe).sum();
+        match cfdi.tipo_comprobante {
+            Tipo::Ingreso | Tipo::Egreso | Tipo::Nomina if cfdi.subtotal != suma_importes => self
+                .error(
+                    "CFDI40108",
+                    ruta,
+                    format!(
+                        "El SubTotal {} no es igual a la suma de los importes de los conceptos {}",
+                        cfdi.subtotal, suma_importes
+                    ),
+                ),
+            Tipo::Traslado | Tipo::Pago if !cfdi.subtotal.is_zero() => self.error(
+                "CFDI40109",
+                ruta,
+                format!(
+                    "En comprobantes de tipo {} el SubTotal debe ser cero",
+                    cfdi.tipo_comprobante
+                ),
+            ),
+            _ => {}
+        }
+
+        if let Some(descuento) = cfdi.descuento {
+            if descuento > cfdi.subtotal {
+                self.error(
+                    "CFDI40110",
+                    ruta,
+                    format!(
+                        "El Descuento {} es mayor que el SubTotal {}",
+                        descuento, cfdi.subtotal
+                    ),
+                );
+            }
+            if excede_decimales(descuento, decimales) {
+                self.error(
+                    "CFDI40111",
+                    ruta,
+                    format!(
+                        "El Descuento {} excede los decimales de la moneda {}",
+                        descuento, cfdi.moneda
+                    ),
+                );
+            }
+        }
+
+        match (&cfdi.moneda, cfdi.tipo_cambio) {
+            (Moneda::Mxn, Some(tipo_cambio)) if tipo_cambio != Decimal::ONE => self.error(
+                "CFDI40113",
+                ruta,
+                format!(
+                    "El TipoCambio debe ser 1 cuando la moneda es MXN, y es {}",
+                    tipo_cambio
+                ),
+            ),
+            (Moneda::Mxn | Moneda::Xxx, None) => {}
+            (moneda, None) => self.error(
+                "CFDI40114",
+                ruta,
+                format!("La moneda es {} y no se registró TipoCambio", moneda),
+            ),
+            (Moneda::Xxx, Some(_)) => self.error(
+                "CFDI40115",
+                ruta,
+                "No debe registrarse TipoCambio cuando la moneda es XXX".to_string(),
+            ),
+            _ => {}
+        }
+
+        let (trasladados, retenidos) = cfdi
+            .impuestos
+            .as_ref()
+            .map(|i| {
+                (
+                    i.total_impuestos_trasladados.unwrap_or_default(),
+                    i.total_impuestos_retenidos.unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default();
+        let (locales_trasladados, locales_retenidos) = totales::impuestos_locales(cfdi);
+        let total = cfdi.subtotal - cfdi.descuento_o_cero() + trasladados - retenidos
+            + locales_trasladados
+            - locales_retenidos;
+        if cfdi.total != total {
+            self.error(
+                "CFDI40118",
+                ruta,
+                format!(
+                    "El Total {} no es igual a SubTotal - Descuento + impuestos trasladados - impuestos retenidos + impuestos locales trasladados - impuestos locales retenidos ({})",
+                    cfdi.total, total
+                ),
+            );
+        }
+
+        if matches!(cfdi.tipo_comprobante, Tipo::Traslado | Tipo::Pago)
+            && cfdi.metodo_pago.is_some()
+        {
+            self.error(
+                "CFDI40124",
+                ruta,
+                format!(
+                    "En comprobantes de tipo {} no debe existir MetodoPago",
+                    cfdi.tipo_comprobante
+                ),
+            );
+        }
+
+        if cfdi.metodo_pago == Some(MetodoPago::PagoEnParcialidadesODiferido)
+            && cfdi
+                .forma_pago
+                .as_ref()
+                .is_some_and(|f| *f != FormaPago::PorDefinir)
+        {
+            self.error(
+                "CFDI40125",
+                ruta,
+                "Cuando el MetodoPago es PPD, la FormaPago debe ser 99 (Por definir)".to_string(),
+            );
+        }
+
+        let comercio_exterior = cfdi
+            .complemento
+            .as_ref()
+            .is_some_and(|c| c.comercio_exterior.is_some());
+        if let Some(exportacion) = &cfdi.exportacion {
+            if comercio_exterior != (*exportacion == Exportacion::Definitiva) {
+                self.error(
+                    "CFDI40131",
+                    ruta,
+                    format!(
+                        "La Exportacion es {} y el complemento de Comercio Exterior {}",
+                        exportacion,
+                        if comercio_exterior {
+                            "existe"
+                        } else {
+                            "no existe"
+                        }
+                    ),
+                );
+            }
+        }
+
+        if !regimen_aplica(&cfdi.emisor.rfc, &cfdi.emisor.regimen_fiscal) {
+            self.error(
+                "CFDI40138",
+                "Comprobante/Emisor",
+                format!(
+                    "El RegimenFiscal {} no aplica al tipo de persona del RFC {}",
+                    cfdi.emisor.regimen_fiscal, cfdi.emisor.rfc
+                ),
+            );
+        }
+        if let Some(regimen) = &cfdi.receptor.regimen_fiscal {
+            if !regimen_aplica(&cfdi.receptor.rfc, regimen) {
+                self.error(
+                    "CFDI40157",
+                    "Comprobante/Receptor",
+                    format!(
+                        "El RegimenFiscalReceptor {} no aplica al tipo de persona del RFC {}",
+                        regimen, cfdi.receptor.rfc
+                    ),
+                );
+            }
+        }
+
+        for (i, concepto) in cfdi.get_conceptos().iter().enumerate() {
+            let ruta = format!("Comprobante/Conceptos/Concepto[{}]", i + 1);
+            self.concepto(concepto, &ruta);
+        }
+
+        // Los conceptos de los comprobantes 3.2 no tienen impuestos con los cuales comparar
+        if let Some(impuestos) = cfdi.impuestos.as_ref().filter(|_| cfdi.version != "3.2") {
+            let ruta = "Comprobante/Impuestos";
+            let calculados = totales::calcular(&cfdi.conceptos.concepto, &cfdi.moneda).impuestos;
+            let retenciones = calculados
+                .as_ref()
+                .and_then(|i| i.total_impuestos_retenidos)
+                .unwrap_or_default();
+            let traslados = calculados
+                .as_ref()
+                .and_then(|i| i.total_impuestos_trasladados)
+                .unwrap_or_default();
+            if let Some(total) = impuestos.total_impuestos_retenidos {
+                if total != retenciones {
+                    self.error(
+                        "CFDI40202",
+                        ruta,
+                        format!(
+                            "El TotalImpuestosRetenidos {} no es igual a la suma redondeada de las retenciones de los conceptos {}",
+                            total, retenciones
+                        ),
+                    );
+                }
+            }
+            if let Some(total) = impuestos.total_impuestos_trasladados {
+                if total != traslados {
+                    self.error(
+                        "CFDI40208",
+                        ruta,
+                        format!(
+                            "El TotalImpuestosTrasladados {} no es igual a la suma redondeada de los traslados de los conceptos {}",
+                            total, traslados
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn concepto(&mut self, concepto: &Concepto, ruta: &str) {
+        let (inferior, superior) = limites(
+            concepto.cantidad,
+            concepto.valor_unitario,
+            concepto.importe.scale(),
+        );
+        if concepto.importe < inferior || concepto.importe > superior {
+            self.error(
+                "CFDI40166",
+                ruta,
+                format!(
+                    "El Importe {} no se encuentra entre el límite inferior {} y superior {}",
+                    concepto.importe, inferior, superior
+                ),
+            );
+        }
+
+        if let Some(descuento) = concepto.descuento {
+            if descuento > concepto.importe {
+                self.error(
+                    "CFDI40167",
+                    ruta,
+                    format!(
+                        "El Descuento {} es mayor que el Importe {}",
+                        descuento, concepto.importe
+                    ),
+                );
+            }
+        }
+
+        match (&concepto.objeto_imp, &concepto.impuestos) {
+            (Some(ObjetoImp::SiObjeto), None) => self.error(
+                "CFDI40169",
+                ruta,
+                "El ObjetoImp es 02 y el concepto no tiene Impuestos".to_string(),
+            ),
+            (
+                Some(
+                    objeto @ (ObjetoImp::NoObjeto
+                    | ObjetoImp::SiObjetoNoObligadoAlDesglose
+                    | ObjetoImp::SiObjetoNoCausaImpuesto
+                    | ObjetoImp::SiObjetoIvaCreditoPodebi),
+                ),
+                Some(_),
+            ) => self.error(
+                "CFDI40170",
+                ruta,
+                format!("El ObjetoImp es {} y el concepto tiene Impuestos", objeto),
+            ),
+            _ => {}
+        }
+
+        let Some(impuestos) = &concepto.impuestos else {
+            return;
+        };
+        for (i, traslado) in impuestos.get_traslados().iter().enumerate() {
+            let ruta = format!("{}/Impuestos/Traslados/Traslado[{}]", ruta, i + 1);
+            self.traslado(traslado, &ruta);
+        }
+        for (i, retencion) in impuestos.get_retenciones().iter().enumerate() {
+            let ruta = format!("{}/Impuestos/Retenciones/Retencion[{}]", ruta, i + 1);
+            self.retencion(retencion, &ruta);
+        }
+    }
+
+    fn traslado(&mut self, traslado: &Traslado, ruta: &str) {
+        match (
+            &traslado.tipo_factor,
+            traslado.tasa_o_cuota,
+            traslado.importe,
+        ) {
+            (TipoFactor::Exento, None, None) => {}
+            (TipoFactor::Exento, _, _) => self.error(
+                "CFDI40174",
+                ruta,
+                "Un traslado Exento no debe tener TasaOCuota ni Importe".to_string(),
+            ),
+            (_, Some(tasa), Some(importe)) => {
+                let base = traslado.base.unwrap_or_default();
+                let (inferior, superior) = limites_impuesto(base, tasa, importe.scale());
+                if importe < inferior || importe > superior {
+                    self.error(
+                        "CFDI40179",
+                        ruta,
+                        format!(
+                            "El Importe {} no se encuentra entre el límite inferior {} y superior {}",
+                            importe, inferior, superior
+                        ),
+                    );
+                }
+            }
+            (tipo_factor, _, _) => self.error(
+                "CFDI40175",
+                ruta,
+                format!(
+                    "Un traslado con TipoFactor {} debe tener TasaOCuota e Importe",
+                    tipo_factor
+                ),
+            ),
+        }
+    }
+
+    fn retencion(&mut self, retencion: &Retencion, ruta: &str) {
+        let (Some(base), Some(tasa)) = (retencion.base, retencion.tasa_o_cuota) else {
+            return;
+        };
+        let (inferior, superior) = limites_impuesto(base, tasa, retencion.importe.scale());
+        if retencion.importe < inferior || retencion.importe > superior {
+            self.error(
+                "CFDI40190",
+                ruta,
+                format!(
+                    "El Importe {} no se encuentra entre el límite inferior {} y superior {}",
+                    retencion.importe, inferior, superior
+                ),
+            );
+        }
+    }
+}
+
+fn excede_decimales(valor: Decimal, decimales: Option<u32>) -> bool {
+    decimales.is_some_and(|d| valor.scale() > d)
+}
+
+/// Los regímenes que no están en el catálogo y los RFC de longitud inválida no se revisan
+fn regimen_aplica(rfc: &Rfc, regimen: &RegimenFiscal) -> bool {
+    match (rfc.tipo_persona(), regimen) {
+        (_, RegimenFiscal::Unknown(_)) => true,
+        (Some(TipoPersona::Moral), regimen) => regimen.aplica_persona_moral(),
+        (Some(TipoPersona::Fisica), regimen) => regimen.aplica_persona_fisica(),
+        (None, _) => true,
+    }
+}
+
+/// Límites inferior y superior de `a` x `b`, considerando la mitad de la última posición
+/// decimal de cada factor, a `decimales` decimales.
+pub(crate) fn limites(a: Decimal, b: Decimal, decimales: u32) -> (Decimal, Decimal) {
+    let mitad_a = Decimal::new(5, a.scale() + 1);
+    let mitad_b = Decimal::new(5, b.scale() + 1);
+    let epsilon = Decimal::new(1, 12);
+
+    let inferior = ((a - mitad_a) * (b - mitad_b)).trunc_with_scale(decimales);
+    let superior = ((a + mitad_a - epsilon) * (b + mitad_b - epsilon))
+        .round_dp_with_strategy(decimales, RoundingStrategy::ToPositiveInfinity);
+    (inferior, superior)
+}
+
+/// Límites del importe de un impuesto: la `Base` se considera con la mitad de su última
+/// posición decimal, y la `TasaOCuota` exacta.
+pub(crate) fn limites_impuesto(base: Decimal, tasa: Decimal, decimales: u32) -> (Decimal, Decimal) {
+    let mitad = Decimal::new(5, base.scale() + 1);
+    let epsilon = Decimal::new(1, 12);
+
+    let inferior = ((base - mitad) * tasa).trunc_with_scale(decimales);
+    let superior = ((base + mitad - epsilon) * tasa)
+        .round_dp_with_strategy(decimales, RoundingStrategy::ToPositiveInfinity);
+    (inferior, superior)
+}
@@ -0,0 +1,348 @@
+//! Escritura de un [`Comprobante`] como documento xml del SAT.
+//!
+//! `quick_xml` serializa los structs usando solo el nombre local de cada nodo (ej. `Emisor`).
+//! Aquí se recorre ese xml y se reescribe agregando el prefijo que le corresponde a cada nodo
+//! (`cfdi:`, `pago20:`, `tfd:`, etc.), y las declaraciones de namespaces y
+//! `xsi:schemaLocation` en el nodo raíz. Los nodos que se conservan como
+//! [`NodoXml`](crate::NodoXml) se copian sin cambios.
+
+use std::borrow::Cow;
+
+use anyhow::{anyhow, bail, Result};
+use quick_xml::escape::escape;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::{Reader, Writer};
+
+use crate::{Comprobante, NodoXml};
+
+const XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Prefijo, namespace y ubicación del esquema (xsd) de un nodo del SAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Namespace {
+    pub prefijo: &'static str,
+    pub uri: &'static str,
+    pub esquema: &'static str,
+}
+
+pub(crate) const CFDI_40: Namespace = Namespace {
+    prefijo: "cfdi",
+    uri: "http://www.sat.gob.mx/cfd/4",
+    esquema: "http://www.sat.gob.mx/sitio_internet/cfd/4/cfdv40.xsd",
+};
+
+pub(crate) const CFDI_33: Namespace = Namespace {
+    prefijo: "cfdi",
+    uri: "http://www.sat.gob.mx/cfd/3",
+    esquema: "http://www.sat.gob.mx/sitio_internet/cfd/3/cfdv33.xsd",
+};
+
+/// Namespace de un complemento, a partir del nombre local de su nodo y su `Version`
+pub(crate) fn namespace_complemento(nombre: &str, version: Option<&str>) -> Option<Namespace> {
+    let ns = match (nombre, version) {
+        ("TimbreFiscalDigital", _) => Namespace {
+            prefijo: "tfd",
+            uri: "http://www.sat.gob.mx/TimbreFiscalDigital",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/TimbreFiscalDigital/TimbreFiscalDigitalv11.xsd",
+        },
+        ("Pagos", Some("1.0")) => Namespace {
+            prefijo: "pago10",
+            uri: "http://www.sat.gob.mx/Pagos",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/Pagos/Pagos10.xsd",
+        },
+        ("Pagos", _) => Namespace {
+            prefijo: "pago20",
+            uri: "http://www.sat.gob.mx/Pagos20",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/Pagos/Pagos20.xsd",
+        },
+        ("Nomina", _) => Namespace {
+            prefijo: "nomina12",
+            uri: "http://www.sat.gob.mx/nomina12",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/nomina/nomina12.xsd",
+        },
+        ("CartaPorte", Some("2.0")) => Namespace {
+            prefijo: "cartaporte20",
+            uri: "http://www.sat.gob.mx/CartaPorte20",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/CartaPorte/CartaPorte20.xsd",
+        },
+        ("CartaPorte", Some("3.0")) => Namespace {
+            prefijo: "cartaporte30",
+            uri: "http://www.sat.gob.mx/CartaPorte30",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/CartaPorte/CartaPorte30.xsd",
+        },
+        ("CartaPorte", _) => Namespace {
+            prefijo: "cartaporte31",
+            uri: "http://www.sat.gob.mx/CartaPorte31",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/CartaPorte/CartaPorte31.xsd",
+        },
+        ("ComercioExterior", _) => Namespace {
+            prefijo: "cce20",
+            uri: "http://www.sat.gob.mx/ComercioExterior20",
+            esquema: "http://www.sat.gob.mx/sitio_internet/cfd/ComercioExterior20/ComercioExterior20.xsd",
+        },
+        _ => return None,
+    };
+    Some(ns)
+}
+
+impl Comprobante {
+    /// Genera el xml del comprobante como lo define el Anexo 20: con los prefijos `cfdi:` y
+    /// los de cada complemento, las declaraciones de namespaces y `xsi:schemaLocation`, y
+    /// los atributos en el orden del esquema.
+    ///
+    /// Se usa el namespace de la `Version` del comprobante (4.0 o 3.3). Los comprobantes 3.2
+    /// solo se pueden leer, y regresan error.
+    ///
+    /// Los [`Complemento::otros`](crate::Complemento::otros) y la
+    /// [`addenda`](Comprobante::addenda) se escriben tal como se leyeron, declarando sus
+    /// namespaces en el nodo principal.
+    ///
+    /// ```rust
+    /// # let xml = r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+    /// #     Fecha="2024-01-15T10:00:00" SubTotal="1000.00" Moneda="MXN" Total="1160.00"
+    /// #     TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+    /// #   <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+    /// #   <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+    /// #       DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+    /// #   <cfdi:Conceptos>
+    /// #     <cfdi:Concepto ClaveProdServ="84111506" Cantidad="1" ClaveUnidad="ACT"
+    /// #         Descripcion="Servicio" ValorUnitario="1000.00" Importe="1000.00" ObjetoImp="02"/>
+    /// #   </cfdi:Conceptos>
+    /// # </cfdi:Comprobante>"#;
+    /// let cfdi = cfdi::parse_cfdi(xml).unwrap();
+    /// let generado = cfdi.to_xml().unwrap();
+    ///
+    /// assert!(generado.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    /// assert!(generado.contains(r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4""#));
+    /// assert!(generado.contains(r#"<cfdi:Emisor Rfc="EKU9003173C9""#));
+    ///
+    /// let releido = cfdi::parse_cfdi(&generado).unwrap();
+    /// assert_eq!(releido.total.to_string(), "1160.00");
+    /// ```
+    pub fn to_xml(&self) -> Result<String> {
+        let raiz = match self.version.as_str() {
+            "4.0" => CFDI_40,
+            "3.3" => CFDI_33,
+            v => bail!("No se puede generar el xml de un CFDI {}", v),
+        };
+        let xml = quick_xml::se::to_string_with_root("Comprobante", self)?;
+        let otros = match &self.complemento {
+            Some(complemento) => complemento.otros.as_slice(),
+            None => &[],
+        };
+        let xml = agregar_namespaces(&xml, raiz, otros, &self.addenda)?;
+        match &self.forma_original {
+            Some(forma) => forma.restaurar(&xml),
+            None => Ok(xml),
+        }
+    }
+}
+
+impl NodoXml {
+    /// El nodo como documento independiente, con las declaraciones de `namespaces` en su nodo
+    /// principal (ej. para leer una addenda con `quick_xml::de::from_str`)
+    pub fn documento(&self) -> String {
+        let fin_nombre = self.xml[1..]
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .map_or(self.xml.len(), |i| i + 1);
+        let mut documento = self.xml[..fin_nombre].to_string();
+        for (prefijo, uri) in &self.namespaces {
+            documento.push_str(" xmlns");
+            if !prefijo.is_empty() {
+                documento.push(':');
+                documento.push_str(prefijo);
+            }
+            documento.push_str(&format!("=\"{}\"", escape(uri)));
+        }
+        documento.push_str(&self.xml[fin_nombre..]);
+        documento
+    }
+}
+
+/// Declaraciones `xmlns` (prefijo, uri) y ubicaciones de esquemas del nodo principal
+struct Declaraciones {
+    xmlns: Vec<(String, String)>,
+    ubicaciones: Vec<String>,
+}
+
+impl Declaraciones {
+    /// Las de los `namespaces` del SAT, más las que usan los `nodos` que se copian tal cual
+    fn nuevas<'a>(namespaces: &[Namespace], nodos: impl Iterator<Item = &'a NodoXml>) -> Self {
+        let mut xmlns: Vec<(String, String)> = namespaces
+            .iter()
+            .map(|ns| (ns.prefijo.to_string(), ns.uri.to_string()))
+            .collect();
+        xmlns.push(("xsi".to_string(), XSI.to_string()));
+        let mut ubicaciones: Vec<String> = namespaces
+            .iter()
+            .map(|ns| format!("{} {}", ns.uri, ns.esquema))
+            .collect();
+
+        for nodo in nodos {
+            for (prefijo, uri) in &nodo.namespaces {
+                if !xmlns.iter().any(|(p, _)| p == prefijo) {
+                    xmlns.push((prefijo.clone(), uri.clone()));
+                }
+            }
+            if let (Some(uri), Some(esquema)) = (&nodo.namespace, &nodo.esquema) {
+                if !ubicaciones.iter().any(|u| u.split(' ').next() == Some(uri)) {
+                    ubicaciones.push(format!("{} {}", uri, esquema));
+                }
+            }
+        }
+        Declaraciones { xmlns, ubicaciones }
+    }
+}
+
+/// Reescribe `xml` (sin prefijos) agregando el prefijo de `raiz` a todos los nodos, excepto
+/// a los complementos y sus subnodos, que llevan el prefijo de su propio namespace.
+///
+/// Los `otros` complementos se copian tal cual al final de `Complemento`, y la `addenda` dentro
+/// de un nodo `Addenda` al final del documento.
+pub(crate) fn agregar_namespaces(
+    xml: &str,
+    raiz: Namespace,
+    otros: &[NodoXml],
+    addenda: &[NodoXml],
+) -> Result<String> {
+    let namespaces = namespaces_usados(xml, raiz)?;
+    let declaraciones = Declaraciones::nuevas(&namespaces, otros.iter().chain(addenda));
+    let copiar = |writer: &mut Writer<Vec<u8>>, nodos: &[NodoXml]| {
+        for nodo in nodos {
+            writer.get_mut().extend_from_slice(nodo.xml.as_bytes());
+        }
+    };
+
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut pila: Vec<(Namespace, String)> = vec![];
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let (ns, inicio) = renombrar(&e, &pila, raiz, &declaraciones)?;
+                pila.push((ns, local(&e)?));
+                writer.write_event(Event::Start(inicio))?;
+            }
+            Event::Empty(e) if pila.len() == 1 && local(&e)? == "Complemento" => {
+                let (ns, inicio) = renombrar(&e, &pila, raiz, &declaraciones)?;
+                let fin = BytesEnd::new(format!("{}:Complemento", ns.prefijo));
+                writer.write_event(Event::Start(inicio))?;
+                copiar(&mut writer, otros);
+                writer.write_event(Event::End(fin))?;
+            }
+            Event::Empty(e) => {
+                let (_, inicio) = renombrar(&e, &pila, raiz, &declaraciones)?;
+                writer.write_event(Event::Empty(inicio))?;
+            }
+            Event::End(_) => {
+                let (ns, nombre) = pila.pop().ok_or_else(|| anyhow!("xml mal formado"))?;
+                if pila.len() == 1 && nombre == "Complemento" {
+                    copiar(&mut writer, otros);
+                }
+                if pila.is_empty() && !addenda.is_empty() {
+                    let nombre = format!("{}:Addenda", ns.prefijo);
+                    writer.write_event(Event::Start(BytesStart::new(nombre.as_str())))?;
+                    copiar(&mut writer, addenda);
+                    writer.write_event(Event::End(BytesEnd::new(nombre)))?;
+                }
+                let nombre = format!("{}:{}", ns.prefijo, nombre);
+                writer.write_event(Event::End(BytesEnd::new(nombre)))?;
+            }
+            Event::Eof => break,
+            e => writer.write_event(e)?,
+        }
+    }
+
+    Ok(String::from_utf8(writer.into_inner())?)
+}
+
+/// Namespaces de todos los nodos del documento, en orden de aparición y sin repetir
+fn namespaces_usados(xml: &str, raiz: Namespace) -> Result<Vec<Namespace>> {
+    let mut reader = Reader::from_str(xml);
+    let mut namespaces = vec![raiz];
+    let mut pila: Vec<(Namespace, String)> = vec![];
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let ns = namespace_de(&e, &pila, raiz)?;
+                pila.push((ns, local(&e)?));
+                if !namespaces.contains(&ns) {
+                    namespaces.push(ns);
+                }
+            }
+            Event::Empty(e) => {
+                let ns = namespace_de(&e, &pila, raiz)?;
+                if !namespaces.contains(&ns) {
+                    namespaces.push(ns);
+                }
+            }
+            Event::End(_) => {
+                pila.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(namespaces)
+}
+
+fn local(e: &BytesStart) -> Result<String> {
+    Ok(std::str::from_utf8(e.local_name().into_inner())?.to_string())
+}
+
+/// Los hijos directos de `Complemento` cambian de namespace, los demás heredan el del padre
+fn namespace_de(
+    e: &BytesStart,
+    pila: &[(Namespace, String)],
+    raiz: Namespace,
+) -> Result<Namespace> {
+    let Some((ns_padre, padre)) = pila.last() else {
+        return Ok(raiz);
+    };
+    if *ns_padre == raiz && padre == "Complemento" {
+        let version = e
+            .try_get_attribute("Version")?
+            .map(|a| a.unescape_value())
+            .transpose()?;
+        if let Some(ns) = namespace_complemento(&local(e)?, version.as_deref()) {
+            return Ok(ns);
+        }
+    }
+    Ok(*ns_padre)
+}
+
+fn renombrar<'a>(
+    e: &'a BytesStart,
+    pila: &[(Namespace, String)],
+    raiz: Namespace,
+    declaraciones: &Declaraciones,
+) -> Result<(Namespace, BytesStart<'a>)> {
+    let ns = namespace_de(e, pila, raiz)?;
+    let mut inicio = BytesStart::new(format!("{}:{}", ns.prefijo, local(e)?));
+
+    if pila.is_empty() {
+        for (prefijo, uri) in &declaraciones.xmlns {
+            let llave = match prefijo.as_str() {
+                "" => "xmlns".to_string(),
+                prefijo => format!("xmlns:{}", prefijo),
+            };
+            inicio.push_attribute((llave.as_str(), uri.as_str()));
+        }
+        let ubicaciones = declaraciones.ubicaciones.join(" ");
+        inicio.push_attribute(("xsi:schemaLocation", ubicaciones.as_str()));
+    }
+
+    for atributo in e.attributes() {
+        let atributo = atributo?;
+        // El valor ya viene escapado, se copia tal cual
+        inicio.push_attribute(Attribute {
+            key: QName(atributo.key.into_inner()),
+            value: Cow::Borrowed(&atributo.value),
+        });
+    }
+    Ok((ns, inicio))
+}
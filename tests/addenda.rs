@@ -0,0 +1,132 @@
+use std::fs;
+
+use cfdi::{cadena_original, parse_cfdi, NodoXml};
+
+fn leer() -> String {
+    fs::read_to_string("tests/data/addenda.xml").unwrap()
+}
+
+/// Texto de `xml` desde `inicio` hasta el final de `fin` (inclusive)
+fn fragmento<'a>(xml: &'a str, inicio: &str, fin: &str) -> &'a str {
+    let i = xml.find(inicio).unwrap();
+    let j = xml[i..].find(fin).unwrap() + i + fin.len();
+    &xml[i..j]
+}
+
+#[test]
+fn conservar_complementos_y_addenda() {
+    let xml = leer();
+    let cfdi = parse_cfdi(&xml).unwrap();
+
+    let complemento = cfdi.complemento.as_ref().unwrap();
+    assert!(complemento.timbre_fiscal_digital.is_some());
+    assert_eq!(
+        complemento.otros,
+        vec![NodoXml {
+            nombre: "ImpuestosLocales".to_string(),
+            namespace: Some("http://www.sat.gob.mx/implocal".to_string()),
+            namespaces: vec![(
+                "implocal".to_string(),
+                "http://www.sat.gob.mx/implocal".to_string()
+            )],
+            esquema: Some(
+                "http://www.sat.gob.mx/sitio_internet/cfd/implocal/implocal.xsd".to_string()
+            ),
+            xml: fragmento(
+                &xml,
+                "<implocal:ImpuestosLocales",
+                "</implocal:ImpuestosLocales>"
+            )
+            .to_string(),
+        }]
+    );
+
+    assert_eq!(cfdi.addenda.len(), 2);
+    let orden = &cfdi.addenda[0];
+    assert_eq!(orden.nombre, "Orden");
+    assert_eq!(
+        orden.namespace.as_deref(),
+        Some("http://www.ejemplo.com.mx/addenda/ordenes")
+    );
+    assert_eq!(orden.namespaces.len(), 1);
+    assert_eq!(orden.esquema, None);
+    assert_eq!(orden.xml, fragmento(&xml, "<ord:Orden", "</ord:Orden>"));
+
+    let remision = &cfdi.addenda[1];
+    assert_eq!(remision.nombre, "DSCargaRemisionProv");
+    assert_eq!(remision.namespace, None);
+    assert!(remision.namespaces.is_empty());
+    assert!(remision.xml.contains(r#"<Remision Id="1" RowOrder="1""#));
+}
+
+#[test]
+fn sin_addenda() {
+    let cfdi = parse_cfdi(&fs::read_to_string("tests/data/ingreso.xml").unwrap()).unwrap();
+    assert!(cfdi.addenda.is_empty());
+    assert!(cfdi.complemento.unwrap().otros.is_empty());
+}
+
+#[test]
+fn documento_de_la_addenda() {
+    let cfdi = parse_cfdi(&leer()).unwrap();
+    let documento = cfdi.addenda[0].documento();
+
+    assert!(documento.starts_with(
+        r#"<ord:Orden xmlns:ord="http://www.ejemplo.com.mx/addenda/ordenes" Numero="4500012345""#
+    ));
+    assert!(documento.ends_with("</ord:Orden>"));
+    assert_eq!(cfdi.addenda[1].documento(), cfdi.addenda[1].xml);
+}
+
+#[test]
+fn reescribir_sin_perder_nodos() {
+    let xml = leer();
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let generado = cfdi.to_xml().unwrap();
+
+    assert!(generado.contains(r#"xmlns:implocal="http://www.sat.gob.mx/implocal""#));
+    assert!(generado.contains(r#"xmlns:ord="http://www.ejemplo.com.mx/addenda/ordenes""#));
+    assert!(generado.contains(
+        "http://www.sat.gob.mx/implocal http://www.sat.gob.mx/sitio_internet/cfd/implocal/implocal.xsd"
+    ));
+    assert!(generado.contains(&format!("{}</cfdi:Complemento>", complemento_otros(&cfdi))));
+    assert!(generado.ends_with(&format!(
+        "<cfdi:Addenda>{}{}</cfdi:Addenda></cfdi:Comprobante>",
+        cfdi.addenda[0].xml, cfdi.addenda[1].xml
+    )));
+
+    let releido = parse_cfdi(&generado).unwrap();
+    assert_eq!(releido.addenda, cfdi.addenda);
+    assert_eq!(
+        releido.complemento.as_ref().unwrap().otros,
+        cfdi.complemento.as_ref().unwrap().otros
+    );
+
+    // Ni los complementos desconocidos ni la addenda forman parte de la cadena original, que
+    // es la del ingreso con el Total que incluye el ISH
+    let esperada = fs::read_to_string("tests/data/ingreso.txt")
+        .unwrap()
+        .replace("|MXN|2514.00|", "|MXN|2539.00|");
+    assert_eq!(cadena_original(&xml).unwrap(), esperada);
+    assert_eq!(cadena_original(&generado).unwrap(), esperada);
+}
+
+#[test]
+fn complemento_solo_con_nodos_desconocidos() {
+    let xml = leer();
+    let inicio = xml.find("<tfd:TimbreFiscalDigital").unwrap();
+    let fin = xml[inicio..].find("/>").unwrap() + inicio + 2;
+    let xml = format!("{}{}", &xml[..inicio], &xml[fin..]);
+
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(&format!(
+        "<cfdi:Complemento>{}</cfdi:Complemento>",
+        complemento_otros(&cfdi)
+    )));
+}
+
+fn complemento_otros(cfdi: &cfdi::Comprobante) -> String {
+    let otros = &cfdi.complemento.as_ref().unwrap().otros;
+    otros.iter().map(|n| n.xml.as_str()).collect()
+}
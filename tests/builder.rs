@@ -0,0 +1,264 @@
+use std::fs;
+
+use cfdi::catalogos::{
+    FormaPago, Impuesto, Meses, MetodoPago, Moneda, Periodicidad, RegimenFiscal, TipoDeComprobante,
+    TipoFactor, TipoRelacion, UsoCfdi,
+};
+use cfdi::sello::{verificar, Certificado, LlavePrivada, Verificacion};
+use cfdi::{parse_cfdi, ComprobanteBuilder, ConceptoBuilder, Decimal, ErrorConstruccion};
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn csd() -> (Certificado, LlavePrivada) {
+    let cer = fs::read("tests/data/csd/EKU9003173C9.cer").unwrap();
+    let key = fs::read("tests/data/csd/EKU9003173C9.key").unwrap();
+    (
+        Certificado::from_der(&cer).unwrap(),
+        LlavePrivada::from_der(&key, "12345678a").unwrap(),
+    )
+}
+
+/// Los mismos datos que `tests/data/ingreso.xml`, sin sus importes
+fn ingreso() -> ComprobanteBuilder {
+    ComprobanteBuilder::new(TipoDeComprobante::Ingreso)
+        .fecha("2024-05-20T13:45:10".parse().unwrap())
+        .lugar_expedicion("45079")
+        .forma_pago(FormaPago::TransferenciaElectronica)
+        .metodo_pago(MetodoPago::PagoEnUnaExhibicion)
+        .emisor(
+            "EKU9003173C9",
+            "ESCUELA KEMPER URGATE",
+            RegimenFiscal::GeneralDeLeyPersonasMorales,
+        )
+        .receptor(
+            "URE180429TM6",
+            "UNIVERSIDAD ROBOTICA ESPAÑOLA",
+            "65000",
+            RegimenFiscal::GeneralDeLeyPersonasMorales,
+            UsoCfdi::GastosEnGeneral,
+        )
+        .concepto(
+            ConceptoBuilder::new(
+                "43232408",
+                "E48",
+                "Licencia de software anual",
+                decimal("1"),
+                decimal("2000.00"),
+            )
+            .no_identificacion("SW-01")
+            .unidad("Servicio")
+            .descuento(decimal("100"))
+            .traslado(Impuesto::Iva, TipoFactor::Tasa, decimal("0.16"))
+            .retencion(Impuesto::Isr, decimal("0.10")),
+        )
+        .concepto(
+            ConceptoBuilder::new(
+                "84111506",
+                "H87",
+                "Capacitación & soporte",
+                decimal("2.5"),
+                decimal("200.00"),
+            )
+            .exento(Impuesto::Iva),
+        )
+}
+
+#[test]
+fn calcular_importes_y_totales() {
+    let cfdi = ingreso().build().unwrap();
+
+    assert_eq!(cfdi.subtotal.to_string(), "2500.00");
+    assert_eq!(cfdi.descuento.unwrap().to_string(), "100.00");
+    assert_eq!(cfdi.total.to_string(), "2514.00");
+
+    let impuestos = cfdi.impuestos.as_ref().unwrap();
+    assert_eq!(impuestos.total_impuestos_trasladados, Some(decimal("304")));
+    assert_eq!(impuestos.total_impuestos_retenidos, Some(decimal("190")));
+    let exento = &impuestos.get_traslados()[1];
+    assert_eq!(exento.base.unwrap().to_string(), "500.00");
+    assert_eq!(exento.importe, None);
+}
+
+#[test]
+fn misma_cadena_que_el_xml() {
+    let (certificado, llave) = csd();
+    let mut cfdi = ingreso().serie_folio("A", "1024").build().unwrap();
+    cfdi.sellar(&certificado, &llave).unwrap();
+
+    let esperada = fs::read_to_string("tests/data/ingreso.txt").unwrap();
+    assert_eq!(cfdi.cadena_original().unwrap(), esperada);
+
+    let xml = cfdi.to_xml().unwrap();
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido);
+    assert_eq!(parse_cfdi(&xml).unwrap().validate(), vec![]);
+}
+
+#[test]
+fn agrupar_impuestos_y_redondear() {
+    let concepto = |valor_unitario: &str, tasa: &str| {
+        ConceptoBuilder::new(
+            "01010101",
+            "H87",
+            "Producto",
+            decimal("3"),
+            decimal(valor_unitario),
+        )
+        .traslado(Impuesto::Iva, TipoFactor::Tasa, decimal(tasa))
+    };
+    let cfdi = ingreso()
+        .moneda(Moneda::Usd)
+        .tipo_cambio(decimal("17.0125"))
+        .concepto(concepto("33.335", "0.16"))
+        .concepto(concepto("10.00", "0.160000"))
+        .concepto(concepto("10.00", "0"))
+        .build()
+        .unwrap();
+
+    let importes: Vec<_> = cfdi.conceptos.concepto[2..]
+        .iter()
+        .map(|c| c.importe.to_string())
+        .collect();
+    assert_eq!(importes, ["100.01", "30.00", "30.00"]);
+
+    let traslados: Vec<_> = cfdi.impuestos.as_ref().unwrap().get_traslados();
+    let grupos: Vec<_> = traslados
+        .iter()
+        .map(|t| {
+            (
+                t.tipo_factor.to_string(),
+                t.tasa_o_cuota.map(|t| t.to_string()),
+                t.base.unwrap().to_string(),
+                t.importe.map(|i| i.to_string()),
+            )
+        })
+        .collect();
+    let grupo = |tipo: &str, tasa: Option<&str>, base: &str, importe: Option<&str>| {
+        (
+            tipo.to_string(),
+            tasa.map(String::from),
+            base.to_string(),
+            importe.map(String::from),
+        )
+    };
+    assert_eq!(
+        grupos,
+        [
+            grupo("Tasa", Some("0.160000"), "2030.01", Some("324.80")),
+            grupo("Exento", None, "500.00", None),
+            grupo("Tasa", Some("0.000000"), "30.00", Some("0.00")),
+        ]
+    );
+    assert_eq!(cfdi.total.to_string(), "2694.81");
+}
+
+#[test]
+fn retenciones_de_tasa_y_cuota() {
+    let cfdi = ingreso()
+        .concepto(
+            ConceptoBuilder::new("50202306", "H87", "Bebida", decimal("4"), decimal("25.00"))
+                .retencion(Impuesto::Iva, decimal("0.106667"))
+                .retencion_cuota(Impuesto::Ieps, decimal("1.5")),
+        )
+        .build()
+        .unwrap();
+
+    let impuestos = cfdi.conceptos.concepto[2].impuestos.as_ref().unwrap();
+    let retenciones: Vec<_> = impuestos
+        .get_retenciones()
+        .iter()
+        .map(|r| {
+            (
+                r.tipo_factor.as_ref().unwrap().to_string(),
+                r.tasa_o_cuota.unwrap().to_string(),
+                r.importe.to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        retenciones,
+        [
+            (
+                "Tasa".to_string(),
+                "0.106667".to_string(),
+                "10.67".to_string()
+            ),
+            (
+                "Cuota".to_string(),
+                "1.500000".to_string(),
+                "150.00".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn datos_faltantes() {
+    let error = ComprobanteBuilder::new(TipoDeComprobante::Ingreso)
+        .fecha("2024-05-20T13:45:10".parse().unwrap())
+        .lugar_expedicion("45079")
+        .build()
+        .unwrap_err();
+    assert_eq!(error, ErrorConstruccion::Faltante("Emisor"));
+    assert_eq!(error.to_string(), "No se indicó Emisor del comprobante");
+}
+
+#[test]
+fn errores_de_validacion() {
+    let error = ingreso()
+        .moneda(Moneda::Usd)
+        .metodo_pago(MetodoPago::PagoEnParcialidadesODiferido)
+        .build()
+        .unwrap_err();
+
+    let ErrorConstruccion::Validacion(errores) = error else {
+        panic!("{error:?}");
+    };
+    let claves: Vec<_> = errores.iter().map(|e| e.codigo).collect();
+    assert_eq!(claves, ["CFDI40114", "CFDI40125"]);
+}
+
+#[test]
+fn cfdi_relacionados_e_informacion_global() {
+    let nota = ingreso()
+        .cfdi_relacionados(
+            TipoRelacion::NotaDeCredito,
+            ["5FB2822E-396D-4725-8521-CDC4BDD20CCF"],
+        )
+        .cfdi_relacionados(
+            TipoRelacion::DevolucionDeMercancia,
+            ["7C1E2D3F-4A5B-4C6D-8E9F-0A1B2C3D4E5F"],
+        )
+        .cfdi_relacionados(
+            TipoRelacion::NotaDeCredito,
+            ["9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D"],
+        )
+        .informacion_global(Periodicidad::Mensual, Meses::Mayo, 2024)
+        .build()
+        .unwrap();
+
+    let xml = nota.to_xml().unwrap();
+    assert!(xml.contains(
+        r#"<cfdi:InformacionGlobal Periodicidad="04" Meses="05" Año="2024"/><cfdi:CfdiRelacionados TipoRelacion="01"><cfdi:CfdiRelacionado UUID="5FB2822E-396D-4725-8521-CDC4BDD20CCF"/><cfdi:CfdiRelacionado UUID="9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D"/></cfdi:CfdiRelacionados><cfdi:CfdiRelacionados TipoRelacion="03">"#
+    ));
+    assert!(xml.find("CfdiRelacionados").unwrap() < xml.find("<cfdi:Emisor").unwrap());
+
+    let leido = parse_cfdi(&xml).unwrap();
+    assert_eq!(leido.cfdi_relacionados.len(), 2);
+    assert_eq!(
+        leido.uuids_relacionados(&TipoRelacion::NotaDeCredito),
+        [
+            "5FB2822E-396D-4725-8521-CDC4BDD20CCF",
+            "9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D"
+        ]
+    );
+    let global = leido.informacion_global.as_ref().unwrap();
+    assert_eq!(
+        (&global.periodicidad, &global.meses, global.anio),
+        (&Periodicidad::Mensual, &Meses::Mayo, 2024)
+    );
+    assert!(leido.cadena_original().unwrap().starts_with(
+        "||4.0|2024-05-20T13:45:10|03||2500.00|100.00|MXN|2514.00|I|01|PUE|45079|04|05|2024|01|5FB2822E-396D-4725-8521-CDC4BDD20CCF|9A4A3C5E-2C2B-4F3B-B7D1-4E5C8A1F0B2D|03|7C1E2D3F-4A5B-4C6D-8E9F-0A1B2C3D4E5F|EKU9003173C9|"
+    ));
+}
@@ -0,0 +1,68 @@
+use std::fs;
+
+use cfdi::cadena_original;
+use cfdi::sello::{verificar, Verificacion};
+
+const DOCUMENTOS: &[&str] = &[
+    "ingreso",
+    "pago",
+    "nomina",
+    "cfdi33",
+    "pago33",
+    "pago10",
+    "comercio_exterior",
+    "cartaporte20",
+    "cartaporte30",
+];
+
+/// Comprobantes timbrados por el SAT, con la cadena generada por el `cadenaoriginal_4_0.xslt`
+/// oficial (ej. `xsltproc cadenaoriginal_4_0.xslt documento.xml`). Las cadenas de `DOCUMENTOS`
+/// se escribieron a mano, así que solo estos comprueban la cadena contra la del SAT.
+///
+/// Todavía no se incluye ninguno: los ejemplos publicados por el SAT y el XSLT no están
+/// disponibles en este árbol.
+const TIMBRADOS: &[&str] = &[];
+
+fn leer(nombre: &str) -> (String, String) {
+    let xml = fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap();
+    let cadena = fs::read_to_string(format!("tests/data/{nombre}.txt")).unwrap();
+    (xml, cadena)
+}
+
+#[test]
+fn cadena_original_del_xml() {
+    for nombre in DOCUMENTOS {
+        let (xml, esperada) = leer(nombre);
+        assert_eq!(cadena_original(&xml).unwrap(), esperada, "{nombre}");
+    }
+}
+
+#[test]
+fn cadena_original_de_timbrados_por_el_sat() {
+    for nombre in TIMBRADOS {
+        let (xml, esperada) = leer(nombre);
+        assert_eq!(cadena_original(&xml).unwrap(), esperada, "{nombre}");
+        assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido, "{nombre}");
+    }
+}
+
+#[test]
+fn version_no_soportada() {
+    let (xml, _) = leer("ingreso");
+    let xml = xml.replace(r#"Version="4.0""#, r#"Version="9.9""#);
+    assert!(cadena_original(&xml).is_err());
+}
+
+#[test]
+fn complemento_no_soportado_se_omite() {
+    let (xml, esperada) = leer("ingreso");
+    let xml = xml.replace(
+        "</cfdi:Complemento>",
+        r#"<implocal:ImpuestosLocales xmlns:implocal="http://www.sat.gob.mx/implocal" version="1.0" TotaldeRetenciones="0.00" TotaldeTraslados="25.00">
+      <implocal:TrasladosLocales ImpLocTrasladado="ISH" TasadeTraslado="3.00" Importe="25.00"/>
+    </implocal:ImpuestosLocales>
+  </cfdi:Complemento>"#,
+    );
+    // El XSLT del SAT sí incluye este complemento, por lo que esta cadena no es la correcta
+    assert_eq!(cadena_original(&xml).unwrap(), esperada);
+}
@@ -0,0 +1,121 @@
+use std::fs;
+
+use cfdi::complementos::cartaporte::CartaPorte;
+use cfdi::{cadena_original, parse_cfdi, Decimal};
+
+fn leer(nombre: &str) -> (String, String) {
+    let xml = fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap();
+    let cadena = fs::read_to_string(format!("tests/data/{nombre}.txt")).unwrap();
+    (xml, cadena)
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn carta_porte(xml: &str) -> CartaPorte {
+    let cfdi = parse_cfdi(xml).unwrap();
+    cfdi.complemento.unwrap().carta_porte.unwrap()
+}
+
+#[test]
+fn leer_carta_porte_20() {
+    let (xml, _) = leer("cartaporte20");
+    let carta_porte = carta_porte(&xml);
+
+    assert_eq!(carta_porte.version, "2.0");
+    assert_eq!(carta_porte.id_ccp, None);
+    assert_eq!(carta_porte.total_dist_rec, Some(decimal("280")));
+
+    let ubicaciones = carta_porte.get_ubicaciones();
+    let ids: Vec<_> = ubicaciones
+        .iter()
+        .map(|u| u.id_ubicacion.as_deref().unwrap())
+        .collect();
+    assert_eq!(ids, ["OR000001", "DE000002"]);
+    assert_eq!(ubicaciones[1].domicilio.as_ref().unwrap().estado, "GUA");
+
+    let mercancia = &carta_porte.get_mercancias()[0];
+    assert_eq!(mercancia.pedimentos[0].pedimento, "22  47  3807  2000123");
+    assert!(mercancia.documentacion_aduanera.is_empty());
+    let transporta = &mercancia.cantidad_transporta[0];
+    assert_eq!(
+        (
+            transporta.id_origen.as_str(),
+            transporta.id_destino.as_str()
+        ),
+        ("OR000001", "DE000002")
+    );
+
+    let autotransporte = carta_porte.mercancias.autotransporte.as_ref().unwrap();
+    let vehiculo = &autotransporte.identificacion_vehicular;
+    assert_eq!(vehiculo.peso_bruto_vehicular, None);
+    assert_eq!(vehiculo.anio_modelo_vm, 2018);
+    let remolques = autotransporte.remolques.as_ref().unwrap();
+    assert_eq!(remolques.remolque[0].placa, "REM5678");
+
+    let figuras = &carta_porte.figura_transporte.as_ref().unwrap().tipos_figura;
+    assert_eq!(figuras.len(), 2);
+    assert_eq!(figuras[1].partes_transporte[0].parte_transporte, "PT01");
+}
+
+#[test]
+fn leer_carta_porte_30() {
+    let (xml, _) = leer("cartaporte30");
+    let carta_porte = carta_porte(&xml);
+
+    assert_eq!(carta_porte.version, "3.0");
+    assert_eq!(
+        carta_porte.id_ccp.as_deref(),
+        Some("CCC2A8E1-5B3D-4F6A-9C7E-0D1F2A3B4C5D")
+    );
+    assert_eq!(carta_porte.regimen_aduanero.as_deref(), Some("IMD"));
+    assert!(carta_porte.regimenes_aduaneros.is_none());
+
+    let origen = &carta_porte.get_ubicaciones()[0];
+    assert_eq!(origen.residencia_fiscal.as_deref(), Some("CHN"));
+    assert!(origen.domicilio.is_none());
+
+    let mercancia = &carta_porte.get_mercancias()[0];
+    assert!(mercancia.pedimentos.is_empty());
+    let documento = &mercancia.documentacion_aduanera[0];
+    assert_eq!(documento.tipo_documento, "01");
+    assert_eq!(documento.rfc_impo.as_deref(), Some("EKU9003173C9"));
+    let detalle = mercancia.detalle_mercancia.as_ref().unwrap();
+    assert_eq!(detalle.peso_bruto - detalle.peso_tara, detalle.peso_neto);
+
+    let maritimo = carta_porte.mercancias.transporte_maritimo.as_ref().unwrap();
+    assert_eq!(maritimo.puntal, Some(decimal("24.60")));
+    let contenedor = &maritimo.contenedor[0];
+    assert_eq!(
+        contenedor.matricula_contenedor.as_deref(),
+        Some("MSCU1234565")
+    );
+    let remolques = contenedor.remolques.as_ref().unwrap();
+    assert_eq!(remolques.remolque[0].placa, "COL4321");
+}
+
+#[test]
+fn cadena_original_de_cada_version() {
+    for nombre in ["cartaporte20", "cartaporte30"] {
+        let (xml, esperada) = leer(nombre);
+        assert_eq!(cadena_original(&xml).unwrap(), esperada, "{nombre}");
+
+        let cfdi = parse_cfdi(&xml).unwrap();
+        assert_eq!(cfdi.validate(), vec![], "{nombre}");
+        let generado = cfdi.to_xml().unwrap();
+        assert_eq!(cadena_original(&generado).unwrap(), esperada, "{nombre}");
+    }
+}
+
+#[test]
+fn orden_del_contenedor_segun_la_version() {
+    let (xml, _) = leer("cartaporte30");
+    let cadena = cadena_original(&xml).unwrap();
+    assert!(cadena.contains("|CM011|MSCU1234565|SL998877|CTR004|COL4321||"));
+
+    // En la versión 2.0 la matrícula va antes del tipo de contenedor
+    let xml = xml.replace(r#"Version="3.0""#, r#"Version="2.0""#);
+    let cadena = cadena_original(&xml).unwrap();
+    assert!(cadena.contains("|MSCU1234565|CM011|SL998877|"));
+}
@@ -0,0 +1,58 @@
+use std::fs;
+
+use cfdi::catalogos::{
+    FormaPago, Impuesto, MetodoPago, Moneda, ObjetoImp, RegimenFiscal, TipoDeComprobante,
+    TipoFactor, UsoCfdi,
+};
+use cfdi::parse_cfdi;
+
+#[test]
+fn leer_catalogos() {
+    let xml = fs::read_to_string("tests/data/ingreso.xml").unwrap();
+    let cfdi = parse_cfdi(&xml).unwrap();
+
+    assert_eq!(cfdi.forma_pago, Some(FormaPago::TransferenciaElectronica));
+    assert_eq!(cfdi.metodo_pago, Some(MetodoPago::PagoEnUnaExhibicion));
+    assert_eq!(cfdi.tipo_comprobante, TipoDeComprobante::Ingreso);
+    assert_eq!(cfdi.moneda, Moneda::Mxn);
+    assert_eq!(cfdi.moneda.decimales(), Some(2));
+    assert_eq!(
+        cfdi.emisor.regimen_fiscal,
+        RegimenFiscal::GeneralDeLeyPersonasMorales
+    );
+    assert_eq!(cfdi.receptor.uso_cfdi, UsoCfdi::GastosEnGeneral);
+
+    let concepto = &cfdi.conceptos.concepto[0];
+    assert_eq!(concepto.objeto_imp, Some(ObjetoImp::SiObjeto));
+    let traslado = &concepto.impuestos.as_ref().unwrap().get_traslados()[0];
+    assert_eq!(traslado.impuesto, Impuesto::Iva);
+    assert_eq!(traslado.tipo_factor, TipoFactor::Tasa);
+}
+
+#[test]
+fn clave_desconocida() {
+    let xml = fs::read_to_string("tests/data/ingreso.xml")
+        .unwrap()
+        .replace(r#"UsoCFDI="G03""#, r#"UsoCFDI="G99""#);
+    let cfdi = parse_cfdi(&xml).unwrap();
+
+    assert_eq!(cfdi.receptor.uso_cfdi, UsoCfdi::Unknown("G99".to_string()));
+    assert!(cfdi.to_xml().unwrap().contains(r#"UsoCFDI="G99""#));
+}
+
+#[test]
+fn decimales_de_la_moneda() {
+    let decimales = |clave: &str| Moneda::from(clave).decimales();
+    assert_eq!(decimales("MXN"), Some(2));
+    assert_eq!(decimales("PLN"), Some(2));
+    assert_eq!(decimales("JPY"), Some(0));
+    assert_eq!(decimales("XAU"), Some(0));
+    for clave in ["BHD", "KWD", "JOD", "OMR", "TND"] {
+        assert_eq!(decimales(clave), Some(3), "{clave}");
+    }
+    assert_eq!(decimales("CLF"), Some(4));
+    assert_eq!(decimales("ABC"), None);
+
+    assert_eq!(Moneda::from("KWD").descripcion(), Some("Dinar kuwaití"));
+    assert_eq!(Moneda::from("ZWL").clave(), "ZWL");
+}
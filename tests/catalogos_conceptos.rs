@@ -0,0 +1,38 @@
+#![cfg(feature = "catalogos-conceptos")]
+
+use std::fs;
+
+use cfdi::catalogos::conceptos::{ClaveProdServ, ClaveUnidad, Inclusion};
+use cfdi::parse_cfdi;
+
+#[test]
+fn buscar_claves_de_conceptos() {
+    let xml = fs::read_to_string("tests/data/nomina.xml").unwrap();
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let concepto = &cfdi.conceptos.concepto[0];
+
+    let clave = concepto.clave_prod_serv_sat().unwrap();
+    assert_eq!(
+        clave.descripcion,
+        "Servicios de contabilidad de sueldos y salarios"
+    );
+    assert_eq!(clave.incluir_iva_trasladado, Inclusion::Si);
+    assert_eq!(clave.incluir_ieps_trasladado, Inclusion::No);
+    assert!(clave.vigente_en(cfdi.fecha));
+
+    assert_eq!(concepto.clave_unidad_sat().unwrap().nombre, "Actividad");
+}
+
+#[test]
+fn claves_inexistentes() {
+    assert!(ClaveProdServ::buscar("99999999").is_none());
+    assert!(ClaveUnidad::buscar("ZZZ").is_none());
+}
+
+#[test]
+fn vigencia() {
+    let clave = ClaveProdServ::buscar("01010101").unwrap();
+    assert_eq!(clave.incluir_iva_trasladado, Inclusion::Opcional);
+    assert!(clave.vigente_en("2022-01-01T00:00:00".parse().unwrap()));
+    assert!(!clave.vigente_en("2021-12-31T23:59:59".parse().unwrap()));
+}
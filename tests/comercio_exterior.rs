@@ -0,0 +1,126 @@
+use std::fs;
+
+use cfdi::complementos::cce20::ComercioExterior;
+use cfdi::{cadena_original, parse_cfdi, Comprobante, Decimal};
+
+fn leer() -> String {
+    fs::read_to_string("tests/data/comercio_exterior.xml").unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn comercio_exterior(cfdi: &Comprobante) -> &ComercioExterior {
+    let complemento = cfdi.complemento.as_ref().unwrap();
+    complemento.comercio_exterior.as_ref().unwrap()
+}
+
+#[test]
+fn mercancias_de_cada_concepto() {
+    let cfdi = parse_cfdi(&leer()).unwrap();
+    let cce = comercio_exterior(&cfdi);
+
+    let fracciones: Vec<Vec<_>> = cfdi
+        .conceptos
+        .concepto
+        .iter()
+        .map(|concepto| {
+            cce.mercancias_de(concepto)
+                .iter()
+                .map(|m| m.fraccion_arancelaria.as_deref().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(fracciones, [["1806900100"], ["0901210100"]]);
+
+    let chocolate = cce.mercancias_de(&cfdi.conceptos.concepto[0])[0];
+    assert_eq!(chocolate.cantidad_aduana, Some(decimal("100")));
+    assert_eq!(chocolate.descripciones_especificas[0].marca, "Kemper");
+}
+
+#[test]
+fn mercancias_sin_concepto() {
+    let xml = leer().replace(
+        r#"NoIdentificacion="CAFE-02" Cantidad="50""#,
+        r#"NoIdentificacion="CAFE-03" Cantidad="50""#,
+    );
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let cce = comercio_exterior(&cfdi);
+    assert!(cce.mercancias_de(&cfdi.conceptos.concepto[1]).is_empty());
+
+    let mut concepto = cfdi.conceptos.concepto[0].clone();
+    concepto.no_identificacion = None;
+    assert!(cce.mercancias_de(&concepto).is_empty());
+}
+
+#[test]
+fn varias_mercancias_por_concepto() {
+    let xml = leer().replace(
+        "</cce20:Mercancias>",
+        r#"<cce20:Mercancia NoIdentificacion="CHOC-01" FraccionArancelaria="1806320100" ValorDolares="0.00"/>
+      </cce20:Mercancias>"#,
+    );
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let cce = comercio_exterior(&cfdi);
+
+    let mercancias = cce.mercancias_de(&cfdi.conceptos.concepto[0]);
+    assert_eq!(mercancias.len(), 2);
+    assert_eq!(
+        mercancias[1].fraccion_arancelaria.as_deref(),
+        Some("1806320100")
+    );
+    assert_eq!(cce.get_mercancias().len(), 3);
+}
+
+#[test]
+fn totales_en_dolares() {
+    let cfdi = parse_cfdi(&leer()).unwrap();
+    let cce = comercio_exterior(&cfdi);
+
+    let total: Decimal = cce.get_mercancias().iter().map(|m| m.valor_dolares).sum();
+    assert_eq!(total, cce.total_usd);
+    assert_eq!(
+        (cfdi.subtotal / cce.tipo_cambio_usd).round_dp(2),
+        cce.total_usd
+    );
+}
+
+#[test]
+fn emisor_receptor_y_destinatarios() {
+    let cfdi = parse_cfdi(&leer()).unwrap();
+    let cce = comercio_exterior(&cfdi);
+
+    let emisor = cce.emisor.as_ref().unwrap().domicilio.as_ref().unwrap();
+    assert_eq!(
+        (emisor.estado.as_str(), emisor.pais.as_str()),
+        ("JAL", "MEX")
+    );
+    let receptor = cce.receptor.as_ref().unwrap();
+    assert_eq!(receptor.num_reg_id_trib.as_deref(), Some("121585958"));
+    assert_eq!(
+        receptor.domicilio.as_ref().unwrap().municipio.as_deref(),
+        Some("Laredo")
+    );
+    assert_eq!(cce.destinatario.len(), 1);
+    assert_eq!(
+        cce.destinatario[0].nombre.as_deref(),
+        Some("ACME WAREHOUSE")
+    );
+    assert!(cce.propietario.is_empty());
+}
+
+#[test]
+fn cadena_original_del_complemento() {
+    let xml = leer();
+    let esperada = fs::read_to_string("tests/data/comercio_exterior.txt").unwrap();
+    assert_eq!(cadena_original(&xml).unwrap(), esperada);
+    assert!(esperada.ends_with(
+        "|2.0|A1|0|FOB|17.0000|217.65|Av. Juárez|120|JAL|MEX|45079|121585958|Main St|500|Laredo|TX|USA|78040|987654321|ACME WAREHOUSE|Industrial Blvd|TX|USA|78045|CHOC-01|1806900100|100|06|1.1765|117.65|Kemper|Amargo 70|CAFE-02|0901210100|50|01|2.00|100.00||"
+    ));
+
+    let cfdi = parse_cfdi(&xml).unwrap();
+    assert_eq!(cfdi.validate(), vec![]);
+    let generado = cfdi.to_xml().unwrap();
+    assert_eq!(cadena_original(&generado).unwrap(), esperada);
+}
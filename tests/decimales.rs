@@ -0,0 +1,54 @@
+use std::fs;
+
+use cfdi::parse_cfdi;
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+#[test]
+fn conservar_decimales_al_leer() {
+    let cfdi = parse_cfdi(&leer("ingreso")).unwrap();
+
+    assert_eq!(cfdi.subtotal.to_string(), "2500.00");
+    assert_eq!(cfdi.descuento.unwrap().to_string(), "100.00");
+    assert_eq!(cfdi.total.to_string(), "2514.00");
+
+    let conceptos = &cfdi.conceptos.concepto;
+    assert_eq!(conceptos[0].cantidad.to_string(), "1");
+    assert_eq!(conceptos[0].valor_unitario.to_string(), "2000.00");
+    assert_eq!(conceptos[1].cantidad.to_string(), "2.5");
+
+    let traslado = &cfdi.impuestos.as_ref().unwrap().get_traslados()[0];
+    assert_eq!(traslado.base.unwrap().to_string(), "1900.00");
+    assert_eq!(traslado.tasa_o_cuota.unwrap().to_string(), "0.160000");
+    assert_eq!(traslado.importe.unwrap().to_string(), "304.00");
+}
+
+#[test]
+fn conservar_decimales_al_escribir() {
+    let xml = leer("ingreso");
+    let generado = parse_cfdi(&xml).unwrap().to_xml().unwrap();
+
+    for atributo in [
+        r#"SubTotal="2500.00""#,
+        r#"Descuento="100.00""#,
+        r#"Total="2514.00""#,
+        r#"Cantidad="1""#,
+        r#"Cantidad="2.5""#,
+        r#"ValorUnitario="2000.00""#,
+        r#"Base="1900.00""#,
+        r#"TasaOCuota="0.160000""#,
+        r#"TotalImpuestosTrasladados="304.00""#,
+    ] {
+        assert!(xml.contains(atributo), "{atributo}");
+        assert!(generado.contains(atributo), "{atributo}");
+    }
+
+    let releido = parse_cfdi(&generado).unwrap();
+    assert_eq!(releido.total.to_string(), "2514.00");
+    assert_eq!(
+        releido.cadena_original().unwrap(),
+        fs::read_to_string("tests/data/ingreso.txt").unwrap()
+    );
+}
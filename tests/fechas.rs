@@ -0,0 +1,116 @@
+use std::fs;
+
+use cfdi::fechas::zona_horaria;
+use cfdi::{parse_cfdi, Comprobante, NaiveDateTime};
+use chrono::TimeDelta;
+use chrono_tz::America;
+
+fn timbrado() -> Comprobante {
+    parse_cfdi(&fs::read_to_string("tests/data/timbrado.xml").unwrap()).unwrap()
+}
+
+fn fecha(fecha: &str) -> NaiveDateTime {
+    fecha.parse().unwrap()
+}
+
+#[test]
+fn leer_y_escribir_fechas() {
+    let cfdi = timbrado();
+    assert_eq!(cfdi.fecha, fecha("2024-05-20T13:45:10"));
+    assert_eq!(
+        cfdi.get_fecha_timbrado(),
+        Some(fecha("2024-05-20T13:46:02"))
+    );
+
+    let xml = cfdi.to_xml().unwrap();
+    assert!(xml.contains(r#"Fecha="2024-05-20T13:45:10""#));
+    assert!(xml.contains(r#"FechaTimbrado="2024-05-20T13:46:02""#));
+}
+
+#[test]
+fn fechas_invalidas() {
+    let xml = fs::read_to_string("tests/data/sin_sellar.xml").unwrap();
+    for invalida in [
+        "2024-05-20",
+        "2024-05-20 13:45:10",
+        "2024-5-20T13:45:10",
+        "2024-05-20T13:45:10.123",
+        "2024-02-30T13:45:10",
+    ] {
+        let xml = xml.replace("2024-05-20T13:45:10", invalida);
+        assert!(parse_cfdi(&xml).is_err(), "{invalida}");
+    }
+}
+
+#[test]
+fn zonas_horarias() {
+    assert_eq!(zona_horaria("45079"), America::Mexico_City);
+    assert_eq!(zona_horaria("22000"), America::Tijuana);
+    assert_eq!(zona_horaria("23000"), America::Mazatlan);
+    assert_eq!(zona_horaria("83000"), America::Hermosillo);
+    assert_eq!(zona_horaria("77500"), America::Cancun);
+    assert_eq!(zona_horaria("32000"), America::Ciudad_Juarez);
+    assert_eq!(zona_horaria("31000"), America::Chihuahua);
+    assert_eq!(zona_horaria("88500"), America::Matamoros);
+    assert_eq!(zona_horaria("63735"), America::Bahia_Banderas);
+    assert_eq!(zona_horaria("63000"), America::Mazatlan);
+    assert_eq!(zona_horaria(""), America::Mexico_City);
+}
+
+#[test]
+fn fecha_en_la_zona_del_lugar_de_expedicion() {
+    let mut cfdi = timbrado();
+    assert_eq!(
+        cfdi.fecha_con_zona().to_rfc3339(),
+        "2024-05-20T13:45:10-06:00"
+    );
+
+    // Tijuana usa horario de verano
+    cfdi.lugar_expedicion = "22000".to_string();
+    assert_eq!(
+        cfdi.fecha_con_zona().to_rfc3339(),
+        "2024-05-20T13:45:10-07:00"
+    );
+
+    // Sonora no
+    cfdi.lugar_expedicion = "83000".to_string();
+    assert_eq!(
+        cfdi.fecha_con_zona().to_rfc3339(),
+        "2024-05-20T13:45:10-07:00"
+    );
+    cfdi.fecha = fecha("2024-01-15T10:00:00");
+    assert_eq!(
+        cfdi.fecha_con_zona().to_rfc3339(),
+        "2024-01-15T10:00:00-07:00"
+    );
+}
+
+#[test]
+fn plazo_de_timbrado() {
+    let mut cfdi = timbrado();
+    assert_eq!(cfdi.tiempo_para_timbrar(), Some(TimeDelta::seconds(52)));
+    assert_eq!(cfdi.timbrado_en_plazo(), Some(true));
+
+    cfdi.fecha = fecha("2024-05-17T13:46:02");
+    assert_eq!(cfdi.timbrado_en_plazo(), Some(true));
+    cfdi.fecha = fecha("2024-05-17T13:46:01");
+    assert_eq!(cfdi.timbrado_en_plazo(), Some(false));
+
+    // 14:00 en Tijuana son las 15:00 en la Zona Centro, después del timbrado
+    cfdi.fecha = fecha("2024-05-20T14:00:00");
+    cfdi.lugar_expedicion = "22000".to_string();
+    assert_eq!(cfdi.tiempo_para_timbrar(), Some(-TimeDelta::seconds(4438)));
+    assert_eq!(cfdi.timbrado_en_plazo(), Some(false));
+
+    cfdi.complemento = None;
+    assert_eq!(cfdi.timbrado_en_plazo(), None);
+}
+
+#[test]
+fn periodo_fiscal() {
+    let mut cfdi = timbrado();
+    assert_eq!(cfdi.periodo_fiscal(), (2024, 5));
+
+    cfdi.fecha = fecha("2023-12-31T23:59:59");
+    assert_eq!(cfdi.periodo_fiscal(), (2023, 12));
+}
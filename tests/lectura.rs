@@ -0,0 +1,238 @@
+use std::fs;
+
+use cfdi::{parse_cfdi, parse_retenciones, ErrorLectura};
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+/// Posición en `xml` de la `n`-ésima (desde 0) aparición de `texto`
+fn posicion(xml: &str, texto: &str, n: usize) -> usize {
+    xml.match_indices(texto).nth(n).unwrap().0
+}
+
+#[test]
+fn xml_invalido() {
+    for xml in [
+        "no es xml <",
+        "<cfdi:Comprobante Version=\"4.0\"><cfdi:Emisor></cfdi:Receptor>",
+    ] {
+        let error = parse_cfdi(xml).unwrap_err();
+        assert!(matches!(error, ErrorLectura::XmlInvalido { .. }), "{error}");
+    }
+
+    let xml = leer("ingreso").replace("</cfdi:Conceptos>", "");
+    match parse_cfdi(&xml).unwrap_err() {
+        ErrorLectura::XmlInvalido { ruta, posicion, .. } => {
+            assert_eq!(ruta, "Comprobante/Conceptos");
+            assert!(xml[posicion..].starts_with("</cfdi:Comprobante>"));
+        }
+        otro => panic!("{otro}"),
+    }
+
+    let xml = leer("ingreso").replace(r#"Importe="500.00""#, r#"Importe="500.00" Importe="1""#);
+    let error = parse_cfdi(&xml).unwrap_err();
+    assert!(matches!(error, ErrorLectura::XmlInvalido { .. }), "{error}");
+    assert_eq!(error.ruta(), "Comprobante/Conceptos/Concepto");
+    assert_eq!(error.posicion(), posicion(&xml, "<cfdi:Concepto ", 1));
+}
+
+#[test]
+fn no_es_cfdi() {
+    let error = parse_cfdi(&leer("retenciones_plataformas")).unwrap_err();
+    assert_eq!(
+        error,
+        ErrorLectura::NoEsCfdi {
+            ruta: "Retenciones".to_string(),
+            posicion: 39,
+            esperado: "Comprobante",
+        }
+    );
+
+    let error = parse_retenciones(&leer("ingreso")).unwrap_err();
+    assert!(matches!(error, ErrorLectura::NoEsCfdi { .. }), "{error}");
+    assert_eq!(error.ruta(), "Comprobante");
+
+    let error = parse_cfdi("").unwrap_err();
+    assert!(matches!(error, ErrorLectura::NoEsCfdi { .. }), "{error}");
+    assert_eq!(error.ruta(), "");
+}
+
+#[test]
+fn version_no_soportada() {
+    let xml = leer("ingreso").replace(r#"Version="4.0""#, r#"Version="5.0""#);
+    assert_eq!(
+        parse_cfdi(&xml).unwrap_err(),
+        ErrorLectura::VersionNoSoportada {
+            ruta: "Comprobante".to_string(),
+            posicion: 39,
+            version: Some("5.0".to_string()),
+        }
+    );
+
+    let xml = leer("ingreso").replace(r#" Version="4.0""#, "");
+    match parse_cfdi(&xml).unwrap_err() {
+        ErrorLectura::VersionNoSoportada { version, .. } => assert_eq!(version, None),
+        otro => panic!("{otro}"),
+    }
+}
+
+#[test]
+fn atributo_faltante() {
+    let xml = leer("ingreso").replacen(r#" TipoFactor="Exento""#, "", 1);
+    assert_eq!(
+        parse_cfdi(&xml).unwrap_err(),
+        ErrorLectura::AtributoFaltante {
+            ruta: "Comprobante/Conceptos/Concepto[2]/Impuestos/Traslados/Traslado[1]".to_string(),
+            posicion: posicion(&xml, "<cfdi:Traslado ", 1),
+            atributo: "TipoFactor".to_string(),
+        }
+    );
+
+    let xml = leer("ingreso").replace(r#" ClaveUnidad="H87""#, "");
+    assert_eq!(
+        parse_cfdi(&xml).unwrap_err(),
+        ErrorLectura::AtributoFaltante {
+            ruta: "Comprobante/Conceptos/Concepto[2]".to_string(),
+            posicion: posicion(&xml, "<cfdi:Concepto ", 1),
+            atributo: "ClaveUnidad".to_string(),
+        }
+    );
+}
+
+#[test]
+fn atributos_requeridos_en_40() {
+    let faltante = |xml: &str| match parse_cfdi(xml).unwrap_err() {
+        ErrorLectura::AtributoFaltante {
+            ruta,
+            posicion,
+            atributo,
+        } => (ruta, posicion, atributo),
+        otro => panic!("{otro}"),
+    };
+
+    let xml = leer("ingreso").replace(r#" Exportacion="01""#, "");
+    assert_eq!(
+        faltante(&xml),
+        ("Comprobante".to_string(), 39, "Exportacion".to_string())
+    );
+
+    let xml = leer("ingreso").replace(r#" RegimenFiscalReceptor="601""#, "");
+    assert_eq!(
+        faltante(&xml),
+        (
+            "Comprobante/Receptor".to_string(),
+            posicion(&xml, "<cfdi:Receptor", 0),
+            "RegimenFiscalReceptor".to_string()
+        )
+    );
+
+    let xml = leer("ingreso").replacen(r#" ObjetoImp="02""#, "", 2);
+    assert_eq!(
+        faltante(&xml),
+        (
+            "Comprobante/Conceptos/Concepto[1]".to_string(),
+            posicion(&xml, "<cfdi:Concepto ", 0),
+            "ObjetoImp".to_string()
+        )
+    );
+
+    let mut xml = leer("ingreso");
+    let base = xml.rfind(r#"Base="500.00" "#).unwrap();
+    xml.replace_range(base..base + r#"Base="500.00" "#.len(), "");
+    assert_eq!(
+        faltante(&xml),
+        (
+            "Comprobante/Impuestos/Traslados/Traslado[2]".to_string(),
+            posicion(&xml, "<cfdi:Traslado ", 3),
+            "Base".to_string()
+        )
+    );
+
+    let xml = leer("ingreso").replace(r#" RfcProvCertif="SAT970701NN3""#, "");
+    assert_eq!(
+        faltante(&xml),
+        (
+            "Comprobante/Complemento/TimbreFiscalDigital".to_string(),
+            posicion(&xml, "<tfd:TimbreFiscalDigital", 0),
+            "RfcProvCertif".to_string()
+        )
+    );
+}
+
+#[test]
+fn nodo_faltante() {
+    let xml = leer("ingreso");
+    let inicio = posicion(&xml, "<cfdi:Emisor", 0);
+    let fin = posicion(&xml, "<cfdi:Receptor", 0);
+    let xml = format!("{}{}", &xml[..inicio], &xml[fin..]);
+
+    assert_eq!(
+        parse_cfdi(&xml).unwrap_err(),
+        ErrorLectura::NodoFaltante {
+            ruta: "Comprobante".to_string(),
+            posicion: 39,
+            nodo: "Emisor".to_string(),
+        }
+    );
+}
+
+#[test]
+fn valor_invalido() {
+    let xml = leer("ingreso").replace(r#"Cantidad="2.5""#, r#"Cantidad="2,5""#);
+    match parse_cfdi(&xml).unwrap_err() {
+        ErrorLectura::ValorInvalido {
+            ruta,
+            posicion: p,
+            atributo,
+            valor,
+            ..
+        } => {
+            assert_eq!(ruta, "Comprobante/Conceptos/Concepto[2]");
+            assert_eq!(p, posicion(&xml, "<cfdi:Concepto ", 1));
+            assert_eq!(atributo, "Cantidad");
+            assert_eq!(valor.as_deref(), Some("2,5"));
+        }
+        otro => panic!("{otro}"),
+    }
+
+    let xml = leer("ingreso").replace("2024-05-20T13:46:02", "2024-05-20 13:46");
+    let error = parse_cfdi(&xml).unwrap_err();
+    assert!(
+        matches!(error, ErrorLectura::ValorInvalido { .. }),
+        "{error}"
+    );
+    assert_eq!(error.ruta(), "Comprobante/Complemento/TimbreFiscalDigital");
+    assert_eq!(
+        error.posicion(),
+        posicion(&xml, "<tfd:TimbreFiscalDigital", 0)
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Comprobante/Complemento/TimbreFiscalDigital (byte {}): valor inválido en \
+             FechaTimbrado (2024-05-20 13:46): Fecha inválida: 2024-05-20 13:46",
+            error.posicion()
+        )
+    );
+
+    let xml = leer("retenciones_plataformas").replace(r#"MontoRet="80.00""#, r#"MontoRet="""#);
+    match parse_retenciones(&xml).unwrap_err() {
+        ErrorLectura::ValorInvalido { ruta, atributo, .. } => {
+            assert_eq!(ruta, "Retenciones/Totales/ImpRetenidos[2]");
+            assert_eq!(atributo, "MontoRet");
+        }
+        otro => panic!("{otro}"),
+    }
+}
+
+#[test]
+fn errores_en_cfdi_32() {
+    let xml = leer("cfdi32").replace(r#"importe="1500.00""#, r#"importe="$1500""#);
+    let error = parse_cfdi(&xml).unwrap_err();
+    assert!(
+        matches!(error, ErrorLectura::ValorInvalido { .. }),
+        "{error}"
+    );
+    assert_eq!(error.ruta(), "Comprobante/Conceptos/Concepto[1]");
+}
@@ -0,0 +1,144 @@
+use std::fs;
+
+use cfdi::complementos::nomina12::Nomina;
+use cfdi::{parse_cfdi, Decimal};
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn nominas(xml: &str) -> Vec<Nomina> {
+    let cfdi = parse_cfdi(xml).unwrap();
+    cfdi.complemento.unwrap().nomina
+}
+
+#[test]
+fn percepciones_y_deducciones_por_clave() {
+    let nomina = &nominas(&leer("nomina"))[0];
+
+    let percepciones: Vec<_> = nomina
+        .percepciones_por_clave()
+        .into_iter()
+        .map(|(clave, importe)| (clave, importe.to_string()))
+        .collect();
+    assert_eq!(
+        percepciones,
+        [
+            ("P001".to_string(), "9500.00".to_string()),
+            ("P019".to_string(), "500.00".to_string()),
+        ]
+    );
+
+    let deducciones = nomina.deducciones_por_clave();
+    assert_eq!(deducciones["D001"], decimal("300.00"));
+    assert_eq!(deducciones["D002"], decimal("1200.00"));
+    assert_eq!(deducciones.len(), 2);
+}
+
+#[test]
+fn sumar_claves_repetidas() {
+    let xml = leer("nomina").replace(
+        r#"<nomina12:Percepciones TotalSueldos="10000.00" TotalGravado="9500.00" TotalExento="500.00">"#,
+        r#"<nomina12:Percepciones TotalSueldos="10000.00" TotalGravado="9500.00" TotalExento="500.00">
+        <nomina12:Percepcion TipoPercepcion="001" Clave="P001" Concepto="Sueldo retroactivo" ImporteGravado="250.00" ImporteExento="0.00"/>"#,
+    );
+    let nomina = &nominas(&xml)[0];
+
+    assert_eq!(nomina.get_percepciones().len(), 3);
+    assert_eq!(nomina.percepciones_por_clave()["P001"], decimal("9750.00"));
+}
+
+#[test]
+fn totales_de_la_nomina() {
+    let cfdi = parse_cfdi(&leer("nomina")).unwrap();
+    let nomina = &cfdi.complemento.as_ref().unwrap().nomina[0];
+
+    let percepciones: Decimal = nomina.get_percepciones().iter().map(|p| p.importe()).sum();
+    assert_eq!(Some(percepciones), nomina.total_percepciones);
+    let gravado: Decimal = nomina
+        .get_percepciones()
+        .iter()
+        .map(|p| p.importe_gravado)
+        .sum();
+    let totales = nomina.percepciones.as_ref().unwrap();
+    assert_eq!(gravado, totales.total_gravado);
+
+    let deducciones: Decimal = nomina.deducciones_por_clave().values().sum();
+    assert_eq!(Some(deducciones), nomina.total_deducciones);
+    let totales = nomina.deducciones.as_ref().unwrap();
+    assert_eq!(
+        totales.total_impuestos_retenidos.unwrap() + totales.total_otras_deducciones.unwrap(),
+        deducciones
+    );
+
+    let otros_pagos: Decimal = nomina.get_otros_pagos().iter().map(|o| o.importe).sum();
+    assert_eq!(Some(otros_pagos), nomina.total_otros_pagos);
+    assert_eq!(
+        nomina.get_otros_pagos()[0]
+            .subsidio_al_empleo
+            .as_ref()
+            .unwrap()
+            .subsidio_causado,
+        decimal("200.00")
+    );
+
+    assert_eq!(percepciones + otros_pagos, cfdi.subtotal);
+    assert_eq!(Some(deducciones), cfdi.descuento);
+    assert_eq!(percepciones + otros_pagos - deducciones, cfdi.total);
+}
+
+#[test]
+fn horas_extra() {
+    let nomina = &nominas(&leer("nomina"))[0];
+    let percepcion = nomina
+        .get_percepciones()
+        .into_iter()
+        .find(|p| p.tipo_percepcion == "019")
+        .unwrap();
+
+    let horas = &percepcion.horas_extra[0];
+    assert_eq!((horas.dias, horas.horas_extra), (1, 3));
+    assert_eq!(horas.tipo_horas, "Dobles");
+    assert_eq!(horas.importe_pagado, percepcion.importe());
+}
+
+#[test]
+fn nomina_ordinaria_y_extraordinaria() {
+    let xml = leer("nomina").replace(
+        "</nomina12:Nomina>",
+        r#"</nomina12:Nomina>
+    <nomina12:Nomina Version="1.2" TipoNomina="E" FechaPago="2024-01-15" FechaInicialPago="2024-01-15" FechaFinalPago="2024-01-15" NumDiasPagados="1" TotalPercepciones="3000.00">
+      <nomina12:Receptor Curp="XOJI740919MDFDMN06" TipoContrato="01" TipoRegimen="02" NumEmpleado="120" PeriodicidadPago="99" ClaveEntFed="JAL"/>
+      <nomina12:Percepciones TotalSueldos="3000.00" TotalGravado="3000.00" TotalExento="0.00">
+        <nomina12:Percepcion TipoPercepcion="002" Clave="P002" Concepto="Aguinaldo" ImporteGravado="3000.00" ImporteExento="0.00"/>
+      </nomina12:Percepciones>
+    </nomina12:Nomina>"#,
+    );
+    let nominas = nominas(&xml);
+
+    let tipos: Vec<_> = nominas.iter().map(|n| n.tipo_nomina.as_str()).collect();
+    assert_eq!(tipos, ["O", "E"]);
+    assert_eq!(
+        nominas[1].percepciones_por_clave()["P002"],
+        decimal("3000.00")
+    );
+    assert!(nominas[1].get_deducciones().is_empty());
+    assert!(nominas[1].deducciones_por_clave().is_empty());
+}
+
+#[test]
+fn escribir_nomina() {
+    let cfdi = parse_cfdi(&leer("nomina")).unwrap();
+    let generado = cfdi.to_xml().unwrap();
+
+    assert!(generado.contains(r#"<nomina12:Percepcion TipoPercepcion="019" Clave="P019""#));
+    assert!(generado.contains(r#"Antigüedad="P210W""#));
+    assert_eq!(
+        parse_cfdi(&generado).unwrap().cadena_original().unwrap(),
+        fs::read_to_string("tests/data/nomina.txt").unwrap()
+    );
+}
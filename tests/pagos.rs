@@ -0,0 +1,122 @@
+use std::fs;
+
+use cfdi::catalogos::{FormaPago, Impuesto, Moneda, ObjetoImp, TipoFactor};
+use cfdi::complementos::pagos20::Pagos;
+use cfdi::{parse_cfdi, Decimal};
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn pagos(xml: &str) -> Pagos {
+    let cfdi = parse_cfdi(xml).unwrap();
+    cfdi.complemento.unwrap().pagos.unwrap()
+}
+
+#[test]
+fn leer_pago() {
+    let pagos = pagos(&leer("pago"));
+
+    let totales = pagos.totales.as_ref().unwrap();
+    assert_eq!(totales.monto_total_pagos, decimal("1160.00"));
+    assert_eq!(totales.total_traslados_base_iva16, Some(decimal("1000.00")));
+    assert_eq!(totales.total_retenciones_iva, None);
+
+    let pago = &pagos.pago[0];
+    assert_eq!(pago.forma_de_pago, FormaPago::TransferenciaElectronica);
+    assert_eq!(pago.moneda, Moneda::Mxn);
+    assert_eq!(pago.num_operacion.as_deref(), Some("REF123"));
+
+    let docto = &pago.docto_relacionado[0];
+    assert_eq!(docto.moneda, Moneda::Mxn);
+    assert_eq!(docto.objeto_imp, Some(ObjetoImp::SiObjeto));
+    assert_eq!(docto.num_parcialidad, Some(1));
+    assert_eq!(
+        docto.imp_saldo_ant.unwrap() - docto.imp_pagado.unwrap(),
+        docto.imp_saldo_insoluto.unwrap()
+    );
+
+    let impuestos = docto.impuestos.as_ref().unwrap();
+    let traslado = &impuestos.traslados.as_ref().unwrap().traslado[0];
+    assert_eq!(traslado.impuesto, Impuesto::Iva);
+    assert_eq!(traslado.tipo_factor, TipoFactor::Tasa);
+    assert_eq!(traslado.importe, Some(decimal("160.00")));
+
+    let impuestos = pago.impuestos.as_ref().unwrap();
+    let traslado = &impuestos.traslados.as_ref().unwrap().traslado[0];
+    assert_eq!(traslado.impuesto, Impuesto::Iva);
+    assert_eq!(traslado.base, decimal("1000.00"));
+}
+
+#[test]
+fn pagos_de_un_mismo_documento() {
+    let xml = leer("pago");
+    let inicio = xml.find("<pago20:Pago ").unwrap();
+    let fin = xml.find("</pago20:Pago>").unwrap() + "</pago20:Pago>".len();
+    let segundo = xml[inicio..fin]
+        .replace(r#"FormaDePagoP="03""#, r#"FormaDePagoP="01""#)
+        .replace(r#"NumParcialidad="1""#, r#"NumParcialidad="2""#)
+        .replace(
+            "8A6D7E24-1B4C-4F3A-9D2E-5C7B8A9F0E1D",
+            "8a6d7e24-1b4c-4f3a-9d2e-5c7b8a9f0e1d",
+        );
+    let xml = format!("{}{segundo}{}", &xml[..fin], &xml[fin..]);
+
+    let pagos = pagos(&xml);
+    let aplicados = pagos.buscar_documento("8A6D7E24-1B4C-4F3A-9D2E-5C7B8A9F0E1D");
+    let formas: Vec<_> = aplicados
+        .iter()
+        .map(|(pago, docto)| (pago.forma_de_pago.clone(), docto.num_parcialidad))
+        .collect();
+    assert_eq!(
+        formas,
+        [
+            (FormaPago::TransferenciaElectronica, Some(1)),
+            (FormaPago::Efectivo, Some(2)),
+        ]
+    );
+    assert!(pagos
+        .buscar_documento("00000000-0000-0000-0000-000000000000")
+        .is_empty());
+}
+
+#[test]
+fn pago_en_moneda_extranjera() {
+    let xml = leer("pago").replace(
+        r#"MonedaP="MXN" TipoCambioP="1""#,
+        r#"MonedaP="USD" TipoCambioP="17.0125""#,
+    );
+    let pago = &pagos(&xml).pago[0];
+
+    assert_eq!(pago.moneda, Moneda::Usd);
+    assert_eq!(pago.tipo_cambio, Some(decimal("17.0125")));
+    assert_eq!(pago.docto_relacionado[0].moneda, Moneda::Mxn);
+}
+
+#[test]
+fn escribir_claves_del_catalogo() {
+    let xml = leer("pago");
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let generado = cfdi.to_xml().unwrap();
+
+    for atributo in [
+        r#"FormaDePagoP="03""#,
+        r#"MonedaP="MXN""#,
+        r#"MonedaDR="MXN""#,
+        r#"ObjetoImpDR="02""#,
+        r#"ImpuestoDR="002""#,
+        r#"TipoFactorDR="Tasa""#,
+        r#"ImpuestoP="002""#,
+        r#"TipoFactorP="Tasa""#,
+    ] {
+        assert!(generado.contains(atributo), "{atributo}");
+    }
+    assert_eq!(
+        parse_cfdi(&generado).unwrap().cadena_original().unwrap(),
+        fs::read_to_string("tests/data/pago.txt").unwrap()
+    );
+}
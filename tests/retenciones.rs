@@ -0,0 +1,119 @@
+use std::fs;
+
+use cfdi::catalogos::{Impuesto, RegimenFiscal};
+use cfdi::retenciones::Retenciones;
+use cfdi::{parse_retenciones, Decimal};
+
+fn leer(nombre: &str) -> Retenciones {
+    parse_retenciones(&fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()).unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+#[test]
+fn leer_retenciones() {
+    let retenciones = leer("retenciones_plataformas");
+
+    assert_eq!(retenciones.version, "2.0");
+    assert_eq!(retenciones.folio_int.as_deref(), Some("RET-0042"));
+    assert_eq!(retenciones.fecha_exp.to_string(), "2024-02-05 11:30:00");
+    assert_eq!(retenciones.cve_retenc, "26");
+
+    assert_eq!(retenciones.emisor.rfc, "EKU9003173C9");
+    assert_eq!(
+        retenciones.emisor.regimen_fiscal,
+        RegimenFiscal::GeneralDeLeyPersonasMorales
+    );
+
+    assert_eq!(retenciones.receptor.nacionalidad, "Nacional");
+    assert_eq!(retenciones.receptor.rfc().unwrap(), "XOJI740919U48");
+    assert_eq!(retenciones.receptor.nombre(), Some("INGRID XODAR JIMENEZ"));
+    let nacional = retenciones.receptor.nacional.as_ref().unwrap();
+    assert_eq!(nacional.curp.as_deref(), Some("XOJI740919MDFDMN05"));
+    assert_eq!(nacional.domicilio_fiscal, "88965");
+
+    assert_eq!(retenciones.periodo.mes_ini, "01");
+    assert_eq!(retenciones.periodo.ejercicio, "2024");
+
+    let totales = &retenciones.totales;
+    assert_eq!(totales.monto_tot_operacion, decimal("1000.00"));
+    assert_eq!(totales.monto_tot_ret, decimal("90.00"));
+    assert_eq!(totales.imp_retenidos.len(), 2);
+    assert_eq!(totales.imp_retenidos[0].impuesto, Some(Impuesto::Isr));
+    assert_eq!(totales.imp_retenidos[1].impuesto, Some(Impuesto::Iva));
+    let suma: Decimal = totales.imp_retenidos.iter().map(|i| i.monto).sum();
+    assert_eq!(suma, totales.monto_tot_ret);
+}
+
+#[test]
+fn timbre_de_retenciones() {
+    let mut retenciones = leer("retenciones_plataformas");
+
+    assert_eq!(
+        retenciones.get_uuid().as_deref(),
+        Some("4F1C2B7E-3D6A-4E8B-9C0D-1A2B3C4D5E6F")
+    );
+    assert_eq!(
+        retenciones.get_fecha_timbrado(),
+        Some("2024-02-05T11:31:12".parse().unwrap())
+    );
+
+    retenciones.complemento = None;
+    assert_eq!(retenciones.get_uuid(), None);
+    assert_eq!(retenciones.get_fecha_timbrado(), None);
+}
+
+#[test]
+fn plataformas_tecnologicas() {
+    let retenciones = leer("retenciones_plataformas");
+    let complemento = retenciones.complemento.unwrap();
+    let plataformas = complemento.plataformas_tecnologicas.unwrap();
+
+    assert_eq!(plataformas.version, "2.0");
+    assert_eq!(plataformas.num_serv, 2);
+    assert_eq!(plataformas.total_iva_trasladado, decimal("160.00"));
+    assert_eq!(plataformas.mon_total_contribucion_gubernamental, None);
+
+    let servicios = &plataformas.servicios.detalles_del_servicio;
+    assert_eq!(servicios.len(), 2);
+    assert_eq!(servicios[0].fecha_serv, "2024-01-12");
+    assert_eq!(servicios[0].impuestos_trasladados.importe, decimal("96.00"));
+    let comision = servicios[1].comision_del_servicio.as_ref().unwrap();
+    assert_eq!(comision.importe, Some(decimal("60.00")));
+
+    let iva: Decimal = servicios
+        .iter()
+        .map(|s| s.impuestos_trasladados.importe)
+        .sum();
+    assert_eq!(iva, plataformas.total_iva_trasladado);
+}
+
+#[test]
+fn dividendos_y_enajenacion_de_acciones() {
+    let retenciones = leer("retenciones_dividendos");
+
+    assert_eq!(retenciones.receptor.nacionalidad, "Extranjero");
+    assert_eq!(retenciones.receptor.rfc(), None);
+    assert_eq!(retenciones.receptor.nombre(), Some("ACME INC"));
+    let extranjero = retenciones.receptor.extranjero.as_ref().unwrap();
+    assert_eq!(extranjero.num_reg_id_trib.as_deref(), Some("121585958"));
+    assert_eq!(retenciones.get_uuid(), None);
+
+    let complemento = retenciones.complemento.unwrap();
+    let dividendos = complemento.dividendos.unwrap();
+    let divid_o_util = dividendos.divid_o_util.unwrap();
+    assert_eq!(divid_o_util.cve_tip_div_o_util, "01");
+    assert_eq!(divid_o_util.mont_isr_acred_ret_mexico, decimal("2000.00"));
+    assert_eq!(divid_o_util.mont_ret_ext_div_ext, None);
+    assert_eq!(
+        dividendos.remanente.unwrap().proporcion_rem,
+        Some(decimal("0.25"))
+    );
+
+    let enajenacion = complemento.enajenacion_de_acciones.unwrap();
+    assert_eq!(enajenacion.ganancia, decimal("1500.00"));
+    assert_eq!(enajenacion.perdida, decimal("0.00"));
+    assert!(complemento.intereses.is_none());
+}
@@ -0,0 +1,72 @@
+use cfdi::rfc::{ErrorRfc, Rfc, TipoPersona};
+
+#[test]
+fn rfc_validos() {
+    for rfc in [
+        "EKU9003173C9",
+        "URE180429TM6",
+        "XOJI740919U48",
+        "VADA800927DJ3",
+        "CACX7605101P8",
+        "XAXX010101000",
+        "XEXX010101000",
+    ] {
+        assert_eq!(Rfc::new(rfc).validar(), Ok(()), "{rfc}");
+    }
+}
+
+#[test]
+fn tipo_de_persona_y_fecha() {
+    let moral: Rfc = "URE180429TM6".parse().unwrap();
+    assert_eq!(moral.tipo_persona(), Some(TipoPersona::Moral));
+    assert_eq!(moral.fecha(), Some((2018, 4, 29)));
+
+    let fisica: Rfc = "XOJI740919U48".parse().unwrap();
+    assert_eq!(fisica.tipo_persona(), Some(TipoPersona::Fisica));
+    assert_eq!(fisica.fecha(), Some((1974, 9, 19)));
+
+    let generico = Rfc::new("XAXX010101000");
+    assert!(generico.es_generico());
+    assert_eq!(generico.fecha(), None);
+}
+
+#[test]
+fn rfc_invalidos() {
+    assert_eq!(Rfc::new("EKU900317").validar(), Err(ErrorRfc::Longitud(9)));
+    assert_eq!(Rfc::new("EK19003173C9").validar(), Err(ErrorRfc::Formato));
+    assert_eq!(Rfc::new("eku9003173C9").validar(), Err(ErrorRfc::Formato));
+    assert_eq!(Rfc::new("EKU9002303C9").validar(), Err(ErrorRfc::Fecha));
+    assert_eq!(
+        "XOJI740919U47".parse::<Rfc>(),
+        Err(ErrorRfc::DigitoVerificador {
+            esperado: '8',
+            encontrado: '7'
+        })
+    );
+}
+
+#[test]
+fn fecha_en_anio_bisiesto() {
+    // RFC con el dígito verificador que le corresponde
+    let rfc = |clave: &str| {
+        let digito = Rfc::new(format!("{clave}0")).digito_verificador().unwrap();
+        Rfc::new(format!("{clave}{digito}"))
+    };
+
+    assert_eq!(rfc("XOJI000229U4").fecha(), Some((2000, 2, 29)));
+    assert_eq!(rfc("XOJI960229U4").fecha(), Some((1996, 2, 29)));
+    assert_eq!(rfc("XOJI970229U4").validar(), Err(ErrorRfc::Fecha));
+    assert_eq!(rfc("XOJI970229U4").fecha(), None);
+    assert_eq!(rfc("XOJI970431U4").validar(), Err(ErrorRfc::Fecha));
+}
+
+#[test]
+fn se_lee_sin_validar() {
+    let xml = std::fs::read_to_string("tests/data/ingreso.xml")
+        .unwrap()
+        .replace(r#"Rfc="URE180429TM6""#, r#"Rfc="URE180429TM5""#);
+    let cfdi = cfdi::parse_cfdi(&xml).unwrap();
+
+    assert_eq!(cfdi.receptor.rfc, "URE180429TM5");
+    assert!(!cfdi.receptor.rfc.es_valido());
+}
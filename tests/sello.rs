@@ -0,0 +1,234 @@
+use std::fs;
+
+use cfdi::rfc::Rfc;
+use cfdi::sello::{
+    verificar, Certificado, CertificadosSat, LlavePrivada, Verificacion, VerificacionTimbre,
+};
+use cfdi::{cadena_original, parse_cfdi, Comprobante};
+
+const CONTRASENA: &str = "12345678a";
+
+fn csd() -> (Certificado, LlavePrivada) {
+    let cer = fs::read("tests/data/csd/EKU9003173C9.cer").unwrap();
+    let key = fs::read("tests/data/csd/EKU9003173C9.key").unwrap();
+    (
+        Certificado::from_der(&cer).unwrap(),
+        LlavePrivada::from_der(&key, CONTRASENA).unwrap(),
+    )
+}
+
+fn sin_sellar() -> Comprobante {
+    parse_cfdi(&fs::read_to_string("tests/data/sin_sellar.xml").unwrap()).unwrap()
+}
+
+fn sellado(cfdi: &mut Comprobante) -> String {
+    let (certificado, llave) = csd();
+    cfdi.sellar(&certificado, &llave).unwrap();
+    cfdi.to_xml().unwrap()
+}
+
+#[test]
+fn no_certificado() {
+    let (certificado, llave) = csd();
+    assert_eq!(certificado.no_certificado(), "30001000000500003416");
+    assert_eq!(certificado.rfc().as_deref(), Some("EKU9003173C9"));
+    assert!(llave.corresponde_a(&certificado));
+
+    let releido = Certificado::from_base64(&certificado.to_base64()).unwrap();
+    assert_eq!(releido.no_certificado(), "30001000000500003416");
+}
+
+#[test]
+fn contrasena_incorrecta() {
+    let key = fs::read("tests/data/csd/EKU9003173C9.key").unwrap();
+    assert!(LlavePrivada::from_der(&key, "incorrecta").is_err());
+}
+
+#[test]
+fn sellar_comprobante() {
+    let (certificado, llave) = csd();
+    let xml = fs::read_to_string("tests/data/sin_sellar.xml").unwrap();
+    let mut cfdi = parse_cfdi(&xml).unwrap();
+    assert!(cfdi.sello.is_none());
+
+    cfdi.sellar(&certificado, &llave).unwrap();
+
+    // Firma generada con `openssl dgst -sha256 -sign` sobre la misma cadena original
+    let esperado = fs::read_to_string("tests/data/sin_sellar.sello").unwrap();
+    assert_eq!(cfdi.sello.as_deref(), Some(esperado.trim()));
+    assert_eq!(cfdi.no_certificado.as_deref(), Some("30001000000500003416"));
+    assert_eq!(cfdi.certificado, Some(certificado.to_base64()));
+
+    let sellado = cfdi.to_xml().unwrap();
+    assert!(sellado.contains(r#"Fecha="2024-05-20T13:45:10" Sello=""#));
+    assert_eq!(
+        cadena_original(&sellado).unwrap(),
+        cfdi.cadena_original().unwrap()
+    );
+
+    let releido = parse_cfdi(&sellado).unwrap();
+    assert_eq!(releido.sello, cfdi.sello);
+    assert_eq!(releido.certificado, cfdi.certificado);
+}
+
+#[test]
+fn verificar_sellado() {
+    let xml = sellado(&mut sin_sellar());
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido);
+}
+
+#[test]
+fn verificar_alterado() {
+    let xml = sellado(&mut sin_sellar()).replace(r#"Total="1160.00""#, r#"Total="1116.00""#);
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::SelloInvalido);
+}
+
+#[test]
+fn verificar_complemento_no_soportado() {
+    let xml = sellado(&mut sin_sellar()).replace(
+        "</cfdi:Comprobante>",
+        r#"<cfdi:Complemento><leyendasFisc:LeyendasFiscales xmlns:leyendasFisc="http://www.sat.gob.mx/leyendasFiscales" version="1.0"><leyendasFisc:Leyenda textoLeyenda="Leyenda"/></leyendasFisc:LeyendasFiscales></cfdi:Complemento></cfdi:Comprobante>"#,
+    );
+    assert_eq!(
+        verificar(&xml).unwrap(),
+        Verificacion::ComplementoNoSoportado("LeyendasFiscales".to_string())
+    );
+}
+
+#[test]
+fn verificar_complemento_concepto_no_soportado() {
+    let xml = sellado(&mut sin_sellar()).replace(
+        "</cfdi:Concepto>",
+        r#"<cfdi:ComplementoConcepto><iedu:instEducativas xmlns:iedu="http://www.sat.gob.mx/iedu" version="1.0" nombreAlumno="Juan Pérez" CURP="XOJI740919MDFDMN06" nivelEducativo="Primaria" autRVOE="1234567"/></cfdi:ComplementoConcepto></cfdi:Concepto>"#,
+    );
+    assert_eq!(
+        verificar(&xml).unwrap(),
+        Verificacion::ComplementoNoSoportado("instEducativas".to_string())
+    );
+}
+
+#[test]
+fn verificar_sin_sello() {
+    let xml = fs::read_to_string("tests/data/sin_sellar.xml").unwrap();
+    assert!(verificar(&xml).is_err());
+}
+
+#[test]
+fn verificar_no_certificado() {
+    let (_, llave) = csd();
+    let mut cfdi = sin_sellar();
+    sellado(&mut cfdi);
+    cfdi.no_certificado = Some("30001000000400002434".to_string());
+    cfdi.sello = Some(llave.sellar(&cfdi.cadena_original().unwrap()));
+
+    assert_eq!(
+        verificar(&cfdi.to_xml().unwrap()).unwrap(),
+        Verificacion::CertificadoNoCorresponde {
+            no_certificado: "30001000000400002434".to_string(),
+            certificado: "30001000000500003416".to_string(),
+        }
+    );
+}
+
+#[test]
+fn verificar_rfc() {
+    let mut cfdi = sin_sellar();
+    cfdi.emisor.rfc = Rfc::new("XIA190128J61");
+    let xml = sellado(&mut cfdi);
+
+    assert_eq!(
+        verificar(&xml).unwrap(),
+        Verificacion::RfcNoCorresponde {
+            emisor: "XIA190128J61".to_string(),
+            certificado: "EKU9003173C9".to_string(),
+        }
+    );
+}
+
+#[test]
+fn verificar_vigencia() {
+    let mut cfdi = sin_sellar();
+    cfdi.fecha = "2027-06-01T09:00:00".parse().unwrap();
+    let xml = sellado(&mut cfdi);
+
+    let Verificacion::CertificadoVencido { fecha, inicio, fin } = verificar(&xml).unwrap() else {
+        panic!("El certificado debería estar vencido");
+    };
+    assert_eq!(fecha.to_rfc3339(), "2027-06-01T09:00:00-06:00");
+    assert_eq!(inicio.to_rfc3339(), "2023-05-18T00:00:00+00:00");
+    assert_eq!(fin.to_rfc3339(), "2027-05-18T00:00:00+00:00");
+}
+
+fn timbrado() -> (String, Comprobante) {
+    let xml = fs::read_to_string("tests/data/timbrado.xml").unwrap();
+    let cfdi = parse_cfdi(&xml).unwrap();
+    (xml, cfdi)
+}
+
+fn certificados_sat() -> CertificadosSat {
+    CertificadosSat::leer_directorio("tests/data/sat").unwrap()
+}
+
+#[test]
+fn cadena_original_timbre() {
+    let (_, cfdi) = timbrado();
+    let timbre = cfdi.complemento.unwrap().timbre_fiscal_digital.unwrap();
+    assert_eq!(
+        timbre.cadena_original(),
+        format!(
+            "||1.1|5F3B2E10-6A4C-4D8E-9B7A-1C2D3E4F5A6B|2024-05-20T13:46:02|SPR190613I52|{}|00001000000509846663||",
+            cfdi.sello.unwrap()
+        )
+    );
+}
+
+#[test]
+fn verificar_timbrado() {
+    let (xml, cfdi) = timbrado();
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido);
+    assert_eq!(
+        cfdi.verificar_timbre(&certificados_sat()).unwrap(),
+        VerificacionTimbre::Valido
+    );
+}
+
+#[test]
+fn verificar_timbre_sin_certificado() {
+    let (_, cfdi) = timbrado();
+    assert_eq!(
+        cfdi.verificar_timbre(&CertificadosSat::new()).unwrap(),
+        VerificacionTimbre::CertificadoSatDesconocido {
+            no_certificado_sat: "00001000000509846663".to_string()
+        }
+    );
+}
+
+#[test]
+fn verificar_timbre_alterado() {
+    let (xml, _) = timbrado();
+    let xml = xml.replace("2024-05-20T13:46:02", "2024-05-21T13:46:02");
+    let cfdi = parse_cfdi(&xml).unwrap();
+    assert_eq!(
+        cfdi.verificar_timbre(&certificados_sat()).unwrap(),
+        VerificacionTimbre::SelloSatInvalido
+    );
+}
+
+#[test]
+fn verificar_timbre_de_otro_comprobante() {
+    let (_, mut cfdi) = timbrado();
+    let mut otro = sin_sellar();
+    otro.total = "1000.00".parse().unwrap();
+    sellado(&mut otro);
+    cfdi.sello = otro.sello;
+
+    assert_eq!(
+        cfdi.verificar_timbre(&certificados_sat()).unwrap(),
+        VerificacionTimbre::SelloCfdNoCorresponde
+    );
+}
+
+#[test]
+fn verificar_timbre_sin_timbrar() {
+    assert!(sin_sellar().verificar_timbre(&certificados_sat()).is_err());
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use cfdi::sello::{verificar, Certificado, Verificacion};
+use cfdi::{cadena_original, parse_cfdi, parse_cfdi_sin_perdida, Decimal};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// Los comprobantes 3.2 no se pueden escribir, por lo que `cfdi32` no está en el corpus
+const CORPUS: [&str; 14] = [
+    "addenda",
+    "cartaporte20",
+    "cartaporte30",
+    "cfdi33",
+    "comercio_exterior",
+    "impuestos_locales",
+    "ingreso",
+    "nomina",
+    "pago",
+    "pago10",
+    "pago33",
+    "sin_perdida",
+    "sin_sellar",
+    "timbrado",
+];
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+/// Atributos de cada nodo del documento, por su ruta (ej. `Comprobante/Conceptos[1]/Concepto[2]`).
+/// No incluye las declaraciones de namespaces, que pueden cambiar de nodo al escribirse.
+fn atributos(xml: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut nodos = BTreeMap::new();
+    let mut pila: Vec<(String, HashMap<String, usize>)> = vec![];
+    loop {
+        let (e, vacio) = match reader.read_event().unwrap() {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(_) => {
+                pila.pop();
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let nombre = String::from_utf8(e.local_name().as_ref().to_vec()).unwrap();
+        let ruta = match pila.last_mut() {
+            Some((padre, hijos)) => {
+                let n = hijos.entry(nombre.clone()).or_default();
+                *n += 1;
+                format!("{padre}/{nombre}[{n}]")
+            }
+            None => nombre,
+        };
+        let valores = e
+            .attributes()
+            .map(Result::unwrap)
+            .filter(|a| !a.key.as_ref().starts_with(b"xmlns"))
+            .map(|a| {
+                let clave = String::from_utf8(a.key.as_ref().to_vec()).unwrap();
+                (clave, a.unescape_value().unwrap().into_owned())
+            })
+            .collect();
+        nodos.insert(ruta.clone(), valores);
+        if !vacio {
+            pila.push((ruta, HashMap::new()));
+        }
+    }
+    nodos
+}
+
+#[test]
+fn mismos_atributos() {
+    for nombre in CORPUS {
+        let xml = leer(nombre);
+        let generado = parse_cfdi_sin_perdida(&xml).unwrap().to_xml().unwrap();
+        let originales = atributos(&xml);
+        let generados = atributos(&generado);
+
+        for (ruta, valores) in &originales {
+            assert_eq!(generados.get(ruta), Some(valores), "{nombre}: {ruta}");
+        }
+        assert_eq!(
+            generados.keys().collect::<Vec<_>>(),
+            originales.keys().collect::<Vec<_>>(),
+            "{nombre}"
+        );
+    }
+}
+
+#[test]
+fn complementos_no_soportados() {
+    let xml = leer("impuestos_locales");
+    let cadena = fs::read_to_string("tests/data/impuestos_locales.txt").unwrap();
+    let cfdi = parse_cfdi_sin_perdida(&xml).unwrap();
+    let certificado = Certificado::from_base64(cfdi.certificado.as_deref().unwrap()).unwrap();
+    assert!(certificado.verificar(&cadena, cfdi.sello.as_deref().unwrap()));
+
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(r#"<implocal:TrasladosLocales ImpLocTrasladado="ISH""#));
+    assert!(generado.contains(r#"textoLeyenda="Impuesto sobre hospedaje del Estado de Jalisco""#));
+    assert_eq!(
+        verificar(&generado).unwrap(),
+        Verificacion::ComplementoNoSoportado("ImpuestosLocales".to_string())
+    );
+}
+
+#[test]
+fn cfdi_32_no_se_escribe() {
+    let xml = leer("cfdi32");
+    let cfdi = parse_cfdi_sin_perdida(&xml).unwrap();
+    assert!(cfdi.forma_original.is_none());
+    assert!(cfdi.to_xml().is_err());
+    let error = cadena_original(&xml).unwrap_err();
+    assert!(error.to_string().ends_with(": 3.2"), "{error}");
+}
+
+#[test]
+fn misma_cadena_original() {
+    for nombre in CORPUS {
+        let xml = leer(nombre);
+        let generado = parse_cfdi_sin_perdida(&xml).unwrap().to_xml().unwrap();
+
+        assert_eq!(
+            cadena_original(&generado).unwrap(),
+            cadena_original(&xml).unwrap(),
+            "{nombre}"
+        );
+    }
+}
+
+#[test]
+fn sello_sigue_valido() {
+    let xml = leer("sin_perdida");
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido);
+
+    let cfdi = parse_cfdi(&xml).unwrap();
+    assert_eq!(cfdi.serie.as_deref(), Some("G"));
+    assert_eq!(cfdi.folio.as_deref(), Some("00873"));
+    assert_eq!(cfdi.condiciones_de_pago.as_deref(), Some("Contado"));
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(r#"Serie="G" Folio="00873" Fecha="#));
+    assert_eq!(verificar(&generado).unwrap(), Verificacion::SelloInvalido);
+
+    let generado = parse_cfdi_sin_perdida(&xml).unwrap().to_xml().unwrap();
+    assert_eq!(verificar(&generado).unwrap(), Verificacion::Valido);
+    assert!(generado.contains(r#"Cantidad="001.50""#));
+    assert!(generado.contains(r#"Folio="00873""#));
+    assert!(generado.contains(r#"NumeroPedimento="23  47  3807  8003832""#));
+}
+
+#[test]
+fn valores_modificados() {
+    let xml = leer("sin_perdida");
+    let mut cfdi = parse_cfdi_sin_perdida(&xml).unwrap();
+    cfdi.conceptos.concepto[0].cantidad = "2".parse::<Decimal>().unwrap();
+    cfdi.conceptos.concepto[1].descripcion = "Mantenimiento".to_string();
+
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(r#"Cantidad="2""#));
+    assert!(!generado.contains("001.50"));
+    assert!(generado.contains(r#"Descripcion="Mantenimiento""#));
+    assert!(generado.contains(r#"Folio="00873""#));
+    assert_eq!(verificar(&generado).unwrap(), Verificacion::SelloInvalido);
+}
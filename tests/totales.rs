@@ -0,0 +1,301 @@
+use std::fs;
+
+use cfdi::totales::Diferencia;
+use cfdi::{parse_cfdi, Decimal};
+
+fn leer_xml(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+fn diferencia(ruta: &str, atributo: &'static str, declarado: &str, calculado: &str) -> Diferencia {
+    let valor = |v: &str| (!v.is_empty()).then(|| decimal(v));
+    Diferencia {
+        ruta: ruta.to_string(),
+        atributo,
+        declarado: valor(declarado),
+        calculado: valor(calculado),
+    }
+}
+
+#[test]
+fn comprobantes_correctos() {
+    for nombre in [
+        "addenda",
+        "cfdi33",
+        "impuestos_locales",
+        "ingreso",
+        "nomina",
+        "pago",
+        "pago33",
+        "sin_perdida",
+        "sin_sellar",
+        "timbrado",
+    ] {
+        let cfdi = parse_cfdi(&leer_xml(nombre)).unwrap();
+        assert_eq!(cfdi.recalculate_totals(), vec![], "{nombre}");
+
+        let mut recalculado = cfdi.clone();
+        assert_eq!(recalculado.update_totals(), vec![], "{nombre}");
+        assert_eq!(
+            recalculado.cadena_original().unwrap(),
+            cfdi.cadena_original().unwrap(),
+            "{nombre}"
+        );
+    }
+}
+
+#[test]
+fn totales_del_comprobante() {
+    let xml = leer_xml("ingreso")
+        .replace(r#"Total="2514.00""#, r#"Total="2500.00""#)
+        .replace(
+            r#"<cfdi:Impuestos TotalImpuestosRetenidos="190.00" TotalImpuestosTrasladados="304.00">"#,
+            r#"<cfdi:Impuestos TotalImpuestosTrasladados="320.00">"#,
+        )
+        .replace(
+            r#"<cfdi:Traslado Base="1900.00" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="304.00"/>
+      <cfdi:Traslado Base="500.00" Impuesto="002" TipoFactor="Exento"/>"#,
+            r#"<cfdi:Traslado Base="500.00" Impuesto="002" TipoFactor="Exento"/>
+      <cfdi:Traslado Base="2000.00" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="320.00"/>
+      <cfdi:Traslado Base="10.00" Impuesto="003" TipoFactor="Tasa" TasaOCuota="0.080000" Importe="0.80"/>"#,
+        );
+    let mut cfdi = parse_cfdi(&xml).unwrap();
+    let diferencias = cfdi.recalculate_totals();
+    assert_eq!(cfdi.total, decimal("2500.00"));
+
+    assert_eq!(
+        diferencias,
+        [
+            diferencia("Comprobante", "Total", "2500.00", "2514.00"),
+            diferencia(
+                "Comprobante/Impuestos",
+                "TotalImpuestosRetenidos",
+                "",
+                "190.00"
+            ),
+            diferencia(
+                "Comprobante/Impuestos",
+                "TotalImpuestosTrasladados",
+                "320.00",
+                "304.00"
+            ),
+            diferencia(
+                "Comprobante/Impuestos/Traslados/Traslado[2]",
+                "Base",
+                "2000.00",
+                "1900.00"
+            ),
+            diferencia(
+                "Comprobante/Impuestos/Traslados/Traslado[2]",
+                "Importe",
+                "320.00",
+                "304.00"
+            ),
+            diferencia(
+                "Comprobante/Impuestos/Traslados/Traslado[3]",
+                "Base",
+                "10.00",
+                ""
+            ),
+            diferencia(
+                "Comprobante/Impuestos/Traslados/Traslado[3]",
+                "Importe",
+                "0.80",
+                ""
+            ),
+        ]
+    );
+    assert_eq!(cfdi.update_totals(), diferencias);
+
+    let original = parse_cfdi(&leer_xml("ingreso")).unwrap();
+    assert_eq!(cfdi.validate(), vec![]);
+    assert_eq!(cfdi.total, original.total);
+    assert_eq!(
+        cfdi.cadena_original().unwrap(),
+        original.cadena_original().unwrap()
+    );
+}
+
+#[test]
+fn importes_de_conceptos_con_tolerancia() {
+    let xml = leer_xml("ingreso")
+        .replace(r#"Cantidad="2.5""#, r#"Cantidad="2.500000""#)
+        .replace(r#"Importe="500.00""#, r#"Importe="500.10""#)
+        .replace(
+            r#"TasaOCuota="0.100000" Importe="190.00""#,
+            r#"TasaOCuota="0.100000" Importe="190.05""#,
+        );
+    let mut cfdi = parse_cfdi(&xml).unwrap();
+    let diferencias = cfdi.update_totals();
+
+    // 2.500000 x 200.00 permite de 499.98 a 500.02; 1900.00 x 0.10, de 189.99 a 190.01
+    assert_eq!(
+        diferencias[..2],
+        [
+            diferencia(
+                "Comprobante/Conceptos/Concepto[1]/Impuestos/Retenciones/Retencion[1]",
+                "Importe",
+                "190.05",
+                "190.00"
+            ),
+            diferencia(
+                "Comprobante/Conceptos/Concepto[2]",
+                "Importe",
+                "500.10",
+                "500.00"
+            ),
+        ]
+    );
+    assert_eq!(cfdi.subtotal, decimal("2500.10"));
+    assert_eq!(cfdi.conceptos.concepto[1].importe, decimal("500.10"));
+}
+
+#[test]
+fn redondear_a_los_decimales_de_la_moneda() {
+    let concepto = r#"<cfdi:Concepto ClaveProdServ="01010101" Cantidad="1" ClaveUnidad="H87"
+        Descripcion="Producto" ValorUnitario="10.333333" Importe="10.333333" ObjetoImp="02">
+      <cfdi:Impuestos>
+        <cfdi:Traslados>
+          <cfdi:Traslado Base="10.333333" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="1.653333"/>
+        </cfdi:Traslados>
+      </cfdi:Impuestos>
+    </cfdi:Concepto>"#;
+    let xml = format!(
+        r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+    Fecha="2024-01-15T10:00:00" FormaPago="03" SubTotal="0" Moneda="MXN" Total="0"
+    TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+  <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+  <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+      DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+  <cfdi:Conceptos>{concepto}{concepto}{concepto}</cfdi:Conceptos>
+</cfdi:Comprobante>"#
+    );
+    let mut cfdi = parse_cfdi(&xml).unwrap();
+    cfdi.update_totals();
+
+    assert_eq!(cfdi.subtotal.to_string(), "31.00");
+    let impuestos = cfdi.impuestos.as_ref().unwrap();
+    let traslado = &impuestos.get_traslados()[0];
+    assert_eq!(traslado.base.unwrap().to_string(), "31.00");
+    assert_eq!(traslado.importe.unwrap().to_string(), "4.96");
+    assert_eq!(cfdi.total.to_string(), "35.96");
+
+    cfdi.moneda = "JPY".parse().unwrap();
+    cfdi.tipo_cambio = Some(decimal("0.12"));
+    cfdi.update_totals();
+    assert_eq!(cfdi.subtotal.to_string(), "31");
+    assert_eq!(cfdi.total.to_string(), "36");
+}
+
+#[test]
+fn recalcular_sin_modificar() {
+    let xml = leer_xml("ingreso").replace(r#"Total="2514.00""#, r#"Total="2500.00""#);
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let original = cfdi.clone();
+
+    assert_eq!(
+        cfdi.recalculate_totals(),
+        [diferencia("Comprobante", "Total", "2500.00", "2514.00")]
+    );
+    assert_eq!(cfdi.total, original.total);
+    assert_eq!(
+        cfdi.cadena_original().unwrap(),
+        original.cadena_original().unwrap()
+    );
+}
+
+#[test]
+fn totales_con_impuestos_locales() {
+    // 2000.00 + 320.00 de IVA + 60.00 de ISH
+    let cfdi = parse_cfdi(&leer_xml("impuestos_locales")).unwrap();
+    assert_eq!(cfdi.total, decimal("2380.00"));
+    assert_eq!(cfdi.recalculate_totals(), vec![]);
+
+    let xml = leer_xml("impuestos_locales").replace(
+        r#"TotaldeRetenciones="0.00""#,
+        r#"TotaldeRetenciones="40.00""#,
+    );
+    let mut cfdi = parse_cfdi(&xml).unwrap();
+    assert_eq!(
+        cfdi.recalculate_totals(),
+        [diferencia("Comprobante", "Total", "2380.00", "2340.00")]
+    );
+    cfdi.update_totals();
+    assert_eq!(cfdi.total, decimal("2340.00"));
+
+    // Un Total sin los 25.00 de ISH del documento de la addenda
+    let xml = leer_xml("addenda").replace(r#"Total="2539.00""#, r#"Total="2514.00""#);
+    assert_eq!(
+        parse_cfdi(&xml).unwrap().recalculate_totals(),
+        [diferencia("Comprobante", "Total", "2514.00", "2539.00")]
+    );
+}
+
+#[test]
+fn totales_del_comprobante_exactos() {
+    let xml = leer_xml("ingreso")
+        .replace(r#"SubTotal="2500.00""#, r#"SubTotal="2500.01""#)
+        .replace(r#"Total="2514.00""#, r#"Total="2514.01""#);
+    assert_eq!(
+        parse_cfdi(&xml).unwrap().recalculate_totals(),
+        [
+            diferencia("Comprobante", "SubTotal", "2500.01", "2500.00"),
+            diferencia("Comprobante", "Total", "2514.01", "2514.00"),
+        ]
+    );
+
+    // Tres traslados de 0.1648 suman 0.4944, que se declara redondeado a 0.49 y no como la
+    // suma de los importes redondeados (0.48)
+    let concepto = r#"<cfdi:Concepto ClaveProdServ="01010101" Cantidad="1" ClaveUnidad="H87"
+        Descripcion="Producto" ValorUnitario="1.03" Importe="1.03" ObjetoImp="02">
+      <cfdi:Impuestos>
+        <cfdi:Traslados>
+          <cfdi:Traslado Base="1.03" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="0.1648"/>
+        </cfdi:Traslados>
+      </cfdi:Impuestos>
+    </cfdi:Concepto>"#;
+    let comprobante = |trasladados: &str, total: &str| {
+        format!(
+            r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" Version="4.0"
+    Fecha="2024-01-15T10:00:00" FormaPago="03" SubTotal="3.09" Moneda="MXN" Total="{total}"
+    TipoDeComprobante="I" Exportacion="01" MetodoPago="PUE" LugarExpedicion="44100">
+  <cfdi:Emisor Rfc="EKU9003173C9" Nombre="ESCUELA KEMPER URGATE" RegimenFiscal="601"/>
+  <cfdi:Receptor Rfc="URE180429TM6" Nombre="UNIVERSIDAD ROBOTICA ESPAÑOLA"
+      DomicilioFiscalReceptor="65000" RegimenFiscalReceptor="601" UsoCFDI="G03"/>
+  <cfdi:Conceptos>{concepto}{concepto}{concepto}</cfdi:Conceptos>
+  <cfdi:Impuestos TotalImpuestosTrasladados="{trasladados}">
+    <cfdi:Traslados>
+      <cfdi:Traslado Base="3.09" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="{trasladados}"/>
+    </cfdi:Traslados>
+  </cfdi:Impuestos>
+</cfdi:Comprobante>"#
+        )
+    };
+
+    let cfdi = parse_cfdi(&comprobante("0.49", "3.58")).unwrap();
+    assert_eq!(cfdi.recalculate_totals(), vec![]);
+
+    let cfdi = parse_cfdi(&comprobante("0.48", "3.57")).unwrap();
+    assert_eq!(
+        cfdi.recalculate_totals(),
+        [
+            diferencia("Comprobante", "Total", "3.57", "3.58"),
+            diferencia(
+                "Comprobante/Impuestos",
+                "TotalImpuestosTrasladados",
+                "0.48",
+                "0.49"
+            ),
+            diferencia(
+                "Comprobante/Impuestos/Traslados/Traslado[1]",
+                "Importe",
+                "0.48",
+                "0.49"
+            ),
+        ]
+    );
+}
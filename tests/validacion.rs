@@ -0,0 +1,180 @@
+use std::fs;
+
+use cfdi::{parse_cfdi, Comprobante};
+
+fn leer(nombre: &str) -> Comprobante {
+    parse_cfdi(&fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()).unwrap()
+}
+
+fn claves(cfdi: &Comprobante) -> Vec<&'static str> {
+    cfdi.validate().iter().map(|e| e.codigo).collect()
+}
+
+#[test]
+fn comprobantes_validos() {
+    for nombre in [
+        "addenda",
+        "impuestos_locales",
+        "ingreso",
+        "nomina",
+        "pago",
+        "sin_sellar",
+        "timbrado",
+    ] {
+        assert_eq!(leer(nombre).validate(), vec![], "{nombre}");
+    }
+}
+
+#[test]
+fn totales() {
+    let mut cfdi = leer("ingreso");
+    cfdi.subtotal = "2600.00".parse().unwrap();
+    cfdi.descuento = Some("2700.001".parse().unwrap());
+    assert_eq!(
+        claves(&cfdi),
+        ["CFDI40108", "CFDI40110", "CFDI40111", "CFDI40118"]
+    );
+}
+
+#[test]
+fn tipo_cambio() {
+    let mut cfdi = leer("ingreso");
+    cfdi.tipo_cambio = Some("17.5".parse().unwrap());
+    assert_eq!(claves(&cfdi), ["CFDI40113"]);
+
+    cfdi.moneda = "USD".parse().unwrap();
+    cfdi.tipo_cambio = None;
+    assert_eq!(claves(&cfdi), ["CFDI40114"]);
+}
+
+#[test]
+fn decimales_de_la_moneda() {
+    let mut cfdi = leer("ingreso");
+    cfdi.subtotal = "2500.000".parse().unwrap();
+    assert_eq!(claves(&cfdi), ["CFDI40107"]);
+
+    cfdi.moneda = "KWD".parse().unwrap();
+    cfdi.tipo_cambio = Some("55.4321".parse().unwrap());
+    assert_eq!(claves(&cfdi), Vec::<&str>::new());
+
+    cfdi.subtotal = "2500.0000".parse().unwrap();
+    assert_eq!(claves(&cfdi), ["CFDI40107"]);
+}
+
+#[test]
+fn importes_de_conceptos_e_impuestos() {
+    let xml = fs::read_to_string("tests/data/ingreso.xml")
+        .unwrap()
+        .replace(
+            r#"TasaOCuota="0.160000" Importe="304.00"/>
+        </cfdi:Traslados>"#,
+            r#"TasaOCuota="0.160000" Importe="304.02"/>
+        </cfdi:Traslados>"#,
+        )
+        .replace(r#"ValorUnitario="200.00""#, r#"ValorUnitario="150.00""#);
+    let errores = parse_cfdi(&xml).unwrap().validate();
+
+    let rutas: Vec<_> = errores
+        .iter()
+        .map(|e| (e.codigo, e.ruta.as_str()))
+        .collect();
+    assert_eq!(
+        rutas,
+        [
+            (
+                "CFDI40179",
+                "Comprobante/Conceptos/Concepto[1]/Impuestos/Traslados/Traslado[1]"
+            ),
+            ("CFDI40166", "Comprobante/Conceptos/Concepto[2]"),
+            ("CFDI40208", "Comprobante/Impuestos"),
+        ]
+    );
+}
+
+#[test]
+fn tolerancia_de_redondeo() {
+    // 333.33 x 0.16 = 53.3328, se acepta 53.33
+    let xml = fs::read_to_string("tests/data/ingreso.xml").unwrap().replace(
+        r#"<cfdi:Traslado Base="500.00" Impuesto="002" TipoFactor="Exento"/>
+        </cfdi:Traslados>
+      </cfdi:Impuestos>
+    </cfdi:Concepto>"#,
+        r#"<cfdi:Traslado Base="333.33" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="53.33"/>
+        </cfdi:Traslados>
+      </cfdi:Impuestos>
+    </cfdi:Concepto>"#,
+    );
+    let codigos: Vec<_> = parse_cfdi(&xml)
+        .unwrap()
+        .validate()
+        .into_iter()
+        .map(|e| e.codigo)
+        .collect();
+    assert_eq!(codigos, ["CFDI40208"]);
+}
+
+#[test]
+fn totales_de_impuestos_redondeados() {
+    // 3 x 0.1648 = 0.4944, que se declara redondeado a 0.49
+    let xml = fs::read_to_string("tests/data/sin_sellar.xml").unwrap();
+    let inicio = xml.find("<cfdi:Concepto ").unwrap();
+    let fin = xml.find("</cfdi:Concepto>").unwrap() + "</cfdi:Concepto>".len();
+    let concepto = xml[inicio..fin]
+        .replace("1000.00", "1.03")
+        .replace(r#"Importe="160.00""#, r#"Importe="0.1648""#);
+    let xml = format!(
+        "{}{}{}",
+        &xml[..inicio],
+        [concepto.as_str(); 3].join("\n    "),
+        &xml[fin..]
+    )
+    .replace(
+        r#"SubTotal="1000.00" Moneda="MXN" Total="1160.00""#,
+        r#"SubTotal="3.09" Moneda="MXN" Total="3.58""#,
+    )
+    .replace("1000.00", "3.09")
+    .replace("160.00", "0.49");
+    let cfdi = parse_cfdi(&xml).unwrap();
+
+    assert_eq!(cfdi.validate(), vec![]);
+    assert_eq!(cfdi.recalculate_totals(), vec![]);
+}
+
+#[test]
+fn objeto_de_impuesto_y_regimen() {
+    let mut cfdi = leer("nomina");
+    cfdi.conceptos.concepto[0].objeto_imp = Some("02".parse().unwrap());
+    cfdi.emisor.regimen_fiscal = "612".parse().unwrap();
+    cfdi.receptor.regimen_fiscal = Some("601".parse().unwrap());
+    assert_eq!(claves(&cfdi), ["CFDI40138", "CFDI40157", "CFDI40169"]);
+}
+
+#[test]
+fn pago() {
+    let mut cfdi = leer("pago");
+    cfdi.forma_pago = Some("03".parse().unwrap());
+    cfdi.metodo_pago = Some("PUE".parse().unwrap());
+    cfdi.subtotal = "1".parse().unwrap();
+    assert_eq!(
+        claves(&cfdi),
+        ["CFDI40103", "CFDI40109", "CFDI40118", "CFDI40124"]
+    );
+}
+
+#[test]
+fn total_con_impuestos_locales() {
+    // Un Total sin los 25.00 de ISH del documento de la addenda
+    let xml = fs::read_to_string("tests/data/addenda.xml")
+        .unwrap()
+        .replace(r#"Total="2539.00""#, r#"Total="2514.00""#);
+    let errores = parse_cfdi(&xml).unwrap().validate();
+    assert_eq!(errores.len(), 1);
+    assert_eq!(errores[0].codigo, "CFDI40118");
+    assert!(errores[0].mensaje.ends_with("(2539.00)"));
+}
+
+#[test]
+fn impuestos_de_cfdi_32() {
+    // Los conceptos 3.2 no tienen impuestos, así que no se comparan los totales del comprobante
+    assert_eq!(leer("cfdi32").validate(), vec![]);
+}
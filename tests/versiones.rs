@@ -0,0 +1,164 @@
+use std::fs;
+
+use cfdi::catalogos::{
+    FormaPago, Impuesto, MetodoPago, Moneda, RegimenFiscal, TipoDeComprobante, TipoFactor, UsoCfdi,
+};
+use cfdi::sello::{verificar, Verificacion};
+use cfdi::{cadena_original, parse_cfdi, Decimal, ErrorLectura};
+
+fn leer(nombre: &str) -> String {
+    fs::read_to_string(format!("tests/data/{nombre}.xml")).unwrap()
+}
+
+fn decimal(valor: &str) -> Decimal {
+    valor.parse().unwrap()
+}
+
+#[test]
+fn leer_cfdi_33() {
+    let cfdi = parse_cfdi(&leer("cfdi33")).unwrap();
+
+    assert_eq!(cfdi.version, "3.3");
+    assert_eq!(cfdi.exportacion, None);
+    assert_eq!(cfdi.receptor.nombre, None);
+    assert_eq!(cfdi.receptor.domicilio_fiscal, None);
+    assert_eq!(cfdi.receptor.regimen_fiscal, None);
+    assert_eq!(cfdi.receptor.uso_cfdi, UsoCfdi::PorDefinir);
+
+    let concepto = &cfdi.conceptos.concepto[0];
+    assert_eq!(concepto.objeto_imp, None);
+    let traslado = &concepto.impuestos.as_ref().unwrap().get_traslados()[0];
+    assert_eq!(traslado.base, Some(decimal("1900.00")));
+
+    let traslado = &cfdi.impuestos.as_ref().unwrap().get_traslados()[0];
+    assert_eq!(traslado.base, None);
+    assert_eq!(traslado.importe, Some(decimal("304.00")));
+}
+
+#[test]
+fn escribir_y_sellar_cfdi_33() {
+    let xml = leer("cfdi33");
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let esperada = fs::read_to_string("tests/data/cfdi33.txt").unwrap();
+
+    assert_eq!(cadena_original(&xml).unwrap(), esperada);
+    assert_eq!(cfdi.cadena_original().unwrap(), esperada);
+    assert_eq!(verificar(&xml).unwrap(), Verificacion::Valido);
+
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(r#"xmlns:cfdi="http://www.sat.gob.mx/cfd/3""#));
+    assert!(generado.contains("cfd/3/cfdv33.xsd"));
+    assert!(!generado.contains("Exportacion"));
+}
+
+#[test]
+fn pagos_10() {
+    let cfdi = parse_cfdi(&leer("pago33")).unwrap();
+    let pagos = cfdi.complemento.as_ref().unwrap().pagos.as_ref().unwrap();
+
+    assert_eq!(pagos.version, "1.0");
+    assert!(pagos.totales.is_none());
+    let docto = &pagos.pago[0].docto_relacionado[0];
+    assert_eq!(
+        docto.metodo_de_pago,
+        Some(MetodoPago::PagoEnParcialidadesODiferido)
+    );
+    assert_eq!(docto.objeto_imp, None);
+    assert_eq!(docto.imp_saldo_insoluto, Some(decimal("0.00")));
+
+    let generado = cfdi.to_xml().unwrap();
+    assert!(generado.contains(r#"xmlns:pago10="http://www.sat.gob.mx/Pagos""#));
+    assert!(generado.contains(r#"<pago10:DoctoRelacionado IdDocumento="#));
+    assert!(!generado.contains("Totales"));
+}
+
+#[test]
+fn pagos_10_sin_parcialidad() {
+    let xml = leer("pago10");
+    let cfdi = parse_cfdi(&xml).unwrap();
+    let pagos = cfdi.complemento.as_ref().unwrap().pagos.as_ref().unwrap();
+
+    let docto = &pagos.pago[0].docto_relacionado[0];
+    assert_eq!(docto.metodo_de_pago, Some(MetodoPago::PagoEnUnaExhibicion));
+    assert_eq!(docto.num_parcialidad, None);
+    assert_eq!(docto.imp_saldo_ant, None);
+    assert_eq!(docto.imp_pagado, None);
+    assert_eq!(docto.imp_saldo_insoluto, None);
+
+    let esperada = fs::read_to_string("tests/data/pago10.txt").unwrap();
+    assert_eq!(cadena_original(&xml).unwrap(), esperada);
+    let generado = cfdi.to_xml().unwrap();
+    assert!(!generado.contains("NumParcialidad"));
+    assert_eq!(cadena_original(&generado).unwrap(), esperada);
+}
+
+#[test]
+fn pagos_20_requieren_parcialidad() {
+    let xml = leer("pago").replace(r#" NumParcialidad="1""#, "");
+    let error = parse_cfdi(&xml).unwrap_err();
+    assert_eq!(
+        error.ruta(),
+        "Comprobante/Complemento/Pagos/Pago[1]/DoctoRelacionado[1]"
+    );
+    assert!(matches!(
+        error,
+        ErrorLectura::AtributoFaltante { atributo, .. } if atributo == "NumParcialidad"
+    ));
+}
+
+#[test]
+fn leer_cfdi_32() {
+    let cfdi = parse_cfdi(&leer("cfdi32")).unwrap();
+
+    assert_eq!(cfdi.version, "3.2");
+    assert_eq!(cfdi.serie.as_deref(), Some("A"));
+    assert_eq!(cfdi.folio.as_deref(), Some("523"));
+    assert_eq!(cfdi.fecha.to_string(), "2016-11-03 17:20:11");
+    assert_eq!(cfdi.tipo_comprobante, TipoDeComprobante::Ingreso);
+    assert_eq!(cfdi.forma_pago, Some(FormaPago::TransferenciaElectronica));
+    assert_eq!(cfdi.metodo_pago, Some(MetodoPago::PagoEnUnaExhibicion));
+    assert_eq!(cfdi.moneda, Moneda::Mxn);
+    assert_eq!(cfdi.total, decimal("1680.00"));
+
+    assert_eq!(cfdi.emisor.rfc, "EKU9003173C9");
+    assert_eq!(
+        cfdi.emisor.regimen_fiscal,
+        RegimenFiscal::Unknown("Régimen General de Ley Personas Morales".to_string())
+    );
+    assert_eq!(
+        cfdi.receptor.nombre.as_deref(),
+        Some("UNIVERSIDAD ROBOTICA ESPAÑOLA")
+    );
+
+    let concepto = &cfdi.conceptos.concepto[0];
+    assert_eq!(concepto.cantidad, decimal("3"));
+    assert_eq!(concepto.unidad.as_deref(), Some("Pieza"));
+    assert_eq!(concepto.importe, decimal("1500.00"));
+
+    let impuestos = cfdi.impuestos.as_ref().unwrap();
+    let traslado = &impuestos.get_traslados()[0];
+    assert_eq!(traslado.impuesto, Impuesto::Iva);
+    assert_eq!(traslado.tipo_factor, TipoFactor::Tasa);
+    assert_eq!(traslado.tasa_o_cuota, Some(decimal("0.16")));
+    assert_eq!(impuestos.get_retenciones()[0].impuesto, Impuesto::Isr);
+
+    assert_eq!(
+        cfdi.get_uuid().as_deref(),
+        Some("1A2B3C4D-5E6F-4A7B-8C9D-0E1F2A3B4C5D")
+    );
+    let tfd = cfdi.complemento.as_ref().unwrap();
+    let tfd = tfd.timbre_fiscal_digital.as_ref().unwrap();
+    assert_eq!(
+        tfd.cadena_original(),
+        "||1.0|1A2B3C4D-5E6F-4A7B-8C9D-0E1F2A3B4C5D|2016-11-03T17:21:02|AAAA|00001000000202864883||"
+    );
+}
+
+#[test]
+fn cfdi_32_solo_lectura() {
+    let xml = leer("cfdi32");
+    let cfdi = parse_cfdi(&xml).unwrap();
+
+    assert!(cfdi.to_xml().is_err());
+    assert!(cadena_original(&xml).is_err());
+}